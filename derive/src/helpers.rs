@@ -1,4 +1,8 @@
-pub(crate) mod instance;
+pub(crate) mod defaults;
+pub(crate) mod fields;
+pub(crate) mod indexes;
 pub(crate) mod relations;
 pub(crate) mod schema_id;
+pub(crate) mod timestamps;
 pub(crate) mod unique_fields;
+pub(crate) mod validation;
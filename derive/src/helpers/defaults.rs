@@ -0,0 +1,44 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::schema::data::SchemaData;
+
+pub(crate) fn getter(schema_data: &SchemaData) -> TokenStream {
+  let nongoose = crate::utils::crates::get_nongoose_crate_name();
+
+  if schema_data.defaults.is_empty() {
+    return quote! {
+      fn __apply_defaults(_document: &mut #nongoose::bson::Document) -> #nongoose::Result<()> {
+        Ok(())
+      }
+    };
+  }
+
+  let mut defaults = quote!();
+  for default in schema_data.defaults.iter() {
+    let path = &default.path;
+    let ty = &default.field.ty;
+
+    let value = match default.function.as_ref() {
+      Some(lit) => {
+        let function = crate::utils::attributes::function_path(lit);
+        quote!(#function())
+      }
+      None => quote!(Default::default()),
+    };
+
+    defaults.extend(quote! {
+      if !document.contains_key(#path) {
+        let value: #ty = #value;
+        document.insert(#path, #nongoose::bson::to_bson(&value)?);
+      }
+    });
+  }
+
+  quote! {
+    fn __apply_defaults(document: &mut #nongoose::bson::Document) -> #nongoose::Result<()> {
+      #defaults
+      Ok(())
+    }
+  }
+}
@@ -0,0 +1,52 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::DeriveInput;
+
+use crate::schema::data::SchemaData;
+
+pub(crate) fn definition(input: &DeriveInput, schema_data: &SchemaData) -> TokenStream {
+  let nongoose = crate::utils::crates::get_nongoose_crate_name();
+
+  let ident = &input.ident;
+  let vis = &input.vis;
+  let fields_ident = format_ident!("{}Fields", ident);
+  let fields_doc = format!("Field paths of [`{}`].", ident);
+
+  let mut fields = quote!();
+  for (field_ident, path) in schema_data.paths.iter() {
+    let field_doc = format!("`{}` field path.", path);
+
+    fields.extend(quote! {
+      #[doc = #field_doc]
+      pub #field_ident: #nongoose::types::SchemaField<#ident>,
+    });
+  }
+
+  quote! {
+    #[doc = #fields_doc]
+    #[derive(Clone, Copy, Debug)]
+    #vis struct #fields_ident {
+      #fields
+    }
+  }
+}
+
+pub(crate) fn getter(input: &DeriveInput, schema_data: &SchemaData) -> TokenStream {
+  let nongoose = crate::utils::crates::get_nongoose_crate_name();
+  let fields_ident = format_ident!("{}Fields", input.ident);
+
+  let mut values = quote!();
+  for (field_ident, path) in schema_data.paths.iter() {
+    values.extend(quote! {
+      #field_ident: #nongoose::types::SchemaField::new(#path),
+    });
+  }
+
+  quote! {
+    type Fields = #fields_ident;
+
+    fn fields() -> Self::Fields {
+      #fields_ident { #values }
+    }
+  }
+}
@@ -0,0 +1,57 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::schema::data::{IndexKey, SchemaData};
+
+pub(crate) fn getter(schema_data: &SchemaData) -> TokenStream {
+  let nongoose = crate::utils::crates::get_nongoose_crate_name();
+
+  let mut indexes = quote!();
+  for index in schema_data.indexes.iter() {
+    let mut keys = quote!();
+    for (path, key) in index.keys.iter() {
+      let value = match key {
+        IndexKey::Asc => quote!(1_i32),
+        IndexKey::Desc => quote!(-1_i32),
+        IndexKey::Special(value) => quote!(#value),
+      };
+
+      keys.extend(quote!(#path: #value,));
+    }
+
+    let mut options = quote!();
+    if let Some(lit) = index.name.as_ref() {
+      options.extend(quote!(.name(#lit.to_string())));
+    }
+
+    if index.unique {
+      options.extend(quote!(.unique(true)));
+    }
+
+    if index.sparse {
+      options.extend(quote!(.sparse(true)));
+    }
+
+    if let Some(lit) = index.partial.as_ref() {
+      let function = crate::utils::attributes::function_path(lit);
+      options.extend(quote!(.partial_filter_expression(#function())));
+    }
+
+    if let Some(seconds) = index.expire_after {
+      options.extend(quote!(.expire_after(std::time::Duration::from_secs(#seconds))));
+    }
+
+    indexes.extend(quote! {
+      #nongoose::IndexModel::builder()
+        .keys(#nongoose::bson::doc! { #keys })
+        .options(#nongoose::options::IndexOptions::builder() #options .build())
+        .build(),
+    });
+  }
+
+  quote! {
+    fn __indexes() -> Vec<#nongoose::IndexModel> {
+      vec![#indexes]
+    }
+  }
+}
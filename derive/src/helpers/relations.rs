@@ -66,6 +66,9 @@ pub(crate) fn getter(schema_data: &SchemaData) -> TokenStream {
 
       set_relations.extend(quote! {
         if field == #field_ident_name {
+          let mut new_value = new_value;
+          <#schema_ident as #nongoose::Schema>::__apply_defaults_bson(&mut new_value)?;
+
           self.#field_ident = #nongoose::bson::from_bson(new_value)?;
           return Ok(());
         }
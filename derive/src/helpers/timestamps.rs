@@ -0,0 +1,34 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::schema::data::SchemaData;
+
+pub(crate) fn getter(schema_data: &SchemaData) -> TokenStream {
+  let nongoose = crate::utils::crates::get_nongoose_crate_name();
+
+  let timestamps = match schema_data.timestamps.as_ref() {
+    Some(timestamps) => timestamps,
+    None => return quote!(),
+  };
+
+  let created_at = timestamps.created_at.ident.as_ref().unwrap();
+  let updated_at = timestamps.updated_at.ident.as_ref().unwrap();
+
+  let created_at_path = schema_data.path(&created_at.to_string());
+  let updated_at_path = schema_data.path(&updated_at.to_string());
+
+  quote! {
+    fn __timestamps() -> Option<(&'static str, &'static str)> {
+      Some((#created_at_path, #updated_at_path))
+    }
+
+    fn __set_timestamps(&mut self, is_new: bool) {
+      let now = #nongoose::bson::DateTime::now();
+      if is_new {
+        self.#created_at = #nongoose::timestamps::Timestamp::from_datetime(now);
+      }
+
+      self.#updated_at = #nongoose::timestamps::Timestamp::from_datetime(now);
+    }
+  }
+}
@@ -1,62 +1,65 @@
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
+use syn::Field;
 
 use crate::schema::data::SchemaData;
 
 pub(crate) fn getter(schema_data: &SchemaData) -> TokenStream {
   let nongoose = crate::utils::crates::get_nongoose_crate_name();
 
-  if !schema_data.unique.is_empty() {
-    let mut idents = quote!();
-    for field in schema_data.unique.iter() {
-      let ident = field.ident.as_ref().unwrap();
-      let ident_str = format!("{}", ident);
-
-      let value = quote!(self.#ident);
-
-      if let Some(lit) = schema_data.convert.get(ident) {
-        let convert_ident = format_ident!("{}", lit.value());
-        idents.extend(quote! {
-          (
-            #nongoose::bson::doc! { #ident_str: #convert_ident(#value.clone()) },
-            #ident_str,
-            #value.clone().to_string(),
-          ),
-        });
-      } else {
-        idents.extend(quote! {
-          (
-            #nongoose::bson::doc! { #ident_str: #value.clone() },
-            #ident_str,
-            #value.clone().to_string(),
-          ),
-        });
-      }
-    }
+  // The single unique fields and the compound unique constraints (`unique_with`).
+  let groups = schema_data
+    .unique
+    .iter()
+    .map(|field| vec![*field])
+    .chain(schema_data.unique_with.iter().cloned());
+
+  let mut idents = quote!();
+  for group in groups {
+    let mut document = quote!();
+    let mut values = quote!();
+    let mut paths = Vec::new();
 
-    quote! {
-      fn __check_unique_fields(&self) -> #nongoose::Result<()> {
-        let data = vec![#idents];
-        for (document, field, value) in data {
-          if let Some(doc) = Self::__get_database(None)
-            .collection::<#nongoose::bson::Document>(Self::collection_name().as_str())
-            .find_one(document, None)?
-          {
-            let data: Self = #nongoose::bson::from_bson(#nongoose::bson::Bson::Document(doc))?;
-            if self.__get_id() != data.__get_id() {
-              return Err(#nongoose::Error::DuplicatedSchemaField(field.to_string(), value.to_string()));
-            }
-          }
-        }
-
-        Ok(())
-      }
+    for field in group.iter() {
+      let (path, bson, value) = field_tokens(schema_data, field);
+
+      document.extend(quote!(#path: #bson,));
+      values.extend(quote!(#value,));
+      paths.push(path);
     }
-  } else {
-    quote! {
-      fn __check_unique_fields(&self) -> #nongoose::Result<()> {
-        Ok(())
-      }
+
+    let paths = paths.join(", ");
+
+    idents.extend(quote! {
+      (
+        #nongoose::bson::doc! { #document },
+        #paths.to_string(),
+        vec![#values].join(", "),
+      ),
+    });
+  }
+
+  quote! {
+    fn __unique_fields(&self) -> Vec<(#nongoose::bson::Document, String, String)> {
+      vec![#idents]
     }
   }
 }
+
+/// Path of the field in the database, its BSON value and its value as string.
+fn field_tokens(schema_data: &SchemaData, field: &Field) -> (String, TokenStream, TokenStream) {
+  let ident = field.ident.as_ref().unwrap();
+  let path = schema_data.path(&ident.to_string());
+
+  let value = quote!(self.#ident);
+
+  let bson = match schema_data.convert.get(ident) {
+    Some(lit) => {
+      let convert_ident = format_ident!("{}", lit.value());
+      quote!(#convert_ident(#value.clone()))
+    }
+    None => quote!(#value.clone()),
+  };
+
+  (path, bson, quote!(#value.clone().to_string()))
+}
@@ -0,0 +1,271 @@
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+use regex::Regex;
+use syn::{Lit, Type};
+
+use crate::schema::data::{SchemaData, ValidationRule};
+
+const NUMERIC_TYPES: [&str; 14] = [
+  "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize", "f32",
+  "f64",
+];
+
+pub(crate) fn getter(schema_data: &SchemaData) -> TokenStream {
+  let nongoose = crate::utils::crates::get_nongoose_crate_name();
+
+  let mut checks = quote!();
+  let mut async_checks = quote!();
+
+  for validation in schema_data.validations.iter() {
+    let ident = validation.field.ident.as_ref().unwrap();
+    let path = &validation.path;
+
+    let option_inner = crate::utils::fields::option_inner(&validation.field.ty);
+    let ty = option_inner.unwrap_or(&validation.field.ty);
+
+    let mut rules = quote!();
+    let mut async_rules = quote!();
+    let mut required = false;
+
+    for rule in validation.rules.iter() {
+      let (name, check, message) = match rule {
+        ValidationRule::Min(lit) => (
+          "min",
+          {
+            let min = typed_lit(lit, ty);
+            quote!(#nongoose::validation::min(value, #min))
+          },
+          format!("must be greater than or equal to {}", lit_value(lit)),
+        ),
+        ValidationRule::Max(lit) => (
+          "max",
+          {
+            let max = typed_lit(lit, ty);
+            quote!(#nongoose::validation::max(value, #max))
+          },
+          format!("must be less than or equal to {}", lit_value(lit)),
+        ),
+        ValidationRule::MinLength(lit) => (
+          "min_length",
+          {
+            let min = length_lit(lit);
+            quote!(#nongoose::validation::min_length(value, #min))
+          },
+          format!("length must be greater than or equal to {}", lit_value(lit)),
+        ),
+        ValidationRule::MaxLength(lit) => (
+          "max_length",
+          {
+            let max = length_lit(lit);
+            quote!(#nongoose::validation::max_length(value, #max))
+          },
+          format!("length must be less than or equal to {}", lit_value(lit)),
+        ),
+        ValidationRule::Regex(lit) => (
+          "regex",
+          match Regex::new(&lit.value()) {
+            Ok(_) => quote!(#nongoose::validation::regex(value, #lit)),
+            Err(error) => {
+              syn::Error::new_spanned(lit, format!("invalid regex: {}", error)).to_compile_error()
+            }
+          },
+          format!("must match {}", lit.value()),
+        ),
+        ValidationRule::OneOf(lits) => (
+          "one_of",
+          {
+            let values = lits.iter().map(|lit| typed_lit(lit, ty));
+            quote!(#nongoose::validation::one_of(value, &[#(#values),*]))
+          },
+          format!(
+            "must be one of {}",
+            lits.iter().map(lit_value).collect::<Vec<_>>().join(", ")
+          ),
+        ),
+        ValidationRule::Required => {
+          required = true;
+          continue;
+        }
+        ValidationRule::Custom(lit) => {
+          let function = crate::utils::attributes::function_path(lit);
+          let name = lit.value();
+
+          rules.extend(quote! {
+            if let Err(message) = #function(value) {
+              errors.push(#nongoose::ValidationError::new(#path, #name, message));
+            }
+          });
+          continue;
+        }
+        ValidationRule::CustomAsync(lit) => {
+          let function = crate::utils::attributes::function_path(lit);
+          let name = lit.value();
+
+          async_rules.extend(quote! {
+            if let Err(message) = #function(value, &mut *ctx).await {
+              errors.push(#nongoose::ValidationError::new(#path, #name, message));
+            }
+          });
+          continue;
+        }
+      };
+
+      rules.extend(quote! {
+        if !#check {
+          errors.push(#nongoose::ValidationError::new(#path, #name, #message));
+        }
+      });
+    }
+
+    if option_inner.is_some() {
+      let none = if required {
+        quote! {
+          else {
+            errors.push(#nongoose::ValidationError::new(#path, "required", "is required"));
+          }
+        }
+      } else {
+        quote!()
+      };
+
+      if !rules.is_empty() || required {
+        checks.extend(quote! {
+          if let Some(value) = &self.#ident {
+            #rules
+          } #none
+        });
+      }
+
+      if !async_rules.is_empty() {
+        async_checks.extend(quote! {
+          if let Some(value) = &self.#ident {
+            #async_rules
+          }
+        });
+      }
+    } else {
+      if required {
+        panic!("Schema required is only supported by Option fields");
+      }
+
+      if !rules.is_empty() {
+        checks.extend(quote! {
+          {
+            let value = &self.#ident;
+            #rules
+          }
+        });
+      }
+
+      if !async_rules.is_empty() {
+        async_checks.extend(quote! {
+          {
+            let value = &self.#ident;
+            #async_rules
+          }
+        });
+      }
+    }
+  }
+
+  // Document validators, called after the validation of the fields.
+  for lit in schema_data.validators.iter() {
+    let function = crate::utils::attributes::function_path(lit);
+    checks.extend(quote! {
+      if let Err(error) = #function(self) {
+        errors.push(error);
+      }
+    });
+  }
+
+  for lit in schema_data.validators_async.iter() {
+    let function = crate::utils::attributes::function_path(lit);
+    async_checks.extend(quote! {
+      if let Err(error) = #function(self, &mut *ctx).await {
+        errors.push(error);
+      }
+    });
+  }
+
+  let validate = if checks.is_empty() {
+    quote! {
+      fn __validate(&self) -> Vec<#nongoose::ValidationError> {
+        Vec::new()
+      }
+    }
+  } else {
+    quote! {
+      fn __validate(&self) -> Vec<#nongoose::ValidationError> {
+        let mut errors = Vec::new();
+        #checks
+        errors
+      }
+    }
+  };
+
+  // The async validators are only available with the `tokio-runtime` feature of Nongoose.
+  if async_checks.is_empty() {
+    return validate;
+  }
+
+  quote! {
+    #validate
+
+    fn __has_async_validators() -> bool {
+      true
+    }
+
+    fn __validate_async<'a>(
+      &'a self,
+      ctx: &'a mut #nongoose::HookContext<'_>,
+    ) -> #nongoose::re_exports::BoxFuture<'a, Vec<#nongoose::ValidationError>> {
+      Box::pin(async move {
+        let mut errors = Vec::new();
+        #async_checks
+        errors
+      })
+    }
+  }
+}
+
+/// Value of a literal to show in the messages.
+fn lit_value(lit: &Lit) -> String {
+  match lit {
+    Lit::Str(lit) => lit.value(),
+    Lit::Int(lit) => lit.base10_digits().to_string(),
+    Lit::Float(lit) => lit.base10_digits().to_string(),
+    Lit::Bool(lit) => lit.value.to_string(),
+    _ => panic!("Invalid validation value"),
+  }
+}
+
+/// Number with the suffix of the (numeric) type of the field, so `min = 0` works with `u8` and `f64` fields.
+/// Negative numbers are written as strings (e.g. `min = "-10"`), because they are not literals.
+fn typed_lit(lit: &Lit, ty: &Type) -> TokenStream {
+  let suffix = match ty {
+    Type::Path(path) => path
+      .path
+      .get_ident()
+      .map(ToString::to_string)
+      .filter(|ident| NUMERIC_TYPES.contains(&ident.as_str())),
+    _ => None,
+  };
+
+  let number = match (lit, suffix.as_ref()) {
+    (Lit::Int(_) | Lit::Float(_), Some(_)) => lit_value(lit),
+    (Lit::Str(lit), Some(_)) => lit.value(),
+    _ => return quote!(#lit),
+  };
+
+  match format!("{}{}", number, suffix.unwrap()).parse() {
+    Ok(tokens) => tokens,
+    Err(_) => panic!("Invalid number: {}", number),
+  }
+}
+
+fn length_lit(lit: &Lit) -> Literal {
+  match lit_value(lit).parse() {
+    Ok(length) => Literal::usize_unsuffixed(length),
+    Err(_) => panic!("Invalid length: {}", lit_value(lit)),
+  }
+}
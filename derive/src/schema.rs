@@ -10,29 +10,50 @@ pub(crate) fn parse(input: &DeriveInput) -> TokenStream {
   let ident = &input.ident;
 
   let fields = crate::utils::fields::get(input);
-  let schema_data = data::parse_fields(fields);
+  let rename_all = crate::utils::serde::get_rename_all(input);
+  let mut schema_data = data::parse_fields(fields, rename_all.as_deref());
+  data::parse_container(input, fields, &mut schema_data);
 
   let nongoose = crate::utils::crates::get_nongoose_crate_name();
 
   // Helpers
-  let instance_getter = crate::helpers::instance::getter();
+  let fields_definition = crate::helpers::fields::definition(input, &schema_data);
+  let fields_getter = crate::helpers::fields::getter(input, &schema_data);
   let schema_id_getter = crate::helpers::schema_id::getter(&schema_data);
   let unique_fields_getter = crate::helpers::unique_fields::getter(&schema_data);
+  let indexes_getter = crate::helpers::indexes::getter(&schema_data);
+  let validation_getter = crate::helpers::validation::getter(&schema_data);
+  let defaults_getter = crate::helpers::defaults::getter(&schema_data);
+  let timestamps_getter = crate::helpers::timestamps::getter(&schema_data);
   let relations_getter = crate::helpers::relations::getter(&schema_data);
 
+  // The `SchemaAfter` traits of the enabled APIs, with their default functions.
+  let schema_after = if schema_data.after_hooks {
+    quote! {}
+  } else {
+    quote! { #nongoose::__schema_after!(#ident); }
+  };
+
   let traits = quote! {
-    #[cfg_attr(feature = "async", #nongoose::re_exports::async_trait)]
+    #fields_definition
+
     impl #nongoose::Schema for #ident {
       fn collection_name() -> String {
         #collection_name.to_string()
       }
 
-      #instance_getter
+      #fields_getter
       #schema_id_getter
       #unique_fields_getter
+      #indexes_getter
+      #validation_getter
+      #defaults_getter
+      #timestamps_getter
       #relations_getter
     }
 
+    #schema_after
+
     impl From<#ident> for #nongoose::bson::Bson {
       fn from(key: #ident) -> Self {
         match #nongoose::bson::to_bson(&key) {
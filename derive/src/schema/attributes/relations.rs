@@ -63,7 +63,7 @@ pub(crate) fn parse(input: &mut DeriveInput) -> TokenStream {
         }
       }
 
-      return quote! { #input };
+      quote! { #input }
     }
     _ => panic!("Schema only supports named fields"),
   }
@@ -1,22 +1,183 @@
 use std::collections::HashMap;
 
 use proc_macro2::Ident;
-use syn::{Field, FieldsNamed, Lit, LitStr, Meta, NestedMeta};
+use syn::{DeriveInput, Field, FieldsNamed, Lit, LitStr, Meta, MetaList, NestedMeta};
+
+/// Index declared with `#[schema(index)]` (field) or `#[schema(index(fields = "..."))]` (container).
+#[derive(Default)]
+pub(crate) struct IndexData {
+  pub keys: Vec<(String, IndexKey)>,
+  pub name: Option<LitStr>,
+  pub unique: bool,
+  pub sparse: bool,
+  pub partial: Option<LitStr>,
+  pub expire_after: Option<u64>,
+}
+
+/// Type of an index key (`"asc"`, `"desc"` or a special index type like `"text"` or `"2dsphere"`).
+pub(crate) enum IndexKey {
+  Asc,
+  Desc,
+  Special(String),
+}
+
+impl IndexKey {
+  fn parse(value: &str) -> Self {
+    match value {
+      "asc" | "1" => Self::Asc,
+      "desc" | "-1" => Self::Desc,
+      "text" | "hashed" | "2d" | "2dsphere" => Self::Special(value.to_string()),
+      _ => panic!("Invalid index type: {}", value),
+    }
+  }
+}
+
+/// Validation rule of a field (`#[schema(min = 0, max_length = 32, ...)]`).
+pub(crate) enum ValidationRule {
+  Min(Lit),
+  Max(Lit),
+  MinLength(Lit),
+  MaxLength(Lit),
+  Regex(LitStr),
+  OneOf(Vec<Lit>),
+  Required,
+  /// `validate = "path::to::fn"`: `fn(&T) -> Result<(), String>`.
+  Custom(LitStr),
+  /// `validate_async = "path::to::fn"`: `async fn(&T, &mut HookContext<'_>) -> Result<(), String>`.
+  CustomAsync(LitStr),
+}
+
+/// Default value of a field (`#[schema(default)]` or `#[schema(default = "path::to::fn")]`).
+pub(crate) struct FieldDefault<'a> {
+  pub field: &'a Field,
+  pub path: String,
+  pub function: Option<LitStr>,
+}
+
+/// Fields of `#[schema(timestamps)]`.
+pub(crate) struct Timestamps<'a> {
+  pub created_at: &'a Field,
+  pub updated_at: &'a Field,
+}
+
+/// Validation rules of a field.
+pub(crate) struct FieldValidation<'a> {
+  pub field: &'a Field,
+  pub path: String,
+  pub rules: Vec<ValidationRule>,
+}
 
 pub(crate) struct SchemaData<'a> {
   pub convert: HashMap<&'a Ident, LitStr>,
   pub id: &'a Field,
+  pub paths: Vec<(&'a Ident, String)>,
   pub unique: Vec<&'a Field>,
+  pub unique_with: Vec<Vec<&'a Field>>,
   pub relations: Vec<(&'a Ident, String, LitStr)>,
+  pub indexes: Vec<IndexData>,
+  pub expires: Option<String>,
+  pub validations: Vec<FieldValidation<'a>>,
+  pub defaults: Vec<FieldDefault<'a>>,
+  /// Document validators (`#[schema(validate = "...")]` in the container).
+  pub validators: Vec<LitStr>,
+  /// Async document validators (`#[schema(validate_async = "...")]` in the container).
+  pub validators_async: Vec<LitStr>,
+  pub timestamps: Option<Timestamps<'a>>,
+  /// The Schema implements the `SchemaAfter` traits itself (`#[schema(after_hooks)]`).
+  pub after_hooks: bool,
 }
 
-pub(crate) fn parse_fields(fields: &FieldsNamed) -> SchemaData<'_> {
+impl<'a> SchemaData<'a> {
+  /// Path of a field in the database (the field ident, renamed by serde), or the value itself if it is not a
+  /// field ident (e.g. the path of a nested field).
+  pub fn path(&self, field: &str) -> String {
+    self
+      .paths
+      .iter()
+      .find(|(ident, _)| *ident == field)
+      .map(|(_, path)| path.clone())
+      .unwrap_or_else(|| field.to_string())
+  }
+
+  /// Add a compound unique constraint, backed by a compound unique index.
+  fn add_unique_with(&mut self, fields: Vec<&'a Field>, name: Option<LitStr>) {
+    let keys = fields
+      .iter()
+      .map(|field| {
+        let field_ident = field.ident.as_ref().unwrap();
+        match self.paths.iter().find(|(ident, _)| *ident == field_ident) {
+          Some((_, path)) => (path.clone(), IndexKey::Asc),
+          None => panic!("Schema unique field is not serialized: {}", field_ident),
+        }
+      })
+      .collect();
+
+    self.indexes.push(IndexData {
+      keys,
+      name,
+      unique: true,
+      ..Default::default()
+    });
+
+    self.unique_with.push(fields);
+  }
+}
+
+/// Get a field by its Rust name.
+fn find_field<'a>(fields: &'a FieldsNamed, name: &str) -> &'a Field {
+  match fields
+    .named
+    .iter()
+    .find(|field| field.ident.as_ref().unwrap() == name)
+  {
+    Some(field) => field,
+    None => panic!("Schema field not found: {}", name),
+  }
+}
+
+/// Get the fields of a comma-separated list of Rust names.
+fn find_fields<'a>(fields: &'a FieldsNamed, names: &str) -> Vec<&'a Field> {
+  names
+    .split(',')
+    .map(str::trim)
+    .filter(|name| !name.is_empty())
+    .map(|name| find_field(fields, name))
+    .collect()
+}
+
+pub(crate) fn parse_fields<'a>(
+  fields: &'a FieldsNamed,
+  rename_all: Option<&str>,
+) -> SchemaData<'a> {
   let mut convert = HashMap::new();
   let mut id = None;
+  let mut paths = Vec::new();
   let mut unique = Vec::new();
+  let mut unique_with = Vec::new();
   let mut relations = Vec::new();
+  let mut indexes = Vec::new();
+  let mut expires = None;
+  let mut validations = Vec::new();
+  let mut defaults = Vec::new();
 
   for field in &fields.named {
+    let path = crate::utils::serde::get_field_name(field, rename_all);
+    if let Some(path) = path.as_ref() {
+      paths.push((field.ident.as_ref().unwrap(), path.clone()));
+    }
+
+    let index_path = || match path.as_ref() {
+      Some(path) => path.clone(),
+      None => panic!("Schema index field is not serialized"),
+    };
+
+    let default_path = || match path.as_ref() {
+      Some(path) => path.clone(),
+      None => panic!("Schema default field is not serialized"),
+    };
+
+    let mut rules = Vec::new();
+
     for attr in &field.attrs {
       if !crate::utils::attributes::is_schema(attr) {
         continue;
@@ -35,6 +196,18 @@ pub(crate) fn parse_fields(fields: &FieldsNamed) -> SchemaData<'_> {
               }
             } else if path.is_ident("unique") && !unique.contains(&field) {
               unique.push(field);
+            } else if path.is_ident("index") {
+              indexes.push(IndexData::field(index_path(), IndexKey::Asc));
+            } else if path.is_ident("expires") {
+              expires = Some(index_path());
+            } else if path.is_ident("required") {
+              rules.push(ValidationRule::Required);
+            } else if path.is_ident("default") {
+              defaults.push(FieldDefault {
+                field,
+                path: default_path(),
+                function: None,
+              });
             }
           }
           NestedMeta::Meta(Meta::NameValue(nv)) => {
@@ -44,6 +217,55 @@ pub(crate) fn parse_fields(fields: &FieldsNamed) -> SchemaData<'_> {
               if let Lit::Str(lit) = nv.lit {
                 convert.insert(field_ident, lit);
               }
+            } else if nv.path.is_ident("min") {
+              rules.push(ValidationRule::Min(nv.lit));
+            } else if nv.path.is_ident("max") {
+              rules.push(ValidationRule::Max(nv.lit));
+            } else if nv.path.is_ident("min_length") {
+              rules.push(ValidationRule::MinLength(nv.lit));
+            } else if nv.path.is_ident("max_length") {
+              rules.push(ValidationRule::MaxLength(nv.lit));
+            } else if nv.path.is_ident("regex") {
+              match nv.lit {
+                Lit::Str(lit) => rules.push(ValidationRule::Regex(lit)),
+                _ => panic!("Schema regex needs a string"),
+              }
+            } else if nv.path.is_ident("validate") || nv.path.is_ident("validate_async") {
+              let lit = match nv.lit {
+                Lit::Str(lit) => lit,
+                _ => panic!("Schema validate needs a function path"),
+              };
+
+              if nv.path.is_ident("validate") {
+                rules.push(ValidationRule::Custom(lit));
+              } else {
+                rules.push(ValidationRule::CustomAsync(lit));
+              }
+            } else if nv.path.is_ident("default") {
+              match nv.lit {
+                Lit::Str(lit) => defaults.push(FieldDefault {
+                  field,
+                  path: default_path(),
+                  function: Some(lit),
+                }),
+                _ => panic!("Schema default needs a function path"),
+              }
+            } else if nv.path.is_ident("expires_after") {
+              let mut index = IndexData::field(index_path(), IndexKey::Asc);
+              index.expire_after = Some(parse_duration(&nv.lit));
+
+              indexes.push(index);
+            } else if nv.path.is_ident("unique_with") {
+              if let Lit::Str(lit) = nv.lit {
+                unique_with.push((field, lit));
+              }
+            } else if nv.path.is_ident("index") {
+              if let Lit::Str(lit) = nv.lit {
+                indexes.push(IndexData::field(
+                  index_path(),
+                  IndexKey::parse(&lit.value()),
+                ));
+              }
             } else if nv.path.is_ident("one_to_one")
               || nv.path.is_ident("many_to_one")
               || nv.path.is_ident("one_to_many")
@@ -57,20 +279,287 @@ pub(crate) fn parse_fields(fields: &FieldsNamed) -> SchemaData<'_> {
               }
             }
           }
+          NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("one_of") => {
+            let values = list
+              .nested
+              .into_iter()
+              .map(|value| match value {
+                NestedMeta::Lit(lit) => lit,
+                _ => panic!("Schema one_of needs a list of literals"),
+              })
+              .collect();
+
+            rules.push(ValidationRule::OneOf(values));
+          }
           _ => continue,
         }
       }
     }
+
+    if !rules.is_empty() {
+      validations.push(FieldValidation {
+        field,
+        path: path.unwrap_or_else(|| field.ident.as_ref().unwrap().to_string()),
+        rules,
+      });
+    }
   }
 
   if id.is_none() {
     panic!("Schema needs an id field");
   }
 
-  SchemaData {
+  // The unique fields are backed by unique indexes (the `_id` index is already unique).
+  for field in unique.iter().filter(|field| Some(**field) != id) {
+    let field_ident = field.ident.as_ref().unwrap();
+    if let Some((_, path)) = paths.iter().find(|(ident, _)| *ident == field_ident) {
+      let mut index = IndexData::field(path.clone(), IndexKey::Asc);
+      index.unique = true;
+
+      indexes.push(index);
+    }
+  }
+
+  let mut schema_data = SchemaData {
     convert,
     id: id.unwrap(),
+    paths,
     unique,
+    unique_with: Vec::new(),
     relations,
+    indexes,
+    expires,
+    validations,
+    defaults,
+    validators: Vec::new(),
+    validators_async: Vec::new(),
+    timestamps: None,
+    after_hooks: false,
+  };
+
+  // `#[schema(unique_with = "a,b")]`: the field is unique together with the given fields.
+  for (field, lit) in unique_with {
+    let mut group = vec![field];
+    group.extend(find_fields(fields, &lit.value()));
+
+    schema_data.add_unique_with(group, None);
   }
+
+  schema_data
+}
+
+impl IndexData {
+  fn field(path: String, key: IndexKey) -> Self {
+    Self {
+      keys: vec![(path, key)],
+      ..Default::default()
+    }
+  }
+
+  /// Parse `index(fields = "a,-b", name = "...", unique, sparse, partial = "path::to::fn")`.
+  fn container(list: MetaList, schema_data: &SchemaData) -> Self {
+    let mut index = Self::default();
+
+    for opt in list.nested {
+      match opt {
+        NestedMeta::Meta(Meta::Path(path)) => {
+          if path.is_ident("unique") {
+            index.unique = true;
+          } else if path.is_ident("sparse") {
+            index.sparse = true;
+          } else {
+            panic!("Invalid index option");
+          }
+        }
+        NestedMeta::Meta(Meta::NameValue(nv)) => {
+          let lit = match nv.lit {
+            Lit::Str(lit) => lit,
+            _ => panic!("Invalid index option"),
+          };
+
+          if nv.path.is_ident("fields") {
+            for field in lit.value().split(',').map(str::trim) {
+              let (field, key) = match field.split_once(':') {
+                Some((field, key)) => (field.trim(), IndexKey::parse(key.trim())),
+                None => match field.strip_prefix('-') {
+                  Some(field) => (field, IndexKey::Desc),
+                  None => (field, IndexKey::Asc),
+                },
+              };
+
+              if field.is_empty() {
+                panic!("Invalid index field");
+              }
+
+              index.keys.push((schema_data.path(field), key));
+            }
+          } else if nv.path.is_ident("name") {
+            index.name = Some(lit);
+          } else if nv.path.is_ident("partial") {
+            index.partial = Some(lit);
+          } else {
+            panic!("Invalid index option");
+          }
+        }
+        _ => panic!("Invalid index option"),
+      }
+    }
+
+    if index.keys.is_empty() {
+      panic!("Schema index needs at least one field");
+    }
+
+    index
+  }
+}
+
+/// Parse the compound unique constraints and indexes declared in the container attributes
+/// (`#[schema(unique(...))]` and `#[schema(index(...))]`).
+pub(crate) fn parse_container<'a>(
+  input: &DeriveInput,
+  fields: &'a FieldsNamed,
+  schema_data: &mut SchemaData<'a>,
+) {
+  for attr in &input.attrs {
+    if !crate::utils::attributes::is_schema(attr) {
+      continue;
+    }
+
+    let attr = crate::utils::attributes::parse(attr);
+    for opt in attr.nested {
+      if let NestedMeta::Meta(Meta::NameValue(nv)) = &opt {
+        if nv.path.is_ident("expires_after_seconds") {
+          let path = match schema_data.expires.as_ref() {
+            Some(path) => path.clone(),
+            None => panic!("Schema expires_after_seconds needs an #[schema(expires)] field"),
+          };
+
+          let mut index = IndexData::field(path, IndexKey::Asc);
+          index.expire_after = Some(parse_duration(&nv.lit));
+
+          schema_data.indexes.push(index);
+        } else if nv.path.is_ident("validate") || nv.path.is_ident("validate_async") {
+          let lit = match &nv.lit {
+            Lit::Str(lit) => lit.clone(),
+            _ => panic!("Schema validate needs a function path"),
+          };
+
+          if nv.path.is_ident("validate") {
+            schema_data.validators.push(lit);
+          } else {
+            schema_data.validators_async.push(lit);
+          }
+        }
+      }
+
+      if let NestedMeta::Meta(Meta::Path(path)) = &opt {
+        if path.is_ident("timestamps") {
+          schema_data.timestamps = Some(Timestamps {
+            created_at: find_field(fields, "created_at"),
+            updated_at: find_field(fields, "updated_at"),
+          });
+        } else if path.is_ident("after_hooks") {
+          schema_data.after_hooks = true;
+        }
+      }
+
+      if let NestedMeta::Meta(Meta::List(list)) = opt {
+        if list.path.is_ident("timestamps") {
+          schema_data.timestamps = Some(parse_timestamps(list, fields));
+        } else if list.path.is_ident("index") {
+          let index = IndexData::container(list, schema_data);
+          schema_data.indexes.push(index);
+        } else if list.path.is_ident("unique") {
+          let (group, name) = parse_unique(list, fields);
+          schema_data.add_unique_with(group, name);
+        }
+      }
+    }
+  }
+}
+
+/// Parse `timestamps(created_at = "...", updated_at = "...")`.
+fn parse_timestamps(list: MetaList, fields: &FieldsNamed) -> Timestamps<'_> {
+  let mut created_at = "created_at".to_string();
+  let mut updated_at = "updated_at".to_string();
+
+  for opt in list.nested {
+    match opt {
+      NestedMeta::Meta(Meta::NameValue(nv)) => match nv.lit {
+        Lit::Str(lit) if nv.path.is_ident("created_at") => created_at = lit.value(),
+        Lit::Str(lit) if nv.path.is_ident("updated_at") => updated_at = lit.value(),
+        _ => panic!("Invalid timestamps option"),
+      },
+      _ => panic!("Invalid timestamps option"),
+    }
+  }
+
+  Timestamps {
+    created_at: find_field(fields, &created_at),
+    updated_at: find_field(fields, &updated_at),
+  }
+}
+
+/// Parse `unique(fields = "a,b", name = "...")`.
+fn parse_unique(list: MetaList, fields: &FieldsNamed) -> (Vec<&Field>, Option<LitStr>) {
+  let mut group = Vec::new();
+  let mut name = None;
+
+  for opt in list.nested {
+    match opt {
+      NestedMeta::Meta(Meta::NameValue(nv)) => match nv.lit {
+        Lit::Str(lit) if nv.path.is_ident("fields") => group = find_fields(fields, &lit.value()),
+        Lit::Str(lit) if nv.path.is_ident("name") => name = Some(lit),
+        _ => panic!("Invalid unique option"),
+      },
+      _ => panic!("Invalid unique option"),
+    }
+  }
+
+  if group.len() < 2 {
+    panic!("Schema compound unique constraint needs at least two fields");
+  }
+
+  (group, name)
+}
+
+/// Parse a duration in seconds: a number of seconds or a string like `"30s"`, `"15m"`, `"1h"`, `"7d"` or `"1h30m"`.
+fn parse_duration(lit: &Lit) -> u64 {
+  let value = match lit {
+    Lit::Int(lit) => return lit.base10_parse().expect("Invalid duration"),
+    Lit::Str(lit) => lit.value(),
+    _ => panic!("Invalid duration"),
+  };
+
+  let mut seconds = 0;
+  let mut number = String::new();
+
+  for c in value.trim().chars() {
+    if c.is_ascii_digit() {
+      number.push(c);
+      continue;
+    }
+
+    let unit = match c {
+      's' => 1,
+      'm' => 60,
+      'h' => 60 * 60,
+      'd' => 24 * 60 * 60,
+      'w' => 7 * 24 * 60 * 60,
+      _ => panic!("Invalid duration: {}", value),
+    };
+
+    let amount: u64 = number
+      .parse()
+      .unwrap_or_else(|_| panic!("Invalid duration: {}", value));
+    seconds += amount * unit;
+    number.clear();
+  }
+
+  // A number without unit is a number of seconds.
+  if !number.is_empty() {
+    seconds += number.parse::<u64>().unwrap();
+  }
+
+  seconds
 }
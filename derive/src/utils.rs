@@ -2,3 +2,4 @@ pub(crate) mod attributes;
 pub(crate) mod collection;
 pub(crate) mod crates;
 pub(crate) mod fields;
+pub(crate) mod serde;
@@ -1,12 +1,59 @@
-use syn::{Attribute, Meta, MetaList};
+use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
+use syn::{Attribute, LitStr, Meta, MetaList, Path};
 
 pub(crate) fn is_schema(attr: &Attribute) -> bool {
   attr.path.is_ident("schema")
 }
 
 pub(crate) fn parse(attr: &Attribute) -> MetaList {
+  let mut attr = attr.clone();
+  attr.tokens = lists_to_parens(attr.tokens);
+
   match attr.parse_meta() {
     Ok(Meta::List(list)) => list,
     _ => panic!("Invalid attribute syntax"),
   }
 }
+
+/// Path of a function given as string (e.g. `validate = "path::to::fn"`).
+pub(crate) fn function_path(lit: &LitStr) -> Path {
+  match lit.parse() {
+    Ok(path) => path,
+    Err(_) => panic!("Invalid function path: {}", lit.value()),
+  }
+}
+
+/// Replace `name = [a, b]` by `name(a, b)`, because a list of literals is not a valid meta.
+fn lists_to_parens(tokens: TokenStream) -> TokenStream {
+  let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+  let mut result = Vec::new();
+  let mut i = 0;
+
+  while i < tokens.len() {
+    match (&tokens[i], tokens.get(i + 1), tokens.get(i + 2)) {
+      (TokenTree::Ident(_), Some(TokenTree::Punct(punct)), Some(TokenTree::Group(group)))
+        if punct.as_char() == '=' && group.delimiter() == Delimiter::Bracket =>
+      {
+        let mut parens = Group::new(Delimiter::Parenthesis, group.stream());
+        parens.set_span(group.span());
+
+        result.push(tokens[i].clone());
+        result.push(TokenTree::Group(parens));
+        i += 3;
+      }
+      (TokenTree::Group(group), _, _) => {
+        let mut new_group = Group::new(group.delimiter(), lists_to_parens(group.stream()));
+        new_group.set_span(group.span());
+
+        result.push(TokenTree::Group(new_group));
+        i += 1;
+      }
+      (token, _, _) => {
+        result.push(token.clone());
+        i += 1;
+      }
+    }
+  }
+
+  result.into_iter().collect()
+}
@@ -1,4 +1,4 @@
-use syn::{Data, DeriveInput, Fields, FieldsNamed};
+use syn::{Data, DeriveInput, Fields, FieldsNamed, GenericArgument, PathArguments, Type};
 
 pub(crate) fn get(input: &DeriveInput) -> &FieldsNamed {
   match &input.data {
@@ -9,3 +9,24 @@ pub(crate) fn get(input: &DeriveInput) -> &FieldsNamed {
     _ => panic!("Schema only supports named fields"),
   }
 }
+
+/// Type inside an `Option`, or `None` if the type is not an `Option`.
+pub(crate) fn option_inner(ty: &Type) -> Option<&Type> {
+  let path = match ty {
+    Type::Path(path) if path.qself.is_none() => &path.path,
+    _ => return None,
+  };
+
+  let segment = path.segments.last()?;
+  if segment.ident != "Option" {
+    return None;
+  }
+
+  match &segment.arguments {
+    PathArguments::AngleBracketed(args) => match args.args.first() {
+      Some(GenericArgument::Type(ty)) => Some(ty),
+      _ => None,
+    },
+    _ => None,
+  }
+}
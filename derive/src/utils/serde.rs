@@ -0,0 +1,107 @@
+use syn::{Attribute, DeriveInput, Field, Lit, Meta, NestedMeta};
+
+fn is_serde(attr: &Attribute) -> bool {
+  attr.path.is_ident("serde")
+}
+
+fn nested(attrs: &[Attribute]) -> Vec<NestedMeta> {
+  let mut result = Vec::new();
+
+  for attr in attrs {
+    if !is_serde(attr) {
+      continue;
+    }
+
+    if let Ok(Meta::List(list)) = attr.parse_meta() {
+      result.extend(list.nested);
+    }
+  }
+
+  result
+}
+
+fn rename_value(meta: &Meta) -> Option<String> {
+  match meta {
+    Meta::NameValue(nv) => match &nv.lit {
+      Lit::Str(lit) => Some(lit.value()),
+      _ => None,
+    },
+    Meta::List(list) => {
+      for opt in list.nested.iter() {
+        if let NestedMeta::Meta(Meta::NameValue(nv)) = opt {
+          if nv.path.is_ident("serialize") {
+            if let Lit::Str(lit) = &nv.lit {
+              return Some(lit.value());
+            }
+          }
+        }
+      }
+
+      None
+    }
+    _ => None,
+  }
+}
+
+pub(crate) fn get_rename_all(input: &DeriveInput) -> Option<String> {
+  for opt in nested(&input.attrs) {
+    if let NestedMeta::Meta(meta) = opt {
+      if meta.path().is_ident("rename_all") {
+        return rename_value(&meta);
+      }
+    }
+  }
+
+  None
+}
+
+fn apply_rename_all(name: &str, rename_all: &str) -> String {
+  let words: Vec<&str> = name.split('_').filter(|w| !w.is_empty()).collect();
+  let capitalize = |word: &str| {
+    let mut chars = word.chars();
+    match chars.next() {
+      Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+      None => String::new(),
+    }
+  };
+
+  match rename_all {
+    "lowercase" => name.to_lowercase(),
+    "UPPERCASE" => name.to_uppercase(),
+    "PascalCase" => words.iter().map(|w| capitalize(w)).collect(),
+    "camelCase" => words
+      .iter()
+      .enumerate()
+      .map(|(i, w)| if i == 0 { w.to_string() } else { capitalize(w) })
+      .collect(),
+    "SCREAMING_SNAKE_CASE" => name.to_uppercase(),
+    "kebab-case" => name.replace('_', "-"),
+    "SCREAMING-KEBAB-CASE" => name.replace('_', "-").to_uppercase(),
+    _ => name.to_string(),
+  }
+}
+
+/// Name of the field in the database, or `None` if the field is not serialized.
+pub(crate) fn get_field_name(field: &Field, rename_all: Option<&str>) -> Option<String> {
+  let mut rename = None;
+
+  for opt in nested(&field.attrs) {
+    match opt {
+      NestedMeta::Meta(Meta::Path(path))
+        if path.is_ident("skip") || path.is_ident("skip_serializing") =>
+      {
+        return None;
+      }
+      NestedMeta::Meta(meta) if meta.path().is_ident("rename") => rename = rename_value(&meta),
+      _ => continue,
+    }
+  }
+
+  let ident = field.ident.as_ref().unwrap().to_string();
+  let ident = ident.trim_start_matches("r#");
+
+  Some(rename.unwrap_or_else(|| match rename_all {
+    Some(rename_all) => apply_rename_all(ident, rename_all),
+    None => ident.to_string(),
+  }))
+}
@@ -31,6 +31,8 @@ impl User {
 #[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
 impl SchemaBefore for User {}
 
+#[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
+
 #[schema_relations]
 #[derive(Clone, Debug, Deserialize, Schema, Serialize)]
 struct Post {
@@ -59,6 +61,8 @@ impl Post {
 #[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
 impl SchemaBefore for Post {}
 
+#[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
+
 #[schema_relations]
 #[derive(Clone, Debug, Deserialize, Schema, Serialize)]
 struct PostComment {
@@ -93,6 +97,8 @@ impl PostComment {
 #[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
 impl SchemaBefore for PostComment {}
 
+#[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
+
 #[derive(Debug)]
 struct SearchResult {
 	posts_with_comments: Vec<String>,
@@ -26,6 +26,8 @@ impl User {
 #[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
 impl SchemaBefore for User {}
 
+#[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
+
 #[schema_relations]
 #[derive(Clone, Debug, Deserialize, Schema, Serialize)]
 struct UserFriend {
@@ -45,6 +47,8 @@ struct UserFriend {
 #[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
 impl SchemaBefore for UserFriend {}
 
+#[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
+
 fn get_instance() -> Nongoose {
   // Get database url.
   let url = match std::env::var("DATABASE_URL") {
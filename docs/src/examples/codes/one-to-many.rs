@@ -31,6 +31,8 @@ impl Author {
 #[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
 impl SchemaBefore for Author {}
 
+#[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
+
 #[schema_relations]
 #[derive(Clone, Debug, Deserialize, Schema, Serialize)]
 struct Post {
@@ -68,6 +70,8 @@ impl Post {
 #[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
 impl SchemaBefore for Post {}
 
+#[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
+
 fn get_instance() -> Nongoose {
   // Get database url.
   let url = match std::env::var("DATABASE_URL") {
@@ -0,0 +1,36 @@
+//! Blocking API of Nongoose.
+//!
+//! Every type of this module has the same methods as its async counterpart, but they block the current
+//! thread until the operation finishes. It is enabled with the `sync` feature flag and can be used together
+//! with the async API of the `tokio-runtime` feature flag: both share the same `#[derive(Schema)]` output.
+//!
+//! # Example
+//! ```rust,no_run,ignore
+//! use nongoose::blocking::{Client, Nongoose};
+//!
+//! let client = Client::with_uri_str("mongodb://localhost:27017/")?;
+//! let nongoose = Nongoose::builder(client.database("nongoose"))
+//!   .add_schema::<User>()
+//!   .build();
+//!
+//! let user = nongoose.create(&User::new("nongoose"))?;
+//! ```
+
+mod after;
+mod before;
+mod context;
+mod cursor;
+mod nongoose;
+mod transaction;
+
+pub use self::{
+  after::SchemaAfter, before::SchemaBefore, context::HookContext, cursor::Cursor,
+  nongoose::Nongoose, transaction::Transaction,
+};
+pub use mongodb::sync::{Client, ClientSession, Database};
+
+/// Handle over a Schema collection of the blocking API. See `nongoose::Model`.
+pub type Model<T> = crate::nongoose::Model<T, Database>;
+
+/// Lazy and chainable query of the blocking API. See `nongoose::Query`.
+pub type Query<T> = crate::nongoose::Query<T, Database>;
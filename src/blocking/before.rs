@@ -0,0 +1,88 @@
+use mongodb::bson::Document;
+
+use super::HookContext;
+use crate::{error::Result, Schema};
+
+/// Schema before functions of the blocking API.
+///
+/// These are called by `blocking::Nongoose.save()` and the remove functions. They receive the `HookContext` of
+/// the operation (its database, session, Nongoose instance and extensions). With the async API, implement
+/// `nongoose::SchemaBefore` instead.
+pub trait SchemaBefore: Schema {
+  /// Executes a custom validation before insert the document to the database.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// impl SchemaBefore for Post {
+  ///   fn before_create(&mut self, ctx: &mut HookContext<'_>) -> Result<()> {
+  ///     let users = ctx.database().collection::<Document>("users");
+  ///     let query = doc! { "_id": self.author_id };
+  ///     let update = doc! { "$inc": { "posts": 1 } };
+  ///
+  ///     // Inside a transaction, update the author with its session
+  ///     match ctx.session() {
+  ///       Some(session) => users.update_one_with_session(query, update, None, session)?,
+  ///       None => users.update_one(query, update, None)?,
+  ///     };
+  ///
+  ///     Ok(())
+  ///   }
+  /// }
+  /// ```
+  fn before_create(&mut self, _ctx: &mut HookContext<'_>) -> Result<()> {
+    Ok(())
+  }
+
+  /// Executes a custom validation before delete the document from the database. If it returns `false`, the
+  /// document is not removed.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// impl SchemaBefore for User {
+  ///   fn before_delete(&mut self, _ctx: &mut HookContext<'_>) -> Result<bool> {
+  ///     Ok(true)
+  ///   }
+  /// }
+  /// ```
+  fn before_delete(&mut self, _ctx: &mut HookContext<'_>) -> Result<bool> {
+    Ok(true)
+  }
+
+  /// Executes a custom validation before update a document with `Nongoose.find_one_and_update()` (or
+  /// `Nongoose.find_by_id_and_update()`). It receives the conditions and the update document, which can be
+  /// modified (e.g. to set an `updated_at` field); if it returns an error, the document is not updated.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// impl SchemaBefore for User {
+  ///   fn before_find_one_and_update(_conditions: &Document, update: &mut Document, _ctx: &mut HookContext<'_>) -> Result<()> {
+  ///     if update.contains_key("$unset") {
+  ///       return Err(Error::NoImplemented);
+  ///     }
+  ///
+  ///     Ok(())
+  ///   }
+  /// }
+  /// ```
+  fn before_find_one_and_update(
+    _conditions: &Document,
+    _update: &mut Document,
+    _ctx: &mut HookContext<'_>,
+  ) -> Result<()> {
+    Ok(())
+  }
+
+  /// Executes a custom validation before replace the document in the database (called on `Nongoose.save()`).
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// impl SchemaBefore for User {
+  ///   fn before_update(&mut self, _ctx: &mut HookContext<'_>) -> Result<()> {
+  ///     Ok(())
+  ///   }
+  /// }
+  /// ```
+  fn before_update(&mut self, _ctx: &mut HookContext<'_>) -> Result<()> {
+    Ok(())
+  }
+}
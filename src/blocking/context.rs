@@ -0,0 +1,61 @@
+use super::{ClientSession, Database, Nongoose};
+use crate::{Extensions, HookOperation, NongooseBuilder};
+
+/// Context of the functions of `blocking::SchemaBefore` and `blocking::SchemaAfter`. See `nongoose::HookContext`.
+pub struct HookContext<'a> {
+  operation: HookOperation,
+  builder: &'a NongooseBuilder<Database>,
+  session: Option<&'a mut ClientSession>,
+  extensions: &'a mut Extensions,
+}
+
+impl<'a> HookContext<'a> {
+  pub(crate) fn new(
+    operation: HookOperation,
+    builder: &'a NongooseBuilder<Database>,
+    session: Option<&'a mut ClientSession>,
+    extensions: &'a mut Extensions,
+  ) -> Self {
+    Self {
+      operation,
+      builder,
+      session,
+      extensions,
+    }
+  }
+
+  /// Operation that calls the function.
+  pub fn operation(&self) -> HookOperation {
+    self.operation
+  }
+
+  /// Database of the Nongoose instance.
+  pub fn database(&self) -> &Database {
+    &self.builder.database
+  }
+
+  /// Session of the transaction, if the operation runs inside one (see `blocking::Nongoose.transaction()`).
+  pub fn session(&mut self) -> Option<&mut ClientSession> {
+    self.session.as_deref_mut()
+  }
+
+  /// Nongoose instance of the operation, to run typed queries (outside the transaction, use `session()` to
+  /// run operations inside it). Its extensions are the ones of the operation, so the nested operations receive
+  /// them too.
+  pub fn nongoose(&self) -> Nongoose {
+    let mut builder = self.builder.clone();
+    builder.extensions = self.extensions.clone();
+
+    Nongoose { builder }
+  }
+
+  /// Extensions of the operation.
+  pub fn extensions(&self) -> &Extensions {
+    self.extensions
+  }
+
+  /// Mutable extensions of the operation.
+  pub fn extensions_mut(&mut self) -> &mut Extensions {
+    self.extensions
+  }
+}
@@ -0,0 +1,34 @@
+use mongodb::{bson::Document, sync};
+
+use crate::error::Result;
+
+/// Lazy cursor over the documents returned by a query of the blocking API.
+///
+/// Each document is deserialized only when it is consumed, so the whole result set is never loaded in memory.
+///
+/// `Cursor<T>` is an `Iterator<Item = nongoose::Result<T>>`.
+pub struct Cursor<T> {
+  cursor: sync::Cursor<Document>,
+  map: Box<dyn FnMut(Document) -> Result<T> + Send>,
+}
+
+impl<T> Cursor<T> {
+  pub(crate) fn new<F>(cursor: sync::Cursor<Document>, map: F) -> Self
+  where
+    F: FnMut(Document) -> Result<T> + Send + 'static,
+  {
+    Self {
+      cursor,
+      map: Box::new(map),
+    }
+  }
+}
+
+impl<T> Iterator for Cursor<T> {
+  type Item = Result<T>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let doc = self.cursor.next()?;
+    Some(doc.map_err(Into::into).and_then(&mut self.map))
+  }
+}
@@ -0,0 +1,1127 @@
+use std::any::Any;
+
+use mongodb::{
+  bson::{doc, Document},
+  options::{
+    AggregateOptions, CountOptions, DeleteOptions, FindOneAndDeleteOptions,
+    FindOneAndUpdateOptions, FindOneOptions, FindOptions, TransactionOptions, UpdateOptions,
+  },
+  results::{DeleteResult, UpdateResult},
+};
+
+use super::{Cursor, Database, Model, Query, SchemaAfter, SchemaBefore, Transaction};
+use crate::{
+  error::{Error, Result},
+  NongooseBuilder, Schema,
+};
+
+/// Blocking Nongoose instance
+///
+/// Same as `nongoose::Nongoose`, but every method blocks the current thread until it finishes.
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct Nongoose {
+  pub(crate) builder: NongooseBuilder<Database>,
+}
+
+impl Nongoose {
+  /// Create a builder for building `Nongoose`. On the builder, call `.add_schema::<Schema>()` (Optional).
+  /// Finally, call `.build()` to create an instance of `Nongoose`.
+  pub fn builder(database: Database) -> NongooseBuilder<Database> {
+    NongooseBuilder::new(database)
+  }
+
+  /// Performs [aggregations](https://docs.mongodb.com/manual/aggregation/) on the schemas collection.
+  ///
+  /// - [MongoDB Aggregation docs](https://docs.mongodb.org/manual/applications/aggregation/)
+  ///
+  /// # Options
+  /// ```rust,no_run,ignore
+  /// AggregationOptions::builder();
+  ///   // Optional (bool)
+  ///   // Enables writing to temporary files. When set to true, aggregation stages can write data to the _tmp subdirectory in the dbPath directory.
+  ///   .allow_disk_use(...)
+  ///   // Optional (u32)
+  ///   // The number of documents the server should return per cursor batch.
+  ///   // Note that this does not have any affect on the documents that are returned by a cursor, only the number of documents kept in memory at a given time (and by extension, the number of round trips needed to return the entire set of documents returned by the query).
+  ///   .batch_size(...)
+  ///   // Optional (bool)
+  ///   // Opt out of document-level validation.
+  ///   .bypass_document_validation(...)
+  ///   // Optional (mongodb::options::Collation)
+  ///   // The collation to use for the operation.
+  ///   // See the [documentation](https://docs.mongodb.com/manual/reference/collation/) for more information on how to use this option.
+  ///   .collation(...)
+  ///   // Optional (String)
+  ///   // Tags the query with an arbitrary string to help trace the operation through the database profiler, currentOp and logs.
+  ///   .comment(...)
+  ///   // Optional (mongodb::options::Hint)
+  ///   // The index to use for the operation.
+  ///   .hint(...)
+  ///   // Optional (std::time::Duration)
+  ///   // The maximum amount of time for the server to wait on new documents to satisfy a tailable await cursor query.
+  ///   // This option will have no effect on non-tailable cursors that result from this operation.
+  ///   .max_await_time(...)
+  ///   // Optional (std::time::Duration)
+  ///   // The maximum amount of time to allow the query to run.
+  ///   // This options maps to the `maxTimeMS` MongoDB query option, so the duration will be sent across the wire as an integer number of milliseconds.
+  ///   .max_time(...)
+  ///   // Optional (mongodb::options::ReadConcern)
+  ///   // The read concern to use for this find query.
+  ///   // If none specified, the default set on the collection will be used.
+  ///   .read_concern(...)
+  ///   // Optional (mongodb::options::SelectionCriteria)
+  ///   // The criteria used to select a server for this find query.
+  ///   // If none specified, the default set on the collection will be used.
+  ///   .selection_criteria(...)
+  ///   // Optional (mongodb::options::WriteConcern)
+  ///   // The write concern for the operation.
+  ///   // If none is specified, the write concern defined on the object executing this operation will be used.
+  ///   .write_concern(...)
+  ///   // Optional (mongodb::bson::Document)
+  ///   // A document with any amount of parameter names, each followed by definitions of constants in the MQL Aggregate Expression language. Each parameter name is then usable to access the value of the corresponding MQL Expression with the “$$” syntax within Aggregate Expression contexts.
+  ///   // This feature is only available on server versions 5.0 and above.
+  ///   .let_vars(...)
+  ///   // Required to create the instance of `CountOptions`
+  ///   .build()
+  /// ```
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// // Aggregation result struct.
+  /// #[derive(Debug)]
+  /// struct SearchResult {
+  ///   posts_with_comments: Vec<String>,
+  ///   users_with_comments: Vec<String>,
+  /// }
+  ///
+  /// impl From<Document> for SearchResult {
+  ///   fn from(document: Document) -> Self {
+  ///     Self {
+  ///       posts_with_comments: document
+  ///         .get_array("posts_with_comments")
+  ///         .unwrap_or(&Vec::new())
+  ///         .iter()
+  ///         .map(|d| d.as_str())
+  ///         .filter(|d| d.is_some())
+  ///         .map(|d| String::from(d.unwrap()))
+  ///         .collect(),
+  ///       users_with_comments: document
+  ///         .get_array("users_with_comments")
+  ///         .unwrap_or(&Vec::new())
+  ///         .iter()
+  ///         .map(|d| d.as_str())
+  ///         .filter(|d| d.is_some())
+  ///         .map(|d| String::from(d.unwrap()))
+  ///         .collect(),
+  ///     }
+  ///   }
+  /// }
+  ///
+  /// // Aggregation
+  /// let aggregation = nongoose
+  ///   .aggregate::<PostComment, SearchResult>(
+  ///     vec![
+  ///       doc! {
+  ///         "$match": {
+  ///           "message": Regex { pattern: String::from(" "), options: String::new() },
+  ///         },
+  ///       },
+  ///       doc! {
+  ///         "$lookup": {
+  ///           "from": User::collection_name(),
+  ///           "localField": "author_id",
+  ///           "foreignField": "_id",
+  ///           "as": "users",
+  ///         }
+  ///       },
+  ///       doc! {
+  ///         "$lookup": {
+  ///           "from": Post::collection_name(),
+  ///           "localField": "post_id",
+  ///           "foreignField": "_id",
+  ///           "as": "posts",
+  ///         },
+  ///       },
+  ///       doc! {
+  ///         "$group": {
+  ///           "_id": "1",
+  ///           "posts_with_comments": { "$addToSet": { "$first": "$posts.title" } },
+  ///           "users_with_comments": { "$addToSet": { "$first": "$users.realname" } },
+  ///         },
+  ///       },
+  ///     ],
+  ///     None,
+  ///   );
+  ///
+  /// match aggregation {
+  ///   Ok(data) => {
+  ///     println!("Total retuned: {} - Data: {:?}", data.len(), data);
+  ///   },
+  ///   Err(error) => {
+  ///     eprintln!("MongoDB error: {}", error);
+  ///   }
+  /// }
+  /// ```
+  pub fn aggregate<S, T>(
+    &self,
+    pipeline: Vec<Document>,
+    options: Option<AggregateOptions>,
+  ) -> Result<Vec<T>>
+  where
+    S: Schema + Clone,
+    T: From<Document>,
+  {
+    self.builder.aggregate_sync::<S, T>(pipeline, options, None)
+  }
+
+  /// Performs [aggregations](https://docs.mongodb.com/manual/aggregation/) on the schemas collection, returning a lazy cursor instead of loading all the results in memory.
+  ///
+  /// The pipeline and options are the same as `Nongoose.aggregate()`.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// let results = nongoose.aggregate_stream::<PostComment, SearchResult>(pipeline, None)?;
+  /// for result in results {
+  ///   match result {
+  ///     Ok(data) => println!("Data: {:?}", data),
+  ///     Err(error) => eprintln!("MongoDB error: {}", error),
+  ///   }
+  /// }
+  /// ```
+  pub fn aggregate_stream<S, T>(
+    &self,
+    pipeline: Vec<Document>,
+    options: Option<AggregateOptions>,
+  ) -> Result<Cursor<T>>
+  where
+    S: Schema,
+    T: From<Document>,
+  {
+    let cursor = self.builder.aggregate_cursor_sync::<S>(pipeline, options)?;
+    Ok(Cursor::new(cursor, |doc| Ok(doc.into())))
+  }
+
+  /// Shortcut for saving one document to the database. `Nongoose.create(doc)` does `Nongoose.save(doc)`.
+  ///
+  /// This function triggers `Nongoose.save()`.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// // Insert one new `User` document
+  /// match nongoose.create::<User>(&user) {
+  ///   Ok(user) => println!("User saved: {}", user.id),
+  ///   Err(error) => eprintln!("Error saving the user: {}", error),
+  /// }
+  /// ```
+  pub fn create<T>(&self, data: &T) -> Result<T>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    self.save(&mut data.clone())
+  }
+
+  /// Counts number of documents that match `conditions` in a database collection.
+  ///
+  /// # Options
+  /// ```rust,no_run,ignore
+  /// CountOptions::builder()
+  ///   // Optional (mongodb::options::Collation)
+  ///   // The collation to use for the operation.
+  ///   // See the [documentation](https://docs.mongodb.com/manual/reference/collation/) for more information on how to use this option.
+  ///   .collation(...)
+  ///   // Optional (mongodb::options::Hint)
+  ///   // The index to use for the operation.
+  ///   .hint(...)
+  ///   // Optional (i64)
+  ///   // The maximum number of documents to query. If a negative number is specified, the documents will be returned in a single batch limited in number
+  ///   // by the positive value of the specified limit.
+  ///   .limit(...)
+  ///   // Optional (std::time::Duration)
+  ///   // The maximum amount of time to allow the query to run.
+  ///   // This options maps to the `maxTimeMS` MongoDB query option, so the duration will be sent across the wire as an integer number of milliseconds.
+  ///   .max_time(...)
+  ///   // Optional (mongodb::options::ReadConcern)
+  ///   // The read concern to use for this find query.
+  ///   // If none specified, the default set on the collection will be used.
+  ///   .read_concern(...)
+  ///   // Optional (mongodb::options::SelectionCriteria)
+  ///   // The criteria used to select a server for this find query.
+  ///   // If none specified, the default set on the collection will be used.
+  ///   .selection_criteria(...)
+  ///   // Optional (u64)
+  ///   // The number of documents to skip before counting.
+  ///   .skip(...)
+  ///   // Required to create the instance of `CountOptions`
+  ///   .build()
+  /// ```
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// // Count users over 18 years of age
+  /// match nongoose.count::<User>(doc! { "age": { "$gte": 18 } }, None) {
+  ///   Ok(users) => println!("Found {} users!", users),
+  ///   Err(error) => eprintln!("Error finding users: {}", error),
+  /// }
+  ///
+  /// // Passing options
+  /// match nongoose.count::<User>(
+  ///   doc! { "age": { "$gte": 18 } },
+  ///   Some(CountOptions::builder().limit(5).build())
+  /// ) {
+  ///   Ok(users) => println!("Found {} users!", users),
+  ///   Err(error) => eprintln!("Error finding users: {}", error),
+  /// }
+  /// ```
+  pub fn count<T>(&self, conditions: Document, options: Option<CountOptions>) -> Result<u64>
+  where
+    T: Schema,
+  {
+    self.builder.count_sync::<T>(conditions, options, None)
+  }
+
+  /// Deletes all the documents that match the conditions in a single operation, without loading them. Use
+  /// `find_and_remove()` to get the removed documents and call `SchemaBefore::before_delete()` for each one.
+  ///
+  /// # Options
+  /// ```rust,no_run,ignore
+  /// DeleteOptions::builder()
+  ///   // Optional (mongodb::options::Collation)
+  ///   // The collation to use for the operation.
+  ///   // See the [documentation](https://docs.mongodb.com/manual/reference/collation/) for more information on how to use this option.
+  ///   .collation(...)
+  ///   // Optional (mongodb::options::Hint)
+  ///   // The index to use for the operation.
+  ///   .hint(...)
+  ///   // Optional (mongodb::options::WriteConcern)
+  ///   // The write concern for the operation.
+  ///   .write_concern(...)
+  ///   // Required to create the instance of `DeleteOptions`
+  ///   .build()
+  /// ```
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// // Delete the users under 18 years of age
+  /// match nongoose.delete_many::<User>(doc! { "age": { "$lt": 18 } }, None) {
+  ///   Ok(result) => println!("Deleted {} users", result.deleted_count),
+  ///   Err(error) => eprintln!("Error deleting users: {}", error),
+  /// }
+  /// ```
+  pub fn delete_many<T>(
+    &self,
+    conditions: Document,
+    options: Option<DeleteOptions>,
+  ) -> Result<DeleteResult>
+  where
+    T: Schema,
+  {
+    self
+      .builder
+      .delete_many_sync::<T>(conditions, options, None)
+  }
+
+  /// Creates the indexes of the Schema (declared with `#[schema(index)]` or added by the plugins with
+  /// `SchemaData.add_index()`) that do not exist in the collection, and updates the time to live of its TTL
+  /// indexes. To create the indexes of every registered Schema, see `NongooseBuilder.sync_indexes()`.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// if let Err(error) = nongoose.ensure_indexes::<User>() {
+  ///   eprintln!("Error creating the indexes of the users: {}", error);
+  /// }
+  /// ```
+  pub fn ensure_indexes<T>(&self) -> Result<()>
+  where
+    T: Schema,
+  {
+    self.builder.ensure_indexes_sync::<T>()
+  }
+
+  /// Finds documents.
+  ///
+  /// `SchemaAfter.after_find()` is called for each document found before returning it.
+  ///
+  /// # Options
+  /// ```rust,no_run,ignore
+  /// FindOptions::builder()
+  ///   // Optional (bool)
+  ///   // Enables writing to temporary files by the server. When set to true, the find operation can write data to the _tmp subdirectory in the dbPath directory.
+  ///   // Only supported in server versions 4.4+.
+  ///   .allow_disk_use(...)
+  ///   // Optional (bool)
+  ///   // If true, partial results will be returned from a mongos rather than an error being returned if one or more shards is down.
+  ///   .allow_partial_results(...)
+  ///   // Optional (u32)
+  ///   // The number of documents the server should return per cursor batch.
+  ///   // Note that this does not have any affect on the documents that are returned by a cursor, only the number of documents kept in memory at a given time
+  ///   // (and by extension, the number of round trips needed to return the entire set of documents returned by the query.
+  ///   .batch_size(...)
+  ///   // Optional (String)
+  ///   // Tags the query with an arbitrary string to help trace the operation through the database profiler, currentOp and logs.
+  ///   .comment(...)
+  ///   // Optional (mongodb::options::CursorType)
+  ///   // The type of cursor to return.
+  ///   .cursor_type(...)
+  ///   // Optional (mongodb::options::Hint)
+  ///   // The index to use for the operation.
+  ///   .hint(...)
+  ///   // Optional (i64)
+  ///   // The maximum number of documents to query. If a negative number is specified, the documents will be returned in a single batch limited in number
+  ///   // by the positive value of the specified limit.
+  ///   .limit(...)
+  ///   // Optional (mongodb::bson::Document)
+  ///   // The exclusive upper bound for a specific index.
+  ///   .max(...)
+  ///   // Optional (std::time::Duration)
+  ///   // The maximum amount of time for the server to wait on new documents to satisfy a tailable cursor query. If the cursor is not tailable, this option is ignored.
+  ///   .max_await_time(...)
+  ///   // Optional (u64)
+  ///   // Maximum number of documents or index keys to scan when executing the query.
+  ///   // Note: this option is deprecated starting in MongoDB version 4.0 and removed in MongoDB 4.2. Use the maxTimeMS option instead.
+  ///   .max_scan(...)
+  ///   // Optional (std::time::Duration)
+  ///   // The maximum amount of time to allow the query to run.
+  ///   // This options maps to the `maxTimeMS` MongoDB query option, so the duration will be sent across the wire as an integer number of milliseconds.
+  ///   .max_time(...)
+  ///   // Optional (mongodb::bson::Document)
+  ///   // The inclusive lower bound for a specific index.
+  ///   .min(...)
+  ///   // Optional (bool)
+  ///   // Whether the server should close the cursor after a period of inactivity.
+  ///   .no_cursor_timeout(...)
+  ///   // Optional (mongodb::bson::Document)
+  ///   // Limits the fields of the document being returned.
+  ///   .projection(...)
+  ///   // Optional (mongodb::options::ReadConcern)
+  ///   // The read concern to use for this find query.
+  ///   // If none specified, the default set on the collection will be used.
+  ///   .read_concern(...)
+  ///   // Optional (bool)
+  ///   // Whether to return only the index keys in the documents.
+  ///   .return_key(...)
+  ///   // Optional (mongodb::options::SelectionCriteria)
+  ///   // The criteria used to select a server for this find query.
+  ///   // If none specified, the default set on the collection will be used.
+  ///   .selection_criteria(...)
+  ///   // Optional (bool)
+  ///   // Whether to return the record identifier for each document.
+  ///   .show_record_id(...)
+  ///   // Optional (u64)
+  ///   // The number of documents to skip before counting.
+  ///   .skip(...)
+  ///   // Optional (mongodb::bson::Document)
+  ///   // The order of the documents for the purposes of the operation.
+  ///   .sort(...)
+  ///   // Optional (mongodb::options::Collation)
+  ///   // The collation to use for the operation.
+  ///   // See the [documentation](https://docs.mongodb.com/manual/reference/collation/) for more information on how to use this option.
+  ///   .collation(...)
+  ///   // Required to create the instance of `FindOptions`
+  ///   .build()
+  /// ```
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// // Search for users over 18 years of age
+  /// match nongoose.find::<User>(doc! { "age": { "$gte": 18 } }, None) {
+  ///   Ok(users) => println!("Found {} users!", users.len()),
+  ///   Err(error) => eprintln!("Error finding users: {}", error),
+  /// }
+  ///
+  /// // Passing options
+  /// match nongoose.find::<User>(
+  ///   doc! { "age": { "$gte": 18 } },
+  ///   Some(FindOptions::builder().sort(doc! { "username": 1 }).build())
+  /// ) {
+  ///   Ok(users) => println!("Found {} users!", users.len()),
+  ///   Err(error) => eprintln!("Error finding users: {}", error),
+  /// }
+  /// ```
+  pub fn find<T>(&self, conditions: Document, options: Option<FindOptions>) -> Result<Vec<T>>
+  where
+    T: Schema + SchemaAfter,
+  {
+    self.builder.find_sync(conditions, options, None)
+  }
+
+  /// Finds documents and remove them from the db.
+  ///
+  /// `SchemaBefore::before_delete()` is called for each document found, and the documents for which it returns
+  /// `true` are removed one by one with `delete_many` (by their `_id` and the conditions, so the documents that
+  /// stopped matching them or were removed by another client in the meantime are not removed by this call). The
+  /// returned `bool` of each document is `true` if this call removed it. `SchemaAfter.after_delete()` is called for
+  /// each removed document.
+  ///
+  /// # Options
+  /// ```rust,no_run,ignore
+  /// FindOptions::builder()
+  ///   // Optional (bool)
+  ///   // Enables writing to temporary files by the server. When set to true, the find operation can write data to the _tmp subdirectory in the dbPath directory.
+  ///   // Only supported in server versions 4.4+.
+  ///   .allow_disk_use(...)
+  ///   // Optional (bool)
+  ///   // If true, partial results will be returned from a mongos rather than an error being returned if one or more shards is down.
+  ///   .allow_partial_results(...)
+  ///   // Optional (u32)
+  ///   // The number of documents the server should return per cursor batch.
+  ///   // Note that this does not have any affect on the documents that are returned by a cursor, only the number of documents kept in memory at a given time
+  ///   // (and by extension, the number of round trips needed to return the entire set of documents returned by the query.
+  ///   .batch_size(...)
+  ///   // Optional (String)
+  ///   // Tags the query with an arbitrary string to help trace the operation through the database profiler, currentOp and logs.
+  ///   .comment(...)
+  ///   // Optional (mongodb::options::CursorType)
+  ///   // The type of cursor to return.
+  ///   .cursor_type(...)
+  ///   // Optional (mongodb::options::Hint)
+  ///   // The index to use for the operation.
+  ///   .hint(...)
+  ///   // Optional (i64)
+  ///   // The maximum number of documents to query. If a negative number is specified, the documents will be returned in a single batch limited in number
+  ///   // by the positive value of the specified limit.
+  ///   .limit(...)
+  ///   // Optional (mongodb::bson::Document)
+  ///   // The exclusive upper bound for a specific index.
+  ///   .max(...)
+  ///   // Optional (std::time::Duration)
+  ///   // The maximum amount of time for the server to wait on new documents to satisfy a tailable cursor query. If the cursor is not tailable, this option is ignored.
+  ///   .max_await_time(...)
+  ///   // Optional (u64)
+  ///   // Maximum number of documents or index keys to scan when executing the query.
+  ///   // Note: this option is deprecated starting in MongoDB version 4.0 and removed in MongoDB 4.2. Use the maxTimeMS option instead.
+  ///   .max_scan(...)
+  ///   // Optional (std::time::Duration)
+  ///   // The maximum amount of time to allow the query to run.
+  ///   // This options maps to the `maxTimeMS` MongoDB query option, so the duration will be sent across the wire as an integer number of milliseconds.
+  ///   .max_time(...)
+  ///   // Optional (mongodb::bson::Document)
+  ///   // The inclusive lower bound for a specific index.
+  ///   .min(...)
+  ///   // Optional (bool)
+  ///   // Whether the server should close the cursor after a period of inactivity.
+  ///   .no_cursor_timeout(...)
+  ///   // Optional (mongodb::bson::Document)
+  ///   // Limits the fields of the document being returned.
+  ///   .projection(...)
+  ///   // Optional (mongodb::options::ReadConcern)
+  ///   // The read concern to use for this find query.
+  ///   // If none specified, the default set on the collection will be used.
+  ///   .read_concern(...)
+  ///   // Optional (bool)
+  ///   // Whether to return only the index keys in the documents.
+  ///   .return_key(...)
+  ///   // Optional (mongodb::options::SelectionCriteria)
+  ///   // The criteria used to select a server for this find query.
+  ///   // If none specified, the default set on the collection will be used.
+  ///   .selection_criteria(...)
+  ///   // Optional (bool)
+  ///   // Whether to return the record identifier for each document.
+  ///   .show_record_id(...)
+  ///   // Optional (u64)
+  ///   // The number of documents to skip before counting.
+  ///   .skip(...)
+  ///   // Optional (mongodb::bson::Document)
+  ///   // The order of the documents for the purposes of the operation.
+  ///   .sort(...)
+  ///   // Optional (mongodb::options::Collation)
+  ///   // The collation to use for the operation.
+  ///   // See the [documentation](https://docs.mongodb.com/manual/reference/collation/) for more information on how to use this option.
+  ///   .collation(...)
+  ///   // Required to create the instance of `FindOptions`
+  ///   .build()
+  /// ```
+  ///
+  /// See more [here](https://docs.rs/mongodb/2.0.1/mongodb/options/struct.FindOptions.html)
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// // Search for users over 18 years of age
+  /// match nongoose.find_and_remove::<User>(doc! { "age": { "$gte": 18 } }, None) {
+  ///   Ok(users) => println!("Found {} users!", users.len()),
+  ///   Err(error) => eprintln!("Error finding users: {}", error),
+  /// }
+  ///
+  /// // Passing options
+  /// match nongoose.find_and_remove::<User>(
+  ///   doc! { "age": { "$gte": 18 } },
+  ///   Some(FindOptions::builder().sort(doc! { "username": 1 }).build())
+  /// ) {
+  ///   Ok(users) => println!("Found {} users!", users.len()),
+  ///   Err(error) => eprintln!("Error finding users: {}", error),
+  /// }
+  /// ```
+  pub fn find_and_remove<T>(
+    &self,
+    conditions: Document,
+    options: Option<FindOptions>,
+  ) -> Result<Vec<(bool, T)>>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    self
+      .builder
+      .find_and_delete_sync(conditions, options, None, None)
+  }
+
+  /// Finds a single document by its `_id` field. `find_by_id(id)` is almost equivalent to `find_one(doc! { "_id": id })`.
+  /// If you want to query by a document's `_id`, use `find_by_id()` instead of `find_one()`.
+  ///
+  /// This function triggers `find_one()`.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// // Find one `User` document by `_id`
+  /// match nongoose.find_by_id::<User>(&ObjectId::parse_str("616c91dc8cb70be8cc7d1f38").unwrap()) {
+  ///   Ok(Some(user)) => println!("User found: {}", user.id),
+  ///   Ok(None) => eprintln!("Cannot find the user"),
+  ///   Err(error) => eprintln!("Error finding user: {}", error),
+  /// }
+  /// ```
+  pub fn find_by_id<T>(&self, id: &T::Id) -> Result<Option<T>>
+  where
+    T: Schema + SchemaAfter,
+  {
+    self.find_one(doc! { "_id": id.clone().into() }, None)
+  }
+
+  /// Finds a single document by its `_id` field and remove it from the db. `find_by_id_and_remove(id)` is almost
+  /// equivalent to `find_one_and_remove(doc! { "_id": id })`. If you want to query by a document's `_id`, use
+  /// `find_by_id_and_remove()` instead of `find_one_and_remove()`.
+  ///
+  /// This function triggers `find_one_and_remove()`, so `SchemaBefore.before_delete()` is called before removing the
+  /// document.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// // Find one `User` document by `_id`
+  /// match nongoose.find_by_id_and_remove::<User>(
+  ///   &ObjectId::parse_str("616c91dc8cb70be8cc7d1f38").unwrap()
+  /// ) {
+  ///   Ok((_, Some(user))) => println!("User found: {}", user.id),
+  ///   Ok((_, None)) => eprintln!("Cannot find the user"),
+  ///   Err(error) => eprintln!("Error finding user: {}", error),
+  /// }
+  /// ```
+  pub fn find_by_id_and_remove<T>(&self, id: &T::Id) -> Result<(bool, Option<T>)>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    self.find_one_and_remove(doc! { "_id": id.clone().into() }, None)
+  }
+
+  /// Finds a single document by its `_id` field and update it. `find_by_id_and_update(id, ...)` is equivalent to
+  /// `find_one_and_update(doc! { "_id": id }, ...)`.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// // Increment the age of the user and return the updated document
+  /// match nongoose.find_by_id_and_update::<User>(
+  ///   &user.id,
+  ///   doc! { "$inc": { "age": 1 } },
+  ///   Some(FindOneAndUpdateOptions::builder().return_document(ReturnDocument::After).build())
+  /// ) {
+  ///   Ok(Some(user)) => println!("User updated: {}", user.age),
+  ///   Ok(None) => eprintln!("Cannot find the user"),
+  ///   Err(error) => eprintln!("Error updating user: {}", error),
+  /// }
+  /// ```
+  pub fn find_by_id_and_update<T>(
+    &self,
+    id: &T::Id,
+    data: Document,
+    options: Option<FindOneAndUpdateOptions>,
+  ) -> Result<Option<T>>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    self.find_one_and_update(doc! { "_id": id.clone().into() }, data, options)
+  }
+
+  /// Finds one document.
+  ///
+  /// `SchemaAfter.after_find()` is called with the document found before returning it.
+  ///
+  /// # Options
+  /// ```rust,no_run,ignore
+  /// FindOneOptions::builder()
+  ///   // Optional (bool)
+  ///   // If true, partial results will be returned from a mongos rather than an error being returned if one or more shards is down.
+  ///   .allow_partial_results(...)
+  ///   // Optional (mongodb::options::Collation)
+  ///   // The collation to use for the operation.
+  ///   // See the [documentation](https://docs.mongodb.com/manual/reference/collation/) for more information on how to use this option.
+  ///   .collation(...)
+  ///   // Optional (String)
+  ///   // Tags the query with an arbitrary string to help trace the operation through the database profiler, currentOp and logs.
+  ///   .comment(...)
+  ///   // Optional (mongodb::options::Hint)
+  ///   // The index to use for the operation.
+  ///   .hint(...)
+  ///   // Optional (mongodb::bson::Document)
+  ///   // The exclusive upper bound for a specific index.
+  ///   .max(...)
+  ///   // Optional (u64)
+  ///   // Maximum number of documents or index keys to scan when executing the query.
+  ///   // Note: this option is deprecated starting in MongoDB version 4.0 and removed in MongoDB 4.2. Use the maxTimeMS option instead.
+  ///   .max_scan(...)
+  ///   // Optional (std::time::Duration)
+  ///   // The maximum amount of time to allow the query to run.
+  ///   // This options maps to the `maxTimeMS` MongoDB query option, so the duration will be sent across the wire as an integer number of milliseconds.
+  ///   .max_time(...)
+  ///   // Optional (mongodb::bson::Document)
+  ///   // The inclusive lower bound for a specific index.
+  ///   .min(...)
+  ///   // Optional (mongodb::bson::Document)
+  ///   // Limits the fields of the document being returned.
+  ///   .projection(...)
+  ///   // Optional (mongodb::options::ReadConcern)
+  ///   // The read concern to use for this find query.
+  ///   // If none specified, the default set on the collection will be used.
+  ///   .read_concern(...)
+  ///   // Optional (bool)
+  ///   // Whether to return only the index keys in the documents.
+  ///   .return_key(...)
+  ///   // Optional (mongodb::options::SelectionCriteria)
+  ///   // The criteria used to select a server for this find query.
+  ///   // If none specified, the default set on the collection will be used.
+  ///   .selection_criteria(...)
+  ///   // Optional (bool)
+  ///   // Whether to return the record identifier for each document.
+  ///   .show_record_id(...)
+  ///   // Optional (u64)
+  ///   // The number of documents to skip before counting.
+  ///   .skip(...)
+  ///   // Optional (mongodb::bson::Document)
+  ///   // The order of the documents for the purposes of the operation.
+  ///   .sort(...)
+  ///   // Required to create the instance of `FindOneOptions`
+  ///   .build()
+  /// ```
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// // Find one user whose `username` is `nongoose`
+  /// match nongoose.find_one::<User>(doc! { "username": "nongoose" }, None) {
+  ///   Ok(Some(user)) => println!("User found: {}", user.id),
+  ///   Ok(None) => eprintln!("Cannot find the user"),
+  ///   Err(error) => eprintln!("Error finding user: {}", error),
+  /// }
+  ///
+  /// // Passing options
+  /// match nongoose.find_one::<User>(
+  ///   doc! { "username": "nongoose" },
+  ///   Some(FindOneOptions::builder().sort(doc! { "username": 1 }).build())
+  /// ) {
+  ///   Ok(Some(user)) => println!("User found: {}", user.id),
+  ///   Ok(None) => eprintln!("Cannot find the user"),
+  ///   Err(error) => eprintln!("Error finding user: {}", error),
+  /// }
+  /// ```
+  pub fn find_one<T>(
+    &self,
+    conditions: Document,
+    options: Option<FindOneOptions>,
+  ) -> Result<Option<T>>
+  where
+    T: Schema + SchemaAfter,
+  {
+    self.builder.find_one_sync(conditions, options, None)
+  }
+
+  /// Finds one document and remove it from the db.
+  ///
+  /// `SchemaBefore.before_delete()` is called with the document found and, only if it returns `true`, the document
+  /// is removed with
+  /// [findOneAndDelete](https://docs.mongodb.com/manual/reference/method/db.collection.findOneAndDelete/) by its
+  /// `_id` and the conditions, so it is not removed if it stopped matching them in the meantime. The returned
+  /// `bool` is `true` if the document was removed (the document returned is the one removed by the server), and
+  /// `false` if `before_delete()` returned `false` or the document was not removed (the document found is returned
+  /// anyway) or no document was found. `SchemaAfter.after_delete()` is called if the document was removed.
+  ///
+  /// # Options
+  /// ```rust,no_run,ignore
+  /// FindOneAndDeleteOptions::builder()
+  ///   // Optional (mongodb::options::Collation)
+  ///   // The collation to use for the operation.
+  ///   // See the [documentation](https://docs.mongodb.com/manual/reference/collation/) for more information on how to use this option.
+  ///   .collation(...)
+  ///   // Optional (mongodb::bson::Bson)
+  ///   // Tags the query with an arbitrary value to help trace the operation through the database profiler, currentOp and logs.
+  ///   .comment(...)
+  ///   // Optional (mongodb::options::Hint)
+  ///   // The index to use for the operation.
+  ///   .hint(...)
+  ///   // Optional (std::time::Duration)
+  ///   // The maximum amount of time to allow the query to run.
+  ///   // This options maps to the `maxTimeMS` MongoDB query option, so the duration will be sent across the wire as an integer number of milliseconds.
+  ///   .max_time(...)
+  ///   // Optional (mongodb::bson::Document)
+  ///   // Limits the fields of the document being returned.
+  ///   .projection(...)
+  ///   // Optional (mongodb::bson::Document)
+  ///   // The order of the documents for the purposes of the operation.
+  ///   .sort(...)
+  ///   // Optional (mongodb::options::WriteConcern)
+  ///   // The level of the write concern.
+  ///   .write_concern(...)
+  ///   // Required to create the instance of `FindOneAndDeleteOptions`
+  ///   .build()
+  /// ```
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// // Find one user whose `username` is `nongoose`
+  /// match nongoose.find_one_and_remove::<User>(doc! { "username": "nongoose" }, None) {
+  ///   Ok((_, Some(user))) => println!("User removed: {}", user.id),
+  ///   Ok((_, None)) => eprintln!("Cannot find the user"),
+  ///   Err(error) => eprintln!("Error removing user: {}", error),
+  /// }
+  ///
+  /// // Passing options
+  /// match nongoose.find_one_and_remove::<User>(
+  ///   doc! { "age": { "$gte": 18 } },
+  ///   Some(FindOneAndDeleteOptions::builder().sort(doc! { "age": -1 }).build())
+  /// ) {
+  ///   Ok((_, Some(user))) => println!("User removed: {}", user.id),
+  ///   Ok((_, None)) => eprintln!("Cannot find the user"),
+  ///   Err(error) => eprintln!("Error removing user: {}", error),
+  /// }
+  /// ```
+  pub fn find_one_and_remove<T>(
+    &self,
+    conditions: Document,
+    options: Option<FindOneAndDeleteOptions>,
+  ) -> Result<(bool, Option<T>)>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    self
+      .builder
+      .find_one_and_delete_sync(conditions, options, None)
+  }
+
+  /// Finds one document and update it in a single atomic operation
+  /// ([findOneAndUpdate](https://docs.mongodb.com/manual/reference/method/db.collection.findOneAndUpdate/)),
+  /// returning the document before (default) or after the update.
+  ///
+  /// `SchemaBefore::before_find_one_and_update()` is called with the conditions and the update document before
+  /// the update, and `SchemaAfter.after_find()` is called with the returned document.
+  ///
+  /// # Options
+  /// ```rust,no_run,ignore
+  /// FindOneAndUpdateOptions::builder()
+  ///   // Optional (Vec<mongodb::bson::Document>)
+  ///   // A set of filters specifying to which array elements an update should apply.
+  ///   .array_filters(...)
+  ///   // Optional (bool)
+  ///   // Opt out of document-level validation.
+  ///   .bypass_document_validation(...)
+  ///   // Optional (mongodb::options::Collation)
+  ///   // The collation to use for the operation.
+  ///   // See the [documentation](https://docs.mongodb.com/manual/reference/collation/) for more information on how to use this option.
+  ///   .collation(...)
+  ///   // Optional (mongodb::options::Hint)
+  ///   // The index to use for the operation.
+  ///   .hint(...)
+  ///   // Optional (std::time::Duration)
+  ///   // The maximum amount of time to allow the query to run.
+  ///   .max_time(...)
+  ///   // Optional (mongodb::bson::Document)
+  ///   // Limits the fields of the document being returned.
+  ///   .projection(...)
+  ///   // Optional (mongodb::options::ReturnDocument)
+  ///   // Whether the operation should return the document before (`ReturnDocument::Before`, default) or after
+  ///   // (`ReturnDocument::After`) the update.
+  ///   .return_document(...)
+  ///   // Optional (mongodb::bson::Document)
+  ///   // The order of the documents for the purposes of the operation.
+  ///   .sort(...)
+  ///   // Optional (bool)
+  ///   // If true, insert a document if no matching document is found.
+  ///   .upsert(...)
+  ///   // Optional (mongodb::options::WriteConcern)
+  ///   // The level of the write concern.
+  ///   .write_concern(...)
+  ///   // Required to create the instance of `FindOneAndUpdateOptions`
+  ///   .build()
+  /// ```
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// // Set the username of the youngest user and return the updated document
+  /// match nongoose.find_one_and_update::<User>(
+  ///   doc! { "username": { "$exists": false } },
+  ///   doc! { "$set": { "username": "nongoose" } },
+  ///   Some(
+  ///     FindOneAndUpdateOptions::builder()
+  ///       .sort(doc! { "age": 1 })
+  ///       .return_document(ReturnDocument::After)
+  ///       .build()
+  ///   )
+  /// ) {
+  ///   Ok(Some(user)) => println!("User updated: {}", user.id),
+  ///   Ok(None) => eprintln!("Cannot find the user"),
+  ///   Err(error) => eprintln!("Error updating user: {}", error),
+  /// }
+  /// ```
+  pub fn find_one_and_update<T>(
+    &self,
+    conditions: Document,
+    data: Document,
+    options: Option<FindOneAndUpdateOptions>,
+  ) -> Result<Option<T>>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    self
+      .builder
+      .find_one_and_update_sync(conditions, data, options, None)
+  }
+
+  /// Finds documents, returning a lazy cursor instead of loading all the documents in memory.
+  ///
+  /// The conditions and options are the same as `Nongoose.find()`, and `SchemaAfter.after_find()` is called for each
+  /// document when it is consumed.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// let users = nongoose.find_stream::<User>(doc! { "age": { "$gte": 18 } }, None)?;
+  /// for user in users {
+  ///   match user {
+  ///     Ok(user) => println!("User found: {}", user.id),
+  ///     Err(error) => eprintln!("Error finding users: {}", error),
+  ///   }
+  /// }
+  /// ```
+  pub fn find_stream<T>(
+    &self,
+    conditions: Document,
+    options: Option<FindOptions>,
+  ) -> Result<Cursor<T>>
+  where
+    T: Schema + SchemaAfter + 'static,
+  {
+    self.builder.find_stream_sync(conditions, options)
+  }
+
+  /// Creates a handle over the Schema collection, with its own defaults (read concern, write concern,
+  /// collation and projection). The handle can be cloned and stored, e.g. in the state of an application.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// let users = nongoose.model::<User>().select(doc! { "password": 0 });
+  ///
+  /// let user = users.find_by_id(&id)?;
+  /// ```
+  pub fn model<T>(&self) -> Model<T>
+  where
+    T: Schema,
+  {
+    Model::new(self.builder.clone())
+  }
+
+  /// Populates fields on an existing document.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// // Populate the role of the user
+  /// match nongoose.populate(user.clone(), "role") {
+  ///   Ok(u) => user = u,
+  ///   Err(error) => eprintln!("Error populating user: {}", error),
+  /// }
+  /// ```
+  pub fn populate<T>(&self, data: T, field: &str) -> Result<T>
+  where
+    T: Schema,
+  {
+    self.builder.populate_sync(data, field, None, None)
+  }
+
+  /// Creates a lazy and chainable query for the Schema collection.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// // Find the 10 oldest users over 18 years of age
+  /// let users = nongoose
+  ///   .query::<User>()
+  ///   .filter(doc! { "age": { "$gte": 18 } })
+  ///   .sort(doc! { "age": -1 })
+  ///   .limit(10)
+  ///   .find();
+  /// ```
+  pub fn query<T>(&self) -> Query<T>
+  where
+    T: Schema,
+  {
+    Query::new(self.builder.clone())
+  }
+
+  /// Removes a document from the db.
+  ///
+  /// `SchemaBefore.before_delete()` is called before removing the document (with a copy of it), and the document is
+  /// not removed if it returns `false`. `SchemaAfter.after_delete()` is called once the document is removed.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// match nongoose.remove(&user) {
+  ///   Ok(true) => println!("The user was deleted!"),
+  ///   Ok(false) => println!("The user could not be deleted!"),
+  ///   Err(error) => eprintln!("Error deleting the user: {}", error),
+  /// }
+  /// ```
+  pub fn remove<T>(&self, data: &T) -> Result<bool>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    self.builder.remove_sync(data, None, None)
+  }
+
+  /// Saves a document by inserting a new document into the database if it does not exist before, or sends an `replace_one` operation with the modifications to the database.
+  ///
+  /// If the document needs to be inserted to the database, the `SchemaBefore.before_create()` method is called before insert the document;
+  /// otherwise, `SchemaBefore.before_update()` is called before replace the document.
+  /// Once the document is saved, `SchemaAfter.after_create()` or `SchemaAfter.after_update()` is called.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// match nongoose.save(&mut user) {
+  ///   Ok(u) => user = u,
+  ///   Err(error) => eprintln!("Error saving user: {}", error),
+  /// }
+  /// ```
+  pub fn save<T>(&self, data: &mut T) -> Result<T>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    self.builder.save_sync(data, None, None)
+  }
+
+  /// Runs the function inside a multi-document [transaction](https://docs.mongodb.com/manual/core/transactions/)
+  /// and commits it. If the function returns an error, the transaction is aborted and the error is returned.
+  ///
+  /// The transaction (and the commit) is retried while MongoDB returns a `TransientTransactionError` (or an
+  /// `UnknownTransactionCommitResult` for the commit), up to 120 seconds, so the function can be called more
+  /// than once.
+  ///
+  /// Transactions need a replica set or a sharded cluster, and the client of the database set in the builder
+  /// (see `NongooseBuilder.client()`); otherwise, returns `Error::ClientNotSet`.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// let post = nongoose.transaction(|tx| {
+  ///   let post = tx.create(&post)?;
+  ///   tx.update_many::<User>(
+  ///     doc! { "_id": post.author_id },
+  ///     doc! { "$inc": { "posts": 1 } },
+  ///     None,
+  ///   )?;
+  ///
+  ///   Ok(post)
+  /// })?;
+  /// ```
+  pub fn transaction<F, R>(&self, callback: F) -> Result<R>
+  where
+    F: FnMut(&mut Transaction) -> Result<R>,
+  {
+    self.transaction_with_options(None, callback)
+  }
+
+  /// Same as `Nongoose.transaction()`, with the options of the transaction (read concern, write concern,
+  /// read preference and max commit time).
+  pub fn transaction_with_options<F, R>(
+    &self,
+    options: Option<TransactionOptions>,
+    callback: F,
+  ) -> Result<R>
+  where
+    F: FnMut(&mut Transaction) -> Result<R>,
+  {
+    let client = self
+      .builder
+      .sync_client
+      .as_ref()
+      .ok_or(Error::ClientNotSet)?;
+    self.builder.auto_index_schemas_sync();
+    let session = client.start_session(None)?;
+
+    Transaction::new(self.builder.clone(), session).run(options, callback)
+  }
+
+  /// Updates _all_ documents in the database that match `conditions` without returning them.
+  ///
+  /// **Note** update_many will _not_ fire update middleware (`SchemaBefore::before_update()`).
+  ///
+  /// # Options
+  /// ```rust,no_run,ignore
+  /// UpdateOptions::builder()
+  ///   // Optional (Vec<mongodb::bson::Document>)
+  ///   // A set of filters specifying to which array elements an update should apply.
+  ///   // See the documentation [here](https://docs.mongodb.com/manual/reference/command/update/) for more information on array filters.
+  ///   .array_filters(...)
+  ///   // Optional (bool)
+  ///   // Opt out of document-level validation.
+  ///   .bypass_document_validation(...)
+  ///   // Optional (bool)
+  ///   // If true, insert a document if no matching document is found.
+  ///   .upsert(...)
+  ///   // Optional (mongodb::options::Collation)
+  ///   // The collation to use for the operation.
+  ///   .collation(...)
+  ///   // Optional (mongodb::options::Hint)
+  ///   // A document or string that specifies the index to use to support the query predicate.
+  ///   // Only available in MongoDB 4.2+. See the official MongoDB [documentation](https://docs.mongodb.com/manual/reference/command/update/#ex-update-command-hint) for examples.
+  ///   .hint(...)
+  ///   // Optional (mongodb::options::WriteConcern)
+  ///   // The write concern for the operation.
+  ///   .write_concern(...)
+  ///   // Required to create the instance of `UpdateOptions`
+  ///   .build()
+  /// ```
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// // Update the age to 18 if it is under 18
+  /// match nongoose.update_many::<User>(
+  ///   doc! { "age": { "$lt": 18 } },
+  ///   doc! { "$set": { "age": 18 } },
+  ///   None
+  /// ) {
+  ///   Ok(result) => println!("Modified {} documents", result.modified_count),
+  ///   Err(error) => eprintln!("Error updating users: {}", error),
+  /// }
+  /// ```
+  pub fn update_many<T>(
+    &self,
+    conditions: Document,
+    data: Document,
+    options: Option<UpdateOptions>,
+  ) -> Result<UpdateResult>
+  where
+    T: Schema,
+  {
+    self
+      .builder
+      .update_many_sync::<T>(conditions, data, options, None)
+  }
+
+  /// Returns a copy of the instance with a request-scoped value (e.g. the id of the current user) in the
+  /// extensions of the `HookContext` of its operations, replacing the previous value of the same type.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// struct CurrentUser(ObjectId);
+  ///
+  /// let nongoose = nongoose.with_extension(CurrentUser(user.id));
+  /// nongoose.save(&mut post)?;
+  /// ```
+  pub fn with_extension<E>(&self, value: E) -> Self
+  where
+    E: Any + Send + Sync,
+  {
+    let mut builder = self.builder.clone();
+    builder.extensions.insert(value);
+
+    Self { builder }
+  }
+}
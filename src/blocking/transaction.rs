@@ -0,0 +1,279 @@
+use std::time::Instant;
+
+use mongodb::{
+  bson::{doc, Document},
+  error::{TRANSIENT_TRANSACTION_ERROR, UNKNOWN_TRANSACTION_COMMIT_RESULT},
+  options::{
+    AggregateOptions, CountOptions, DeleteOptions, FindOneAndDeleteOptions,
+    FindOneAndUpdateOptions, FindOneOptions, FindOptions, TransactionOptions, UpdateOptions,
+  },
+  results::{DeleteResult, UpdateResult},
+};
+
+use super::{ClientSession, Database, SchemaAfter, SchemaBefore};
+use crate::{error::Result, nongoose::TRANSACTION_TIMEOUT, NongooseBuilder, Schema};
+
+/// Multi-document transaction of the blocking API, created by `blocking::Nongoose.transaction()`.
+///
+/// It has the same methods as `blocking::Nongoose`, but all of them are executed inside the transaction.
+pub struct Transaction {
+  builder: NongooseBuilder<Database>,
+  session: ClientSession,
+}
+
+impl Transaction {
+  pub(crate) fn new(builder: NongooseBuilder<Database>, session: ClientSession) -> Self {
+    Self { builder, session }
+  }
+
+  /// Session of the transaction, to run other MongoDB operations inside it.
+  pub fn session(&mut self) -> &mut ClientSession {
+    &mut self.session
+  }
+
+  /// Same as `blocking::Nongoose.aggregate()`, inside the transaction.
+  pub fn aggregate<S, T>(
+    &mut self,
+    pipeline: Vec<Document>,
+    options: Option<AggregateOptions>,
+  ) -> Result<Vec<T>>
+  where
+    S: Schema,
+    T: From<Document>,
+  {
+    self
+      .builder
+      .aggregate_sync::<S, T>(pipeline, options, Some(&mut self.session))
+  }
+
+  /// Same as `blocking::Nongoose.count()`, inside the transaction.
+  pub fn count<T>(&mut self, conditions: Document, options: Option<CountOptions>) -> Result<u64>
+  where
+    T: Schema,
+  {
+    self
+      .builder
+      .count_sync::<T>(conditions, options, Some(&mut self.session))
+  }
+
+  /// Same as `blocking::Nongoose.create()`, inside the transaction.
+  pub fn create<T>(&mut self, data: &T) -> Result<T>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    self.save(&mut data.clone())
+  }
+
+  /// Same as `blocking::Nongoose.delete_many()`, inside the transaction.
+  pub fn delete_many<T>(
+    &mut self,
+    conditions: Document,
+    options: Option<DeleteOptions>,
+  ) -> Result<DeleteResult>
+  where
+    T: Schema,
+  {
+    self
+      .builder
+      .delete_many_sync::<T>(conditions, options, Some(&mut self.session))
+  }
+
+  /// Same as `blocking::Nongoose.find()`, inside the transaction. The `HookContext` of the `SchemaBefore` and
+  /// `SchemaAfter` functions has the session of the transaction.
+  pub fn find<T>(&mut self, conditions: Document, options: Option<FindOptions>) -> Result<Vec<T>>
+  where
+    T: Schema + SchemaAfter,
+  {
+    self
+      .builder
+      .find_sync(conditions, options, Some(&mut self.session))
+  }
+
+  /// Same as `blocking::Nongoose.find_and_remove()`, inside the transaction. The `HookContext` of the `SchemaBefore`
+  /// and `SchemaAfter` functions has the session of the transaction.
+  pub fn find_and_remove<T>(
+    &mut self,
+    conditions: Document,
+    options: Option<FindOptions>,
+  ) -> Result<Vec<(bool, T)>>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    self
+      .builder
+      .find_and_delete_sync(conditions, options, None, Some(&mut self.session))
+  }
+
+  /// Same as `blocking::Nongoose.find_by_id()`, inside the transaction.
+  pub fn find_by_id<T>(&mut self, id: &T::Id) -> Result<Option<T>>
+  where
+    T: Schema + SchemaAfter,
+  {
+    self.find_one(doc! { "_id": id.clone().into() }, None)
+  }
+
+  /// Same as `blocking::Nongoose.find_by_id_and_remove()`, inside the transaction.
+  pub fn find_by_id_and_remove<T>(&mut self, id: &T::Id) -> Result<(bool, Option<T>)>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    self.find_one_and_remove(doc! { "_id": id.clone().into() }, None)
+  }
+
+  /// Same as `blocking::Nongoose.find_by_id_and_update()`, inside the transaction.
+  pub fn find_by_id_and_update<T>(
+    &mut self,
+    id: &T::Id,
+    data: Document,
+    options: Option<FindOneAndUpdateOptions>,
+  ) -> Result<Option<T>>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    self.find_one_and_update(doc! { "_id": id.clone().into() }, data, options)
+  }
+
+  /// Same as `blocking::Nongoose.find_one()`, inside the transaction. The `HookContext` of the `SchemaBefore` and
+  /// `SchemaAfter` functions has the session of the transaction.
+  pub fn find_one<T>(
+    &mut self,
+    conditions: Document,
+    options: Option<FindOneOptions>,
+  ) -> Result<Option<T>>
+  where
+    T: Schema + SchemaAfter,
+  {
+    self
+      .builder
+      .find_one_sync(conditions, options, Some(&mut self.session))
+  }
+
+  /// Same as `blocking::Nongoose.find_one_and_remove()`, inside the transaction. The `HookContext` of the
+  /// `SchemaBefore` and `SchemaAfter` functions has the session of the transaction.
+  pub fn find_one_and_remove<T>(
+    &mut self,
+    conditions: Document,
+    options: Option<FindOneAndDeleteOptions>,
+  ) -> Result<(bool, Option<T>)>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    self
+      .builder
+      .find_one_and_delete_sync(conditions, options, Some(&mut self.session))
+  }
+
+  /// Same as `blocking::Nongoose.find_one_and_update()`, inside the transaction. The `HookContext` of the
+  /// `SchemaBefore` and `SchemaAfter` functions has the session of the transaction.
+  pub fn find_one_and_update<T>(
+    &mut self,
+    conditions: Document,
+    data: Document,
+    options: Option<FindOneAndUpdateOptions>,
+  ) -> Result<Option<T>>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    self
+      .builder
+      .find_one_and_update_sync(conditions, data, options, Some(&mut self.session))
+  }
+
+  /// Same as `blocking::Nongoose.populate()`, inside the transaction.
+  pub fn populate<T>(&mut self, data: T, field: &str) -> Result<T>
+  where
+    T: Schema,
+  {
+    self
+      .builder
+      .populate_sync(data, field, None, Some(&mut self.session))
+  }
+
+  /// Same as `blocking::Nongoose.remove()`, inside the transaction. The `HookContext` of the `SchemaBefore` and
+  /// `SchemaAfter` functions has the session of the transaction.
+  pub fn remove<T>(&mut self, data: &T) -> Result<bool>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    self
+      .builder
+      .remove_sync(data, None, Some(&mut self.session))
+  }
+
+  /// Same as `blocking::Nongoose.save()`, inside the transaction. The `HookContext` of the `SchemaBefore` and
+  /// `SchemaAfter` functions has the session of the transaction.
+  pub fn save<T>(&mut self, data: &mut T) -> Result<T>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    self.builder.save_sync(data, None, Some(&mut self.session))
+  }
+
+  /// Same as `blocking::Nongoose.update_many()`, inside the transaction.
+  pub fn update_many<T>(
+    &mut self,
+    conditions: Document,
+    data: Document,
+    options: Option<UpdateOptions>,
+  ) -> Result<UpdateResult>
+  where
+    T: Schema,
+  {
+    self
+      .builder
+      .update_many_sync::<T>(conditions, data, options, Some(&mut self.session))
+  }
+
+  // Internals
+  pub(crate) fn run<F, R>(
+    mut self,
+    options: Option<TransactionOptions>,
+    mut callback: F,
+  ) -> Result<R>
+  where
+    F: FnMut(&mut Transaction) -> Result<R>,
+  {
+    let start = Instant::now();
+
+    'transaction: loop {
+      self.session.start_transaction(options.clone())?;
+
+      let result = match callback(&mut self) {
+        Ok(result) => result,
+        Err(error) => {
+          // The server aborts the transaction anyway, so the abort error is ignored.
+          let _ = self.session.abort_transaction();
+
+          if error.contains_label(TRANSIENT_TRANSACTION_ERROR)
+            && start.elapsed() < TRANSACTION_TIMEOUT
+          {
+            continue 'transaction;
+          }
+
+          return Err(error);
+        }
+      };
+
+      loop {
+        match self.session.commit_transaction() {
+          Ok(()) => return Ok(result),
+          Err(error) => {
+            if start.elapsed() >= TRANSACTION_TIMEOUT {
+              return Err(error.into());
+            }
+
+            if error.contains_label(UNKNOWN_TRANSACTION_COMMIT_RESULT) {
+              continue;
+            }
+
+            if error.contains_label(TRANSIENT_TRANSACTION_ERROR) {
+              continue 'transaction;
+            }
+
+            return Err(error.into());
+          }
+        }
+      }
+    }
+  }
+}
@@ -1,3 +1,8 @@
+use mongodb::error::{ErrorKind, WriteFailure};
+
+/// Code of the MongoDB duplicate key errors (E11000).
+const DUPLICATE_KEY_CODE: i32 = 11000;
+
 /// An error that can occur in the `nongoose` crate.
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -9,27 +14,152 @@ pub enum Error {
   #[error("BSON encoding error: {0}")]
   BSONEncode(#[from] mongodb::bson::ser::Error),
 
-  /// Wrap MongoDB errors.
+  /// Wrap MongoDB errors (except the duplicate key errors, see `Error::DuplicatedSchemaField`).
   #[error("MongoDB error: {0}")]
-  MongoDB(#[from] mongodb::error::Error),
+  MongoDB(#[source] mongodb::error::Error),
 
   /// Wrap BSON Document value access errors.
   #[error("Document value access error")]
   DocumentAccessError(#[from] mongodb::bson::document::ValueAccessError),
 
-  /// Wrap Tokio Task errors.
-  #[cfg(feature = "tokio")]
-  #[error("Tokio task error: {0}")]
-  Task(#[from] tokio::task::JoinError),
-
   /// Wrap duplicated schema field (`field_name` and `field_value`).
+  ///
+  /// It is returned by the check of the unique fields before saving a document and by the MongoDB duplicate key
+  /// errors (E11000) of the unique indexes (the fields and values of a compound index are separated by commas).
   #[error("Duplicated schema field ({0}): {1}")]
   DuplicatedSchemaField(String, String),
 
+  /// The document does not pass the validation of the Schema fields (see `Schema.validate()`), with every
+  /// failing field and rule.
+  #[error("Validation error: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+  Validation(Vec<crate::ValidationError>),
+
+  /// The Schema has async validators (`#[schema(validate_async = "...")]`), which the blocking API cannot call, so
+  /// its documents cannot be saved with `blocking::Nongoose`.
+  #[error("Schema has async validators, not supported by the blocking API ({collection})")]
+  AsyncValidatorsNotSupported {
+    /// Collection name of the Schema.
+    collection: String,
+  },
+
+  /// The Schema of the collection is not registered in the Nongoose instance (see `NongooseBuilder.add_schema()`).
+  #[error("Schema is not associated to a Nongoose instance ({collection})")]
+  SchemaNotRegistered {
+    /// Collection name of the Schema.
+    collection: String,
+  },
+
+  /// The MongoDB client is not set in the Nongoose instance (see `NongooseBuilder.client()`).
+  #[error("MongoDB client is not set in the Nongoose instance")]
+  ClientNotSet,
+
   /// Wrap no implemented errors.
   #[error("No implemented")]
   NoImplemented,
 }
 
+impl Error {
+  /// Returns `true` if the error is a MongoDB error with the given label (e.g. `TransientTransactionError`).
+  pub fn contains_label<T: AsRef<str>>(&self, label: T) -> bool {
+    match self {
+      Error::MongoDB(error) => error.contains_label(label),
+      _ => false,
+    }
+  }
+}
+
+impl From<mongodb::error::Error> for Error {
+  fn from(error: mongodb::error::Error) -> Self {
+    let message = match error.kind.as_ref() {
+      ErrorKind::Write(WriteFailure::WriteError(error)) if error.code == DUPLICATE_KEY_CODE => {
+        Some(error.message.as_str())
+      }
+      ErrorKind::Command(error) if error.code == DUPLICATE_KEY_CODE => Some(error.message.as_str()),
+      ErrorKind::BulkWrite(failure) => failure
+        .write_errors
+        .as_ref()
+        .and_then(|errors| errors.iter().find(|e| e.code == DUPLICATE_KEY_CODE))
+        .map(|error| error.message.as_str()),
+      _ => None,
+    };
+
+    match message.and_then(parse_duplicate_key) {
+      Some((field, value)) => Error::DuplicatedSchemaField(field, value),
+      None => Error::MongoDB(error),
+    }
+  }
+}
+
+/// Get the fields and values of a duplicate key error message, e.g.
+/// `E11000 duplicate key error collection: db.users index: email_1 dup key: { email: "dsolarte@nongoose.com" }`.
+fn parse_duplicate_key(message: &str) -> Option<(String, String)> {
+  let (prefix, key) = message.split_once("dup key: {")?;
+  let key = &key[..key.rfind('}')?];
+
+  let mut fields = Vec::new();
+  let mut values = Vec::new();
+
+  for pair in split_key_values(key) {
+    let (field, value) = pair.split_once(':')?;
+    fields.push(field.trim().to_string());
+
+    let value = value.trim();
+    values.push(
+      match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(value) => value.replace("\\\"", "\""),
+        None => value.to_string(),
+      },
+    );
+  }
+
+  // Before MongoDB 4.2, the field names are not in the message, so the name of the index is used.
+  if fields.iter().all(String::is_empty) {
+    let (_, index) = prefix.split_once("index: ")?;
+    let index = index.trim().rsplit('$').next()?;
+    fields = vec![index.to_string()];
+  }
+
+  Some((fields.join(", "), values.join(", ")))
+}
+
+/// Split the `field: value` pairs of a duplicate key, ignoring the commas inside the values.
+fn split_key_values(key: &str) -> Vec<&str> {
+  let mut pairs = Vec::new();
+  let mut depth = 0;
+  let mut quoted = false;
+  let mut escaped = false;
+  let mut start = 0;
+
+  for (i, c) in key.char_indices() {
+    if quoted {
+      match c {
+        _ if escaped => escaped = false,
+        '\\' => escaped = true,
+        '"' => quoted = false,
+        _ => {}
+      }
+
+      continue;
+    }
+
+    match c {
+      '"' => quoted = true,
+      '{' | '[' | '(' => depth += 1,
+      '}' | ']' | ')' => depth -= 1,
+      ',' if depth == 0 => {
+        pairs.push(&key[start..i]);
+        start = i + 1;
+      }
+      _ => {}
+    }
+  }
+
+  if !key[start..].trim().is_empty() {
+    pairs.push(&key[start..]);
+  }
+
+  pairs
+}
+
 /// The result type for all methods that can return an error in the `nongoose` crate.
 pub type Result<T> = std::result::Result<T, Error>;
@@ -5,7 +5,7 @@
 //! # Installation
 //!
 //! ## Requeriments
-//! - Rust 1.48+
+//! - Rust 1.70+
 //! - MongoDB 3.6+
 //!
 //! ## Importing
@@ -21,25 +21,46 @@
 //! | Feature         | Description                                                                                                       | Extra dependencies                                      | Default |
 //! |:----------------|:------------------------------------------------------------------------------------------------------------------|:--------------------------------------------------------|:--------|
 //! | `derive`        | Enable support for the macro derives                                                                              | `nongoose-derive`                                       | yes     |
-//! | `sync`          | Expose the synchronous API. This flag cannot be used in conjuntion with either of the async runtime feature flags | n/a                                                     | no      |
-//! | `tokio-runtime` | Enable support for the `tokio` async runtime                                                                      | `tokio` 1.0 with the `macros` feature and `async-trait` | yes     |
+//! | `sync`          | Expose the blocking API in `nongoose::blocking`. It can be enabled together with `tokio-runtime`                  | n/a                                                     | no      |
+//! | `tokio-runtime` | Expose the async API, based on the `tokio` async runtime of the MongoDB driver                                    | `async-trait` and `futures`                             | yes     |
+//!
+//! At least one of `sync` and `tokio-runtime` must be enabled; with `default-features = false`, add one of them
+//! (e.g. `features = ["derive", "sync"]`).
 
 #![warn(missing_docs)]
 #![deny(unused_imports)]
 #![doc(html_root_url = "https://docs.rs/nongoose/0.1.0-beta.1")]
 
+#[cfg(not(any(feature = "sync", feature = "tokio-runtime")))]
+compile_error!("enable `sync` or `tokio-runtime`");
+
+#[cfg(feature = "sync")]
+pub mod blocking;
 mod error;
+mod middleware;
 mod nongoose;
+mod plugin;
 #[doc(hidden)]
 pub mod re_exports;
 mod schema;
 
-pub use crate::nongoose::{Nongoose, NongooseBuilder};
+#[cfg(feature = "tokio-runtime")]
+pub use crate::nongoose::{Cursor, Nongoose, Transaction};
+pub use crate::nongoose::{Model, NongooseBuilder, Query};
 pub use error::{Error, Result};
-pub use mongodb::{
-  bson, options, results,
-  sync::{Client, Database},
-};
+pub use middleware::{DocumentMiddleware, MiddlewareQuery, MiddlewareResult, QueryMiddleware};
+pub use mongodb::{bson, options, results, IndexModel};
+#[cfg(feature = "tokio-runtime")]
+pub use mongodb::{Client, ClientSession, Database};
 #[cfg(feature = "derive")]
 pub use nongoose_derive::{schema_relations, Schema};
-pub use schema::{types, Schema, SchemaBefore};
+pub use plugin::{Plugin, PluginSetup};
+pub use schema::validation::ValidationError;
+pub use schema::{timestamps, types, validation, Extensions, HookOperation, Schema, SchemaData};
+#[cfg(feature = "tokio-runtime")]
+pub use schema::{HookContext, SchemaAfter, SchemaBefore};
+
+// The example of the README is compiled by the doctests.
+#[cfg(all(doctest, feature = "derive", feature = "tokio-runtime"))]
+#[doc = include_str!("../README.md")]
+struct ReadmeDoctests;
@@ -0,0 +1,329 @@
+use std::{fmt, sync::Arc};
+
+use mongodb::{
+  bson::Document,
+  options::{
+    AggregateOptions, CountOptions, DeleteOptions, FindOneAndDeleteOptions,
+    FindOneAndUpdateOptions, FindOneOptions, FindOptions, UpdateOptions,
+  },
+  results::{DeleteResult, UpdateResult},
+};
+
+use crate::{error::Result, Schema};
+
+/// Query middleware
+///
+/// A query middleware is registered in the Nongoose instance with `NongooseBuilder.add_query_middleware()` and
+/// it is called by the `find`, `find_one`, `count`, `update_many`, `find_one_and_update`, `delete_many` and
+/// `aggregate` functions of every registered Schema, and by the deletes of the `find_and_remove` and
+/// `find_one_and_remove` functions (the equivalent of Mongoose `pre('find')` and `post('find')`), with the async
+/// and the blocking API. The queries of `populate()` call it as `find` (many relations) or `find_one` (one
+/// relation) with the collection of the related Schema.
+///
+/// The internal queries of `save()` (the check of the `unique` fields and the check of whether the document
+/// already exists) do not call it, because a filter added by a middleware (a tenant or a soft delete) would hide
+/// documents that still conflict with the saved one.
+///
+/// # Example
+/// ```rust,no_run,ignore
+/// // Exclude the soft-deleted documents of every query
+/// struct SoftDelete;
+///
+/// impl QueryMiddleware for SoftDelete {
+///   fn pre(&self, _collection: &str, query: &mut MiddlewareQuery) -> Result<()> {
+///     if let Some(conditions) = query.conditions() {
+///       conditions.insert("deleted_at", doc! { "$exists": false });
+///     }
+///
+///     Ok(())
+///   }
+/// }
+///
+/// let nongoose = Nongoose::builder(database)
+///   .add_schema::<User>()
+///   .add_query_middleware(SoftDelete)
+///   .build();
+/// ```
+pub trait QueryMiddleware: Send + Sync {
+  /// Inspects or rewrites the query (conditions, options, update or pipeline) before it is executed. If it
+  /// returns an error, the query is not executed and the error is returned.
+  fn pre(&self, _collection: &str, _query: &mut MiddlewareQuery) -> Result<()> {
+    Ok(())
+  }
+
+  /// Observes the result of the query after it is executed. The documents are the ones returned by the
+  /// database (before `SchemaAfter.after_find()` is called). If it returns an error, the error is returned
+  /// instead of the result.
+  fn post(&self, _collection: &str, _result: &MiddlewareResult) -> Result<()> {
+    Ok(())
+  }
+}
+
+/// Document middleware
+///
+/// A document middleware is registered in the Nongoose instance with `NongooseBuilder.add_document_middleware()`
+/// and it is called by the `save`, `create` and remove functions of every registered Schema (the equivalent of
+/// Mongoose `pre('save')` and `pre('remove')`), with the async and the blocking API. The `pre` functions are
+/// called after the `SchemaBefore` functions and the `post` functions before the `SchemaAfter` functions.
+///
+/// # Example
+/// ```rust,no_run,ignore
+/// // Save the last update time of every document
+/// struct UpdatedAt;
+///
+/// impl DocumentMiddleware for UpdatedAt {
+///   fn pre_save(&self, _collection: &str, document: &mut Document, _is_new: bool) -> Result<()> {
+///     document.insert("updated_at", DateTime::now());
+///     Ok(())
+///   }
+/// }
+/// ```
+pub trait DocumentMiddleware: Send + Sync {
+  /// Inspects or changes the document before it is inserted (`is_new`) or replaced. The changes are saved and
+  /// returned by `save()`, so the document needs to be deserializable into the Schema. If it returns an error,
+  /// the document is not saved and the error is returned.
+  fn pre_save(&self, _collection: &str, _document: &mut Document, _is_new: bool) -> Result<()> {
+    Ok(())
+  }
+
+  /// Observes the document after it is inserted (`is_new`) or replaced. If it returns an error, the error is
+  /// returned by `save()`, but the document is not reverted.
+  fn post_save(&self, _collection: &str, _document: &Document, _is_new: bool) -> Result<()> {
+    Ok(())
+  }
+
+  /// Observes the document before it is removed. If it returns an error, the document is not removed and the
+  /// error is returned.
+  fn pre_remove(&self, _collection: &str, _document: &Document) -> Result<()> {
+    Ok(())
+  }
+
+  /// Observes the document after it is removed. If it returns an error, the error is returned by the remove
+  /// function, but the document is not restored.
+  fn post_remove(&self, _collection: &str, _document: &Document) -> Result<()> {
+    Ok(())
+  }
+}
+
+/// Query passed to `QueryMiddleware.pre()`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum MiddlewareQuery<'a> {
+  /// `aggregate()` and `aggregate_stream()`.
+  Aggregate {
+    /// Stages of the aggregation.
+    pipeline: &'a mut Vec<Document>,
+    /// Options of the aggregation.
+    options: &'a mut Option<AggregateOptions>,
+  },
+
+  /// `count()`.
+  Count {
+    /// Conditions of the documents to count.
+    conditions: &'a mut Document,
+    /// Options of the count.
+    options: &'a mut Option<CountOptions>,
+  },
+
+  /// `delete_many()`, and the delete of each document found by `find_and_remove()`.
+  DeleteMany {
+    /// Conditions of the documents to delete.
+    conditions: &'a mut Document,
+    /// Options of the delete.
+    options: &'a mut Option<DeleteOptions>,
+  },
+
+  /// `find()`, `find_stream()` and the `populate()` of a many relation.
+  Find {
+    /// Conditions of the documents to find.
+    conditions: &'a mut Document,
+    /// Options of the find.
+    options: &'a mut Option<FindOptions>,
+  },
+
+  /// `find_one()`, `find_by_id()` and the `populate()` of a one relation.
+  FindOne {
+    /// Conditions of the document to find.
+    conditions: &'a mut Document,
+    /// Options of the find.
+    options: &'a mut Option<FindOneOptions>,
+  },
+
+  /// The delete of the document found by `find_one_and_remove()` and `find_by_id_and_remove()`.
+  FindOneAndDelete {
+    /// Conditions of the document to delete (the conditions of the find and the `_id` of the document found).
+    conditions: &'a mut Document,
+    /// Options of the delete.
+    options: &'a mut Option<FindOneAndDeleteOptions>,
+  },
+
+  /// `find_one_and_update()` and `find_by_id_and_update()`.
+  FindOneAndUpdate {
+    /// Conditions of the document to update.
+    conditions: &'a mut Document,
+    /// Update document.
+    update: &'a mut Document,
+    /// Options of the update.
+    options: &'a mut Option<FindOneAndUpdateOptions>,
+  },
+
+  /// `update_many()`.
+  UpdateMany {
+    /// Conditions of the documents to update.
+    conditions: &'a mut Document,
+    /// Update document.
+    update: &'a mut Document,
+    /// Options of the update.
+    options: &'a mut Option<UpdateOptions>,
+  },
+}
+
+impl MiddlewareQuery<'_> {
+  /// Get the conditions of the query (`None` for an aggregation, use its `$match` stages instead).
+  pub fn conditions(&mut self) -> Option<&mut Document> {
+    match self {
+      MiddlewareQuery::Aggregate { .. } => None,
+      MiddlewareQuery::Count { conditions, .. }
+      | MiddlewareQuery::DeleteMany { conditions, .. }
+      | MiddlewareQuery::Find { conditions, .. }
+      | MiddlewareQuery::FindOne { conditions, .. }
+      | MiddlewareQuery::FindOneAndDelete { conditions, .. }
+      | MiddlewareQuery::FindOneAndUpdate { conditions, .. }
+      | MiddlewareQuery::UpdateMany { conditions, .. } => Some(conditions),
+    }
+  }
+}
+
+/// Result passed to `QueryMiddleware.post()`.
+///
+/// It is not passed for `aggregate_stream()` and `find_stream()`, because their documents are not loaded.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum MiddlewareResult<'a> {
+  /// Documents returned by `aggregate()`.
+  Aggregate(&'a [Document]),
+
+  /// Number of documents returned by `count()`.
+  Count(u64),
+
+  /// Result of `delete_many()`, or of the delete of each document found by `find_and_remove()`.
+  DeleteMany(&'a DeleteResult),
+
+  /// Documents returned by `find()` or by the `populate()` of a many relation.
+  Find(&'a [Document]),
+
+  /// Document returned by `find_one()` or by the `populate()` of a one relation.
+  FindOne(Option<&'a Document>),
+
+  /// Document removed by `find_one_and_remove()`.
+  FindOneAndDelete(Option<&'a Document>),
+
+  /// Document returned by `find_one_and_update()`.
+  FindOneAndUpdate(Option<&'a Document>),
+
+  /// Result of `update_many()`.
+  UpdateMany(&'a UpdateResult),
+}
+
+/// Query and document middlewares registered in a Nongoose instance.
+#[derive(Clone, Default)]
+pub(crate) struct Middlewares {
+  query: Vec<Arc<dyn QueryMiddleware>>,
+  document: Vec<Arc<dyn DocumentMiddleware>>,
+}
+
+impl Middlewares {
+  pub(crate) fn add(&mut self, middleware: Arc<dyn QueryMiddleware>) {
+    self.query.push(middleware);
+  }
+
+  pub(crate) fn add_document(&mut self, middleware: Arc<dyn DocumentMiddleware>) {
+    self.document.push(middleware);
+  }
+
+  pub(crate) fn pre(&self, collection: &str, mut query: MiddlewareQuery) -> Result<()> {
+    for middleware in self.query.iter() {
+      middleware.pre(collection, &mut query)?;
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn post(&self, collection: &str, result: MiddlewareResult) -> Result<()> {
+    for middleware in self.query.iter() {
+      middleware.post(collection, &result)?;
+    }
+
+    Ok(())
+  }
+
+  /// Call `DocumentMiddleware.pre_save()` and set the changes of the document on `data`.
+  pub(crate) fn pre_save<T>(&self, data: &mut T, is_new: bool) -> Result<Document>
+  where
+    T: Schema,
+  {
+    let mut document = data.__to_document()?;
+    if self.document.is_empty() {
+      return Ok(document);
+    }
+
+    for middleware in self.document.iter() {
+      middleware.pre_save(&T::collection_name(), &mut document, is_new)?;
+    }
+
+    *data = T::from_document(document.clone())?;
+    Ok(document)
+  }
+
+  pub(crate) fn post_save<T>(&self, document: &Document, is_new: bool) -> Result<()>
+  where
+    T: Schema,
+  {
+    for middleware in self.document.iter() {
+      middleware.post_save(&T::collection_name(), document, is_new)?;
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn pre_remove<T>(&self, data: &T) -> Result<()>
+  where
+    T: Schema,
+  {
+    if self.document.is_empty() {
+      return Ok(());
+    }
+
+    let document = data.__to_document()?;
+    for middleware in self.document.iter() {
+      middleware.pre_remove(&T::collection_name(), &document)?;
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn post_remove<T>(&self, data: &T) -> Result<()>
+  where
+    T: Schema,
+  {
+    if self.document.is_empty() {
+      return Ok(());
+    }
+
+    let document = data.__to_document()?;
+    for middleware in self.document.iter() {
+      middleware.post_remove(&T::collection_name(), &document)?;
+    }
+
+    Ok(())
+  }
+}
+
+impl fmt::Debug for Middlewares {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Middlewares")
+      .field("query", &self.query.len())
+      .field("document", &self.document.len())
+      .finish()
+  }
+}
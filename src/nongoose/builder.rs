@@ -1,41 +1,328 @@
+#[cfg(feature = "tokio-runtime")]
+use futures::{StreamExt, TryStreamExt};
+use std::{
+  collections::HashSet,
+  sync::{Arc, Mutex},
+};
+
+use mongodb::bson::{bson, doc};
+use mongodb::options::ReplaceOptions;
+#[cfg(feature = "sync")]
+use mongodb::sync;
+#[cfg(feature = "tokio-runtime")]
+use mongodb::ClientSession;
 use mongodb::{
-  bson::{from_bson, Bson, Document},
-  options::{AggregateOptions, CountOptions, FindOneOptions, FindOptions, UpdateOptions},
-  results::UpdateResult,
-  sync::Database,
+  bson::{Bson, Document},
+  error::ErrorKind,
+  options::{
+    AggregateOptions, CountOptions, DeleteOptions, FindOneAndDeleteOptions,
+    FindOneAndUpdateOptions, FindOneOptions, FindOptions, InsertOneOptions, ReadConcern,
+    UpdateOptions, WriteConcern,
+  },
+  results::{DeleteResult, UpdateResult},
+  IndexModel,
+};
+
+#[cfg(feature = "sync")]
+use crate::blocking;
+use crate::schema::validation;
+use crate::{
+  error::{Error, Result},
+  middleware::{MiddlewareQuery, MiddlewareResult, Middlewares},
+  plugin::{PluginSetup, Plugins},
+  schema::{types::SchemaRelationType, SchemaData},
+  DocumentMiddleware, Extensions, HookOperation, Plugin, QueryMiddleware, Schema,
 };
 
-use crate::{error::Result, schema::SchemaData, Nongoose, Schema};
+#[cfg(feature = "tokio-runtime")]
+pub(crate) type DefaultDatabase = mongodb::Database;
+#[cfg(not(feature = "tokio-runtime"))]
+pub(crate) type DefaultDatabase = mongodb::sync::Database;
+
+/// Documents to set on a relation field by `populate`.
+pub(crate) struct Population {
+  pub(crate) collection_name: String,
+  pub(crate) conditions: Document,
+  pub(crate) many: bool,
+}
+
+/// Options of the `find_one` that looks for the document to remove in `find_one_and_remove`, so
+/// `SchemaBefore.before_delete()` can be called before removing it.
+fn find_one_and_delete_find_options(
+  options: &Option<FindOneAndDeleteOptions>,
+) -> Option<FindOneOptions> {
+  options.as_ref().map(|options| {
+    FindOneOptions::builder()
+      .collation(options.collation.clone())
+      .comment_bson(options.comment.clone())
+      .hint(options.hint.clone())
+      .max_time(options.max_time)
+      .projection(options.projection.clone())
+      .sort(options.sort.clone())
+      .build()
+  })
+}
+
+/// Compare two indexes by their keys and options (the names are ignored). If `ttl` is `false`, the time to live
+/// (`expire_after`) is ignored too.
+fn same_index(a: &IndexModel, b: &IndexModel, ttl: bool) -> bool {
+  // The keys listed by the server can be doubles, so the numbers are compared as doubles. The server stores the
+  // fields of a text index as `{ _fts: "text", _ftsx: 1 }` (and lists them in its weights), so the text fields
+  // are replaced by those keys and compared apart.
+  let keys = |index: &IndexModel| {
+    let mut keys = Vec::new();
+    let mut text_fields = index
+      .options
+      .as_ref()
+      .and_then(|options| options.weights.as_ref())
+      .map(|weights| weights.keys().cloned().collect::<Vec<_>>())
+      .unwrap_or_default();
+
+    for (key, value) in index.keys.iter() {
+      let is_text = matches!(value, Bson::String(value) if value == "text");
+      if is_text || key == "_fts" || key == "_ftsx" {
+        if is_text {
+          text_fields.push(key.clone());
+        }
+
+        if !keys.iter().any(|(key, _)| key == "_fts") {
+          keys.push((String::from("_fts"), Bson::String(String::from("text"))));
+          keys.push((String::from("_ftsx"), Bson::Double(1.0)));
+        }
+
+        continue;
+      }
+
+      let value = match value {
+        Bson::Int32(value) => Bson::Double(*value as f64),
+        Bson::Int64(value) => Bson::Double(*value as f64),
+        value => value.clone(),
+      };
+
+      keys.push((key.clone(), value));
+    }
+
+    text_fields.sort();
+    text_fields.dedup();
+    (keys, text_fields)
+  };
+
+  let options = |index: &IndexModel| {
+    let options = index.options.clone().unwrap_or_default();
+    (
+      options.unique.unwrap_or(false),
+      options.sparse.unwrap_or(false),
+      options.partial_filter_expression,
+      options.expire_after.filter(|_| ttl),
+    )
+  };
+
+  keys(a) == keys(b) && options(a) == options(b)
+}
+
+fn index_name(index: &IndexModel) -> String {
+  index
+    .options
+    .as_ref()
+    .and_then(|options| options.name.clone())
+    .unwrap_or_default()
+}
+
+fn index_ttl(index: &IndexModel) -> Option<std::time::Duration> {
+  index
+    .options
+    .as_ref()
+    .and_then(|options| options.expire_after)
+}
+
+/// Changes to apply to the indexes of a collection.
+#[derive(Default)]
+struct IndexChanges {
+  /// Declared indexes that do not exist in the collection.
+  created: Vec<IndexModel>,
+
+  /// Names of the existing indexes that are not declared (the `_id` index is never dropped).
+  dropped: Vec<String>,
+
+  /// `collMod` index options of the existing TTL indexes whose time to live changed.
+  modified: Vec<Document>,
+}
+
+impl IndexChanges {
+  fn new(declared: Vec<IndexModel>, existing: Vec<IndexModel>, drop_undeclared: bool) -> Self {
+    let mut changes = Self::default();
+    let mut kept = Vec::new();
+
+    for index in existing {
+      let name = index_name(&index);
+
+      if name == "_id_" || declared.iter().any(|d| same_index(d, &index, true)) {
+        kept.push(index);
+      } else if let Some(declared) = declared.iter().find(|d| {
+        same_index(d, &index, false) && index_ttl(d).is_some() && index_ttl(&index).is_some()
+      }) {
+        let seconds = index_ttl(declared).unwrap_or_default().as_secs() as i64;
+        changes
+          .modified
+          .push(doc! { "name": name, "expireAfterSeconds": seconds });
+
+        kept.push(declared.clone());
+      } else if drop_undeclared {
+        changes.dropped.push(name);
+      } else {
+        kept.push(index);
+      }
+    }
+
+    changes.created = declared
+      .into_iter()
+      .filter(|index| !kept.iter().any(|k| same_index(index, k, true)))
+      .collect();
+
+    changes
+  }
+}
+
+/// Listing the indexes of a collection that does not exist returns a `NamespaceNotFound` error.
+fn is_namespace_not_found(error: &mongodb::error::Error) -> bool {
+  matches!(error.kind.as_ref(), ErrorKind::Command(error) if error.code == 26)
+}
 
 /// Specifies the options to a Nongoose instance.
+///
+/// `D` is the database the instance works with: `nongoose::Database` to build a `nongoose::Nongoose`,
+/// or `nongoose::blocking::Database` to build a `nongoose::blocking::Nongoose`.
 #[derive(Clone, Debug)]
 #[non_exhaustive]
-pub struct NongooseBuilder {
+pub struct NongooseBuilder<D = DefaultDatabase> {
   /// MongoDB instance
-  pub database: Database,
+  pub database: D,
 
   /// Registered schemas.
   ///
   /// You can add one using `NongooseBuilder.add_schema<Schema>()`
   pub schemas: Vec<SchemaData>,
+
+  /// Query and document middlewares.
+  ///
+  /// You can add one using `NongooseBuilder.add_query_middleware()` or `NongooseBuilder.add_document_middleware()`
+  pub(crate) middlewares: Middlewares,
+
+  /// Plugins.
+  ///
+  /// You can add one using `NongooseBuilder.plugin()`
+  pub(crate) plugins: Plugins,
+
+  /// Extensions passed to the `HookContext` of every operation (see `Nongoose.with_extension()`).
+  pub(crate) extensions: Extensions,
+
+  /// Check the unique fields with a `find_one` before saving a document.
+  ///
+  /// You can disable it using `NongooseBuilder.unique_pre_check(false)`
+  pub(crate) unique_pre_check: bool,
+
+  /// Create the indexes of each Schema the first time the built instance uses it.
+  ///
+  /// You can enable it using `NongooseBuilder.auto_index(true)`
+  pub(crate) auto_index: bool,
+
+  /// Names of the Schemas whose indexes were synced (or tried by `auto_index`), shared by the built instances.
+  pub(crate) synced_indexes: Arc<Mutex<HashSet<String>>>,
+
+  /// MongoDB client of the database, used to start the sessions of the transactions.
+  #[cfg(feature = "tokio-runtime")]
+  pub(crate) client: Option<mongodb::Client>,
+
+  /// MongoDB client of the database, used to start the sessions of the transactions.
+  #[cfg(feature = "sync")]
+  pub(crate) sync_client: Option<sync::Client>,
 }
 
-impl NongooseBuilder {
+impl<D> NongooseBuilder<D> {
+  pub(crate) fn new(database: D) -> Self {
+    Self {
+      database,
+      schemas: Vec::new(),
+      middlewares: Middlewares::default(),
+      plugins: Plugins::default(),
+      extensions: Extensions::default(),
+      unique_pre_check: true,
+      auto_index: false,
+      synced_indexes: Arc::default(),
+      #[cfg(feature = "tokio-runtime")]
+      client: None,
+      #[cfg(feature = "sync")]
+      sync_client: None,
+    }
+  }
+
   /// Register a Schema if it was not registered before.
   pub fn add_schema<T>(mut self) -> Self
   where
     T: Schema,
   {
-    let collection_name = T::collection_name();
-    if !self.has_schema(&collection_name) {
-      let schema = SchemaData::new::<T>();
+    if !self.has_schema(&T::collection_name()) {
+      let mut schema = SchemaData::new::<T>();
+      self.plugins.add_schema(&mut schema);
 
-      super::globals::add_schema(&schema);
       self.schemas.push(schema);
+    }
+
+    self
+  }
 
-      T::__get_database(Some(self.database.clone()));
+  /// Register a plugin: its middlewares are registered and it is called for every Schema, the registered ones
+  /// and the ones registered after it (see `Plugin`).
+  pub fn plugin<P>(mut self, plugin: P) -> Self
+  where
+    P: Plugin + 'static,
+  {
+    plugin.setup(&mut PluginSetup::new(&mut self.middlewares));
+    for schema in self.schemas.iter_mut() {
+      plugin.add_schema(schema);
     }
 
+    self.plugins.add(Arc::new(plugin));
+    self
+  }
+
+  /// Register a query middleware, called by the `find`, `find_one`, `count`, `update_many`, `find_one_and_update`,
+  /// `delete_many` and `aggregate` functions (and the deletes of the remove functions that take conditions and the
+  /// queries of `populate`) of every Schema in the registration order (see `QueryMiddleware`).
+  pub fn add_query_middleware<M>(mut self, middleware: M) -> Self
+  where
+    M: QueryMiddleware + 'static,
+  {
+    self.middlewares.add(Arc::new(middleware));
+    self
+  }
+
+  /// Register a document middleware, called by the `save`, `create` and remove functions of every Schema in the
+  /// registration order (see `DocumentMiddleware`).
+  pub fn add_document_middleware<M>(mut self, middleware: M) -> Self
+  where
+    M: DocumentMiddleware + 'static,
+  {
+    self.middlewares.add_document(Arc::new(middleware));
+    self
+  }
+
+  /// Enable or disable the check of the unique fields (`#[schema(unique)]`) before saving a document (enabled by
+  /// default). It costs a `find_one` per unique field and it is not safe under concurrency: the unique indexes
+  /// (created by `NongooseBuilder.sync_indexes()`, `Nongoose.ensure_indexes()` or `auto_index`) reject the
+  /// duplicated values anyway, returning `Error::DuplicatedSchemaField` too.
+  pub fn unique_pre_check(mut self, enabled: bool) -> Self {
+    self.unique_pre_check = enabled;
+    self
+  }
+
+  /// Enable or disable the creation of the indexes of each Schema the first time the built instance uses it
+  /// (disabled by default), like `Nongoose.ensure_indexes()`. It is tried once per Schema and its errors are
+  /// ignored (e.g. a read-only user, or duplicated values in a new unique index), so it never fails the
+  /// operation; use `NongooseBuilder.sync_indexes()` to get them. The transactions try it for every registered
+  /// Schema before starting, so no index is created inside a transaction.
+  pub fn auto_index(mut self, enabled: bool) -> Self {
+    self.auto_index = enabled;
     self
   }
 
@@ -44,147 +331,1769 @@ impl NongooseBuilder {
     self.schemas.iter().any(|e| e.get_name().as_str() == name)
   }
 
-  /// Return the Nongoose instance.
-  pub fn build(&self) -> Nongoose {
-    Nongoose {
+  // Internals
+  fn check_relations(&self) -> Result<()> {
+    for schema in self.schemas.iter() {
+      for relation in schema.get_relations() {
+        if !self.has_schema(&relation.schema_name) {
+          return Err(Error::SchemaNotRegistered {
+            collection: relation.schema_name,
+          });
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Unique fields to check before saving a document (none if `unique_pre_check` is disabled).
+  pub(crate) fn unique_fields<T>(&self, data: &T) -> Vec<(Document, String, String)>
+  where
+    T: Schema,
+  {
+    if self.unique_pre_check {
+      data.__unique_fields()
+    } else {
+      Vec::new()
+    }
+  }
+
+  fn get_schema(&self, name: &str) -> Option<&SchemaData> {
+    self.schemas.iter().find(|e| e.get_name().as_str() == name)
+  }
+
+  pub(crate) fn populations<T>(&self, data: &T, field: &str) -> Result<Vec<Population>>
+  where
+    T: Schema,
+  {
+    self.collection_name::<T>()?;
+
+    let mut populations = Vec::new();
+    if let Some(relations) = data.__get_relations() {
+      for relation in relations.iter() {
+        if relation.field_ident != field {
+          continue;
+        }
+
+        let collection_name = &relation.schema_name;
+
+        if relation.relation_type == SchemaRelationType::OneToOne
+          || relation.relation_type == SchemaRelationType::ManyToOne
+        {
+          populations.push(Population {
+            collection_name: collection_name.clone(),
+            conditions: doc! { "_id": relation.field_value.clone() },
+            many: false,
+          });
+        } else if relation.relation_type == SchemaRelationType::OneToMany {
+          if let Some(schema) = self.get_schema(collection_name) {
+            if let Some(schema_relation) = schema.get_relations().iter().find(|schema_relation| {
+              schema_relation.relation_type == SchemaRelationType::ManyToOne
+                && schema_relation.schema_name == T::collection_name()
+            }) {
+              populations.push(Population {
+                collection_name: collection_name.clone(),
+                conditions: doc! { schema_relation.field_id(): data.__get_id().into() },
+                many: true,
+              });
+            }
+          }
+        }
+      }
+    }
+
+    Ok(populations)
+  }
+
+  fn collection_name<T>(&self) -> Result<String>
+  where
+    T: Schema,
+  {
+    let collection_name = T::collection_name();
+    if !self.has_schema(&collection_name) {
+      return Err(Error::SchemaNotRegistered {
+        collection: collection_name,
+      });
+    }
+
+    Ok(collection_name)
+  }
+
+  /// Returns `false` if the indexes of the Schema were already synced.
+  fn set_synced_indexes(&self, name: String) -> bool {
+    self
+      .synced_indexes
+      .lock()
+      .unwrap_or_else(|error| error.into_inner())
+      .insert(name)
+  }
+
+  /// Whether `auto_index` has to create the indexes of the Schema (only once).
+  fn needs_auto_index(&self, name: &str) -> bool {
+    self.auto_index && self.set_synced_indexes(name.to_string())
+  }
+}
+
+#[cfg(feature = "sync")]
+impl NongooseBuilder<sync::Database> {
+  /// Set the MongoDB client of the database (required by `blocking::Nongoose.transaction()`).
+  pub fn client(mut self, client: sync::Client) -> Self {
+    self.sync_client = Some(client);
+    self
+  }
+
+  /// Return the blocking Nongoose instance.
+  pub fn build(&self) -> blocking::Nongoose {
+    blocking::Nongoose {
       builder: self.clone(),
     }
   }
 
+  /// Return the blocking Nongoose instance after verifying that the Schemas used by the relations of the
+  /// registered Schemas are registered too; otherwise, returns `Error::SchemaNotRegistered`.
+  pub fn build_strict(&self) -> Result<blocking::Nongoose> {
+    self.check_relations()?;
+    Ok(self.build())
+  }
+
+  /// Create the indexes of the registered Schemas (declared with `#[schema(index)]` or added by the plugins) that
+  /// do not exist in their collections, and update the time to live of the TTL indexes (`collMod`). If
+  /// `drop_undeclared` is `true`, the indexes of the collections that are not declared are dropped (except the
+  /// `_id` index).
+  ///
+  /// The operations never create the indexes, unless `NongooseBuilder.auto_index(true)` is set.
+  pub fn sync_indexes(&self, drop_undeclared: bool) -> Result<()> {
+    for schema in self.schemas.iter() {
+      self.sync_schema_indexes_sync(schema, drop_undeclared)?;
+    }
+
+    Ok(())
+  }
+
   // Internals
+  /// Try `auto_index` for every registered Schema, before a transaction starts.
+  pub(crate) fn auto_index_schemas_sync(&self) {
+    for schema in self.schemas.iter() {
+      if self.needs_auto_index(&schema.get_name()) {
+        let _ = self.sync_schema_indexes_sync(schema, false);
+      }
+    }
+  }
+
+  pub(crate) fn hook_context<'a>(
+    &'a self,
+    operation: HookOperation,
+    session: Option<&'a mut sync::ClientSession>,
+    extensions: &'a mut Extensions,
+  ) -> blocking::HookContext<'a> {
+    blocking::HookContext::new(operation, self, session, extensions)
+  }
+
+  pub(crate) fn collection<T>(&self) -> Result<sync::Collection<Document>>
+  where
+    T: Schema,
+  {
+    let collection_name = self.collection_name::<T>()?;
+    if self.needs_auto_index(&collection_name) {
+      // `auto_index` never fails the operation
+      let _ = self.ensure_indexes_sync::<T>();
+    }
+
+    Ok(
+      self
+        .database
+        .collection::<Document>(collection_name.as_str()),
+    )
+  }
+
+  fn check_unique_fields_sync<T>(
+    &self,
+    data: &T,
+    mut session: Option<&mut sync::ClientSession>,
+  ) -> Result<()>
+  where
+    T: Schema,
+  {
+    let collection = self.collection::<T>()?;
+    let id: Bson = data.__get_id().into();
+
+    for (document, field, value) in self.unique_fields(data) {
+      let doc = match session.as_deref_mut() {
+        Some(session) => collection.find_one_with_session(document, None, session)?,
+        None => collection.find_one(document, None)?,
+      };
+
+      if let Some(doc) = doc {
+        if doc.get("_id") != Some(&id) {
+          return Err(Error::DuplicatedSchemaField(field, value));
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn aggregate_cursor_sync<S>(
+    &self,
+    mut pipeline: Vec<Document>,
+    mut options: Option<AggregateOptions>,
+  ) -> Result<sync::Cursor<Document>>
+  where
+    S: Schema,
+  {
+    let collection = self.collection::<S>()?;
+
+    self.middlewares.pre(
+      &S::collection_name(),
+      MiddlewareQuery::Aggregate {
+        pipeline: &mut pipeline,
+        options: &mut options,
+      },
+    )?;
+
+    Ok(collection.aggregate(pipeline, options)?)
+  }
+
   pub(crate) fn aggregate_sync<S, T>(
     &self,
-    pipeline: Vec<Document>,
-    options: Option<AggregateOptions>,
+    mut pipeline: Vec<Document>,
+    mut options: Option<AggregateOptions>,
+    session: Option<&mut sync::ClientSession>,
   ) -> Result<Vec<T>>
   where
     S: Schema,
     T: From<Document>,
   {
-    let collection_name = S::collection_name();
-    if !self.has_schema(&collection_name) {
-      panic!(
-        "Schema is not associated to a Nongoose instance ({})",
-        collection_name
-      );
-    }
+    let collection = self.collection::<S>()?;
 
-    let documents = self
-      .database
-      .collection::<Document>(collection_name.as_str())
-      .aggregate(pipeline, options)?;
+    self.middlewares.pre(
+      &S::collection_name(),
+      MiddlewareQuery::Aggregate {
+        pipeline: &mut pipeline,
+        options: &mut options,
+      },
+    )?;
 
-    let mut data = Vec::new();
-    for doc in documents {
-      data.push(doc?.into());
-    }
+    let documents = match session {
+      Some(session) => collection
+        .aggregate_with_session(pipeline, options, session)?
+        .iter(session)
+        .collect::<mongodb::error::Result<Vec<Document>>>()?,
+      None => collection
+        .aggregate(pipeline, options)?
+        .collect::<mongodb::error::Result<Vec<Document>>>()?,
+    };
 
-    Ok(data)
+    self.middlewares.post(
+      &S::collection_name(),
+      MiddlewareResult::Aggregate(&documents),
+    )?;
+
+    Ok(documents.into_iter().map(Into::into).collect())
   }
 
   pub(crate) fn count_sync<T>(
     &self,
-    conditions: Document,
-    options: Option<CountOptions>,
+    mut conditions: Document,
+    mut options: Option<CountOptions>,
+    session: Option<&mut sync::ClientSession>,
   ) -> Result<u64>
   where
     T: Schema,
   {
-    let collection_name = T::collection_name();
-    if !self.has_schema(&collection_name) {
-      panic!(
-        "Schema is not associated to a Nongoose instance ({})",
-        collection_name
-      );
+    let collection = self.collection::<T>()?;
+
+    self.middlewares.pre(
+      &T::collection_name(),
+      MiddlewareQuery::Count {
+        conditions: &mut conditions,
+        options: &mut options,
+      },
+    )?;
+
+    let count = match session {
+      Some(session) => collection.count_documents_with_session(conditions, options, session)?,
+      None => collection.count_documents(conditions, options)?,
+    };
+
+    self
+      .middlewares
+      .post(&T::collection_name(), MiddlewareResult::Count(count))?;
+
+    Ok(count)
+  }
+
+  pub(crate) fn delete_many_sync<T>(
+    &self,
+    mut conditions: Document,
+    mut options: Option<DeleteOptions>,
+    session: Option<&mut sync::ClientSession>,
+  ) -> Result<DeleteResult>
+  where
+    T: Schema,
+  {
+    let collection = self.collection::<T>()?;
+
+    self.middlewares.pre(
+      &T::collection_name(),
+      MiddlewareQuery::DeleteMany {
+        conditions: &mut conditions,
+        options: &mut options,
+      },
+    )?;
+
+    let result = match session {
+      Some(session) => collection.delete_many_with_session(conditions, options, session)?,
+      None => collection.delete_many(conditions, options)?,
+    };
+
+    self
+      .middlewares
+      .post(&T::collection_name(), MiddlewareResult::DeleteMany(&result))?;
+
+    Ok(result)
+  }
+
+  pub(crate) fn ensure_indexes_sync<T>(&self) -> Result<()>
+  where
+    T: Schema,
+  {
+    let collection_name = self.collection_name::<T>()?;
+    if let Some(schema) = self.get_schema(&collection_name) {
+      self.sync_schema_indexes_sync(schema, false)?;
     }
 
-    Ok(
+    Ok(())
+  }
+
+  fn sync_schema_indexes_sync(&self, schema: &SchemaData, drop_undeclared: bool) -> Result<()> {
+    let collection = self
+      .database
+      .collection::<Document>(schema.get_name().as_str());
+
+    let existing = match collection.list_indexes(None) {
+      Ok(cursor) => cursor.collect::<std::result::Result<Vec<_>, _>>()?,
+      Err(error) if is_namespace_not_found(&error) => Vec::new(),
+      Err(error) => return Err(error.into()),
+    };
+
+    let changes = IndexChanges::new(schema.get_indexes(), existing, drop_undeclared);
+    for name in changes.dropped {
+      collection.drop_index(name, None)?;
+    }
+
+    for index in changes.modified {
       self
         .database
-        .collection::<Document>(collection_name.as_str())
-        .count_documents(conditions, options)?,
-    )
+        .run_command(doc! { "collMod": schema.get_name(), "index": index }, None)?;
+    }
+
+    if !changes.created.is_empty() {
+      collection.create_indexes(changes.created, None)?;
+    }
+
+    self.set_synced_indexes(schema.get_name());
+    Ok(())
   }
 
-  pub(crate) fn find_sync<T>(
+  pub(crate) fn find_and_delete_sync<T>(
     &self,
     conditions: Document,
     options: Option<FindOptions>,
-  ) -> Result<Vec<T>>
+    write_concern: Option<WriteConcern>,
+    session: Option<&mut sync::ClientSession>,
+  ) -> Result<Vec<(bool, T)>>
   where
-    T: Schema,
+    T: Schema + blocking::SchemaBefore + blocking::SchemaAfter,
   {
-    let collection_name = T::collection_name();
-    if !self.has_schema(&collection_name) {
-      panic!(
-        "Schema is not associated to a Nongoose instance ({})",
-        collection_name
-      );
+    let mut extensions = self.extensions.clone();
+    let mut ctx = self.hook_context(HookOperation::Delete, session, &mut extensions);
+
+    let mut result = Vec::new();
+    for mut data in self.find_sync::<T>(conditions.clone(), options, ctx.session())? {
+      let remove = data.before_delete(&mut ctx)?;
+      if remove {
+        self.middlewares.pre_remove(&data)?;
+      }
+
+      result.push((remove, data));
     }
 
-    let cursor = self
-      .database
-      .collection::<Document>(collection_name.as_str())
-      .find(Some(conditions), options)?;
+    // Each document is deleted with the conditions, so the documents that changed and do not match them anymore
+    // (or were deleted by another client) are not reported as removed.
+    let options = DeleteOptions::builder()
+      .write_concern(write_concern)
+      .build();
+    for (removed, data) in result.iter_mut().filter(|(removed, _)| *removed) {
+      let conditions = doc! { "$and": [conditions.clone(), data.__get_id_query()] };
+      let deleted = self.delete_many_sync::<T>(conditions, Some(options.clone()), ctx.session())?;
 
-    let mut documents = Vec::new();
-    for doc in cursor.collect::<Vec<mongodb::error::Result<Document>>>() {
-      documents.push(from_bson(Bson::Document(doc?))?);
+      *removed = deleted.deleted_count > 0;
     }
 
-    Ok(documents)
+    for (_, data) in result.iter_mut().filter(|(removed, _)| *removed) {
+      self.middlewares.post_remove(data)?;
+      data.after_delete(&mut ctx)?;
+    }
+
+    Ok(result)
   }
 
-  pub(crate) fn find_one_sync<T>(
+  pub(crate) fn find_cursor_sync<T>(
     &self,
-    conditions: Document,
-    options: Option<FindOneOptions>,
-  ) -> Result<Option<T>>
+    mut conditions: Document,
+    mut options: Option<FindOptions>,
+  ) -> Result<sync::Cursor<Document>>
   where
     T: Schema,
   {
-    let collection_name = T::collection_name();
-    if !self.has_schema(&collection_name) {
-      panic!(
-        "Schema is not associated to a Nongoose instance ({})",
-        collection_name
-      );
-    }
+    let collection = self.collection::<T>()?;
 
-    Ok(
-      match self
-        .database
-        .collection::<Document>(collection_name.as_str())
-        .find_one(Some(conditions), options)?
-      {
-        Some(document) => from_bson(Bson::Document(document))?,
-        None => None,
+    self.middlewares.pre(
+      &T::collection_name(),
+      MiddlewareQuery::Find {
+        conditions: &mut conditions,
+        options: &mut options,
       },
-    )
+    )?;
+
+    Ok(collection.find(Some(conditions), options)?)
   }
 
-  pub(crate) fn update_many_sync<T>(
+  pub(crate) fn find_stream_sync<T>(
     &self,
     conditions: Document,
-    data: Document,
-    options: Option<UpdateOptions>,
-  ) -> Result<UpdateResult>
+    options: Option<FindOptions>,
+  ) -> Result<blocking::Cursor<T>>
   where
-    T: Schema,
+    T: Schema + blocking::SchemaAfter + 'static,
   {
-    let collection_name = T::collection_name();
-    if !self.has_schema(&collection_name) {
-      panic!(
-        "Schema is not associated to a Nongoose instance ({})",
-        collection_name
-      );
-    }
+    let cursor = self.find_cursor_sync::<T>(conditions, options)?;
 
-    Ok(
-      self
-        .database
-        .collection::<Document>(collection_name.as_str())
-        .update_many(conditions, data, options)?,
-    )
+    let builder = self.clone();
+    let mut extensions = self.extensions.clone();
+
+    Ok(blocking::Cursor::new(cursor, move |doc| {
+      let mut data: T = T::from_document(doc)?;
+      data.after_find(&mut builder.hook_context(HookOperation::Find, None, &mut extensions))?;
+
+      Ok(data)
+    }))
+  }
+
+  pub(crate) fn find_sync<T>(
+    &self,
+    mut conditions: Document,
+    mut options: Option<FindOptions>,
+    session: Option<&mut sync::ClientSession>,
+  ) -> Result<Vec<T>>
+  where
+    T: Schema + blocking::SchemaAfter,
+  {
+    let collection = self.collection::<T>()?;
+
+    self.middlewares.pre(
+      &T::collection_name(),
+      MiddlewareQuery::Find {
+        conditions: &mut conditions,
+        options: &mut options,
+      },
+    )?;
+
+    let mut extensions = self.extensions.clone();
+    let mut ctx = self.hook_context(HookOperation::Find, session, &mut extensions);
+
+    let documents = match ctx.session() {
+      Some(session) => collection
+        .find_with_session(Some(conditions), options, session)?
+        .iter(session)
+        .collect::<mongodb::error::Result<Vec<Document>>>()?,
+      None => collection
+        .find(Some(conditions), options)?
+        .collect::<mongodb::error::Result<Vec<Document>>>()?,
+    };
+
+    self
+      .middlewares
+      .post(&T::collection_name(), MiddlewareResult::Find(&documents))?;
+
+    documents
+      .into_iter()
+      .map(|doc| {
+        let mut data: T = T::from_document(doc)?;
+        data.after_find(&mut ctx)?;
+
+        Ok(data)
+      })
+      .collect()
+  }
+
+  pub(crate) fn find_one_sync<T>(
+    &self,
+    mut conditions: Document,
+    mut options: Option<FindOneOptions>,
+    session: Option<&mut sync::ClientSession>,
+  ) -> Result<Option<T>>
+  where
+    T: Schema + blocking::SchemaAfter,
+  {
+    let collection = self.collection::<T>()?;
+
+    self.middlewares.pre(
+      &T::collection_name(),
+      MiddlewareQuery::FindOne {
+        conditions: &mut conditions,
+        options: &mut options,
+      },
+    )?;
+
+    let mut extensions = self.extensions.clone();
+    let mut ctx = self.hook_context(HookOperation::Find, session, &mut extensions);
+
+    let document = match ctx.session() {
+      Some(session) => collection.find_one_with_session(Some(conditions), options, session)?,
+      None => collection.find_one(Some(conditions), options)?,
+    };
+
+    self.middlewares.post(
+      &T::collection_name(),
+      MiddlewareResult::FindOne(document.as_ref()),
+    )?;
+
+    let mut data: Option<T> = match document {
+      Some(document) => Some(T::from_document(document)?),
+      None => None,
+    };
+
+    if let Some(data) = data.as_mut() {
+      data.after_find(&mut ctx)?;
+    }
+
+    Ok(data)
+  }
+
+  pub(crate) fn find_one_and_delete_sync<T>(
+    &self,
+    conditions: Document,
+    options: Option<FindOneAndDeleteOptions>,
+    session: Option<&mut sync::ClientSession>,
+  ) -> Result<(bool, Option<T>)>
+  where
+    T: Schema + blocking::SchemaBefore + blocking::SchemaAfter,
+  {
+    let collection = self.collection::<T>()?;
+
+    let mut extensions = self.extensions.clone();
+    let mut ctx = self.hook_context(HookOperation::Delete, session, &mut extensions);
+
+    let find_options = find_one_and_delete_find_options(&options);
+    let mut data = match self.find_one_sync::<T>(conditions.clone(), find_options, ctx.session())? {
+      Some(data) => data,
+      None => return Ok((false, None)),
+    };
+
+    if !data.before_delete(&mut ctx)? {
+      return Ok((false, Some(data)));
+    }
+
+    self.middlewares.pre_remove(&data)?;
+
+    // The document is not removed if it changed and does not match the conditions anymore.
+    let mut conditions = doc! { "$and": [conditions, data.__get_id_query()] };
+    let mut options = options;
+
+    self.middlewares.pre(
+      &T::collection_name(),
+      MiddlewareQuery::FindOneAndDelete {
+        conditions: &mut conditions,
+        options: &mut options,
+      },
+    )?;
+
+    let document = match ctx.session() {
+      Some(session) => collection.find_one_and_delete_with_session(conditions, options, session)?,
+      None => collection.find_one_and_delete(conditions, options)?,
+    };
+
+    self.middlewares.post(
+      &T::collection_name(),
+      MiddlewareResult::FindOneAndDelete(document.as_ref()),
+    )?;
+
+    let mut data: T = match document {
+      Some(document) => T::from_document(document)?,
+      None => return Ok((false, Some(data))),
+    };
+
+    self.middlewares.post_remove(&data)?;
+    data.after_delete(&mut ctx)?;
+
+    Ok((true, Some(data)))
+  }
+
+  pub(crate) fn find_one_and_update_sync<T>(
+    &self,
+    mut conditions: Document,
+    mut data: Document,
+    mut options: Option<FindOneAndUpdateOptions>,
+    session: Option<&mut sync::ClientSession>,
+  ) -> Result<Option<T>>
+  where
+    T: Schema + blocking::SchemaBefore + blocking::SchemaAfter,
+  {
+    let collection = self.collection::<T>()?;
+
+    let mut extensions = self.extensions.clone();
+    let mut ctx = self.hook_context(HookOperation::FindOneAndUpdate, session, &mut extensions);
+
+    T::before_find_one_and_update(&conditions, &mut data, &mut ctx)?;
+
+    T::__update_timestamps(&mut data);
+
+    self.middlewares.pre(
+      &T::collection_name(),
+      MiddlewareQuery::FindOneAndUpdate {
+        conditions: &mut conditions,
+        update: &mut data,
+        options: &mut options,
+      },
+    )?;
+
+    let document = match ctx.session() {
+      Some(session) => {
+        collection.find_one_and_update_with_session(conditions, data, options, session)?
+      }
+      None => collection.find_one_and_update(conditions, data, options)?,
+    };
+
+    self.middlewares.post(
+      &T::collection_name(),
+      MiddlewareResult::FindOneAndUpdate(document.as_ref()),
+    )?;
+
+    let mut result: Option<T> = match document {
+      Some(document) => Some(T::from_document(document)?),
+      None => None,
+    };
+
+    if let Some(result) = result.as_mut() {
+      result.after_find(&mut ctx)?;
+    }
+
+    Ok(result)
+  }
+
+  pub(crate) fn update_many_sync<T>(
+    &self,
+    mut conditions: Document,
+    mut data: Document,
+    mut options: Option<UpdateOptions>,
+    session: Option<&mut sync::ClientSession>,
+  ) -> Result<UpdateResult>
+  where
+    T: Schema,
+  {
+    let collection = self.collection::<T>()?;
+
+    T::__update_timestamps(&mut data);
+
+    self.middlewares.pre(
+      &T::collection_name(),
+      MiddlewareQuery::UpdateMany {
+        conditions: &mut conditions,
+        update: &mut data,
+        options: &mut options,
+      },
+    )?;
+
+    let result = match session {
+      Some(session) => collection.update_many_with_session(conditions, data, options, session)?,
+      None => collection.update_many(conditions, data, options)?,
+    };
+
+    self
+      .middlewares
+      .post(&T::collection_name(), MiddlewareResult::UpdateMany(&result))?;
+
+    Ok(result)
+  }
+
+  pub(crate) fn populate_sync<T>(
+    &self,
+    mut data: T,
+    field: &str,
+    read_concern: Option<ReadConcern>,
+    mut session: Option<&mut sync::ClientSession>,
+  ) -> Result<T>
+  where
+    T: Schema,
+  {
+    let find_options = FindOptions::builder()
+      .read_concern(read_concern.clone())
+      .build();
+    let find_one_options = FindOneOptions::builder().read_concern(read_concern).build();
+
+    for population in self.populations(&data, field)? {
+      let collection = self
+        .database
+        .collection::<Document>(population.collection_name.as_str());
+
+      let mut conditions = population.conditions;
+
+      if population.many {
+        let mut options = Some(find_options.clone());
+
+        self.middlewares.pre(
+          &population.collection_name,
+          MiddlewareQuery::Find {
+            conditions: &mut conditions,
+            options: &mut options,
+          },
+        )?;
+
+        let documents = match session.as_deref_mut() {
+          Some(session) => collection
+            .find_with_session(Some(conditions), options, session)?
+            .iter(session)
+            .collect::<mongodb::error::Result<Vec<Document>>>()?,
+          None => collection
+            .find(Some(conditions), options)?
+            .collect::<mongodb::error::Result<Vec<Document>>>()?,
+        };
+
+        self.middlewares.post(
+          &population.collection_name,
+          MiddlewareResult::Find(&documents),
+        )?;
+
+        data.__set_relations(field, bson!(documents))?;
+      } else {
+        let mut options = Some(find_one_options.clone());
+
+        self.middlewares.pre(
+          &population.collection_name,
+          MiddlewareQuery::FindOne {
+            conditions: &mut conditions,
+            options: &mut options,
+          },
+        )?;
+
+        let doc = match session.as_deref_mut() {
+          Some(session) => collection.find_one_with_session(Some(conditions), options, session)?,
+          None => collection.find_one(Some(conditions), options)?,
+        };
+
+        self.middlewares.post(
+          &population.collection_name,
+          MiddlewareResult::FindOne(doc.as_ref()),
+        )?;
+
+        if let Some(doc) = doc {
+          data.__set_relations(field, Bson::Document(doc))?;
+        }
+      }
+    }
+
+    Ok(data)
+  }
+
+  pub(crate) fn remove_sync<T>(
+    &self,
+    data: &T,
+    write_concern: Option<WriteConcern>,
+    session: Option<&mut sync::ClientSession>,
+  ) -> Result<bool>
+  where
+    T: Schema + blocking::SchemaBefore + blocking::SchemaAfter,
+  {
+    let collection = self.collection::<T>()?;
+
+    let mut data = data.clone();
+    let mut extensions = self.extensions.clone();
+    let mut ctx = self.hook_context(HookOperation::Delete, session, &mut extensions);
+
+    if !data.before_delete(&mut ctx)? {
+      return Ok(false);
+    }
+
+    self.middlewares.pre_remove(&data)?;
+
+    let options = Some(
+      DeleteOptions::builder()
+        .write_concern(write_concern)
+        .build(),
+    );
+    let result = match ctx.session() {
+      Some(session) => {
+        collection.delete_one_with_session(data.__get_id_query(), options, session)?
+      }
+      None => collection.delete_one(data.__get_id_query(), options)?,
+    };
+
+    if result.deleted_count != 1 {
+      return Ok(false);
+    }
+
+    self.middlewares.post_remove(&data)?;
+    data.after_delete(&mut ctx)?;
+    Ok(true)
+  }
+
+  pub(crate) fn save_sync<T>(
+    &self,
+    data: &mut T,
+    write_concern: Option<WriteConcern>,
+    mut session: Option<&mut sync::ClientSession>,
+  ) -> Result<T>
+  where
+    T: Schema + blocking::SchemaBefore + blocking::SchemaAfter,
+  {
+    // The document is validated before querying the database.
+    validation::validate_blocking(data)?;
+
+    let collection = self.collection::<T>()?;
+
+    let mut extensions = self.extensions.clone();
+
+    self.check_unique_fields_sync(data, session.as_deref_mut())?;
+
+    let exists = match session.as_deref_mut() {
+      Some(session) => {
+        collection.find_one_with_session(Some(data.__get_id_query()), None, session)?
+      }
+      None => collection.find_one(Some(data.__get_id_query()), None)?,
+    }
+    .is_some();
+
+    if exists {
+      data.__set_timestamps(false);
+      let mut ctx = self.hook_context(HookOperation::Update, session, &mut extensions);
+      data.before_update(&mut ctx)?;
+
+      let id_query = data.__get_id_query();
+      let document = self.middlewares.pre_save(data, false)?;
+
+      let options = Some(
+        ReplaceOptions::builder()
+          .upsert(true)
+          .write_concern(write_concern)
+          .build(),
+      );
+      match ctx.session() {
+        Some(session) => {
+          collection.replace_one_with_session(id_query, document.clone(), options, session)?
+        }
+        None => collection.replace_one(id_query, document.clone(), options)?,
+      };
+
+      self.middlewares.post_save::<T>(&document, false)?;
+      data.after_update(&mut ctx)?;
+    } else {
+      data.__set_timestamps(true);
+      let mut ctx = self.hook_context(HookOperation::Create, session, &mut extensions);
+      data.before_create(&mut ctx)?;
+
+      let document = self.middlewares.pre_save(data, true)?;
+
+      let options = Some(
+        InsertOneOptions::builder()
+          .write_concern(write_concern)
+          .build(),
+      );
+
+      match ctx.session() {
+        Some(session) => collection.insert_one_with_session(document.clone(), options, session)?,
+        None => collection.insert_one(document.clone(), options)?,
+      };
+
+      self.middlewares.post_save::<T>(&document, true)?;
+      data.after_create(&mut ctx)?;
+    }
+
+    Ok(data.clone())
+  }
+}
+
+#[cfg(feature = "tokio-runtime")]
+impl NongooseBuilder<mongodb::Database> {
+  /// Set the MongoDB client of the database (required by `Nongoose.transaction()`).
+  pub fn client(mut self, client: mongodb::Client) -> Self {
+    self.client = Some(client);
+    self
+  }
+
+  /// Return the Nongoose instance.
+  pub fn build(&self) -> crate::Nongoose {
+    crate::Nongoose {
+      builder: self.clone(),
+    }
+  }
+
+  /// Return the Nongoose instance after verifying that the Schemas used by the relations of the registered
+  /// Schemas are registered too; otherwise, returns `Error::SchemaNotRegistered`.
+  pub fn build_strict(&self) -> Result<crate::Nongoose> {
+    self.check_relations()?;
+    Ok(self.build())
+  }
+
+  /// Create the indexes of the registered Schemas (declared with `#[schema(index)]` or added by the plugins) that
+  /// do not exist in their collections, and update the time to live of the TTL indexes (`collMod`). If
+  /// `drop_undeclared` is `true`, the indexes of the collections that are not declared are dropped (except the
+  /// `_id` index).
+  ///
+  /// The operations never create the indexes, unless `NongooseBuilder.auto_index(true)` is set.
+  pub async fn sync_indexes(&self, drop_undeclared: bool) -> Result<()> {
+    for schema in self.schemas.iter() {
+      self.sync_schema_indexes(schema, drop_undeclared).await?;
+    }
+
+    Ok(())
+  }
+
+  // Internals
+  /// Try `auto_index` for every registered Schema, before a transaction starts.
+  pub(crate) async fn auto_index_schemas(&self) {
+    for schema in self.schemas.iter() {
+      if self.needs_auto_index(&schema.get_name()) {
+        let _ = self.sync_schema_indexes(schema, false).await;
+      }
+    }
+  }
+
+  pub(crate) fn hook_context<'a>(
+    &'a self,
+    operation: HookOperation,
+    session: Option<&'a mut ClientSession>,
+    extensions: &'a mut Extensions,
+  ) -> crate::HookContext<'a> {
+    crate::HookContext::new(operation, self, session, extensions)
+  }
+
+  pub(crate) async fn collection<T>(&self) -> Result<mongodb::Collection<Document>>
+  where
+    T: Schema,
+  {
+    let collection_name = self.collection_name::<T>()?;
+    if self.needs_auto_index(&collection_name) {
+      // `auto_index` never fails the operation
+      let _ = self.ensure_indexes::<T>().await;
+    }
+
+    Ok(
+      self
+        .database
+        .collection::<Document>(collection_name.as_str()),
+    )
+  }
+
+  async fn check_unique_fields<T>(
+    &self,
+    data: &T,
+    mut session: Option<&mut ClientSession>,
+  ) -> Result<()>
+  where
+    T: Schema,
+  {
+    let collection = self.collection::<T>().await?;
+    let id: Bson = data.__get_id().into();
+
+    for (document, field, value) in self.unique_fields(data) {
+      let doc = match session.as_deref_mut() {
+        Some(session) => {
+          collection
+            .find_one_with_session(document, None, session)
+            .await?
+        }
+        None => collection.find_one(document, None).await?,
+      };
+
+      if let Some(doc) = doc {
+        if doc.get("_id") != Some(&id) {
+          return Err(Error::DuplicatedSchemaField(field, value));
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  pub(crate) async fn aggregate_cursor<S>(
+    &self,
+    mut pipeline: Vec<Document>,
+    mut options: Option<AggregateOptions>,
+  ) -> Result<mongodb::Cursor<Document>>
+  where
+    S: Schema,
+  {
+    let collection = self.collection::<S>().await?;
+
+    self.middlewares.pre(
+      &S::collection_name(),
+      MiddlewareQuery::Aggregate {
+        pipeline: &mut pipeline,
+        options: &mut options,
+      },
+    )?;
+
+    Ok(collection.aggregate(pipeline, options).await?)
+  }
+
+  pub(crate) async fn aggregate<S, T>(
+    &self,
+    mut pipeline: Vec<Document>,
+    mut options: Option<AggregateOptions>,
+    session: Option<&mut ClientSession>,
+  ) -> Result<Vec<T>>
+  where
+    S: Schema,
+    T: From<Document>,
+  {
+    let collection = self.collection::<S>().await?;
+
+    self.middlewares.pre(
+      &S::collection_name(),
+      MiddlewareQuery::Aggregate {
+        pipeline: &mut pipeline,
+        options: &mut options,
+      },
+    )?;
+
+    let documents: Vec<Document> = match session {
+      Some(session) => {
+        collection
+          .aggregate_with_session(pipeline, options, session)
+          .await?
+          .stream(session)
+          .try_collect()
+          .await?
+      }
+      None => {
+        collection
+          .aggregate(pipeline, options)
+          .await?
+          .try_collect()
+          .await?
+      }
+    };
+
+    self.middlewares.post(
+      &S::collection_name(),
+      MiddlewareResult::Aggregate(&documents),
+    )?;
+
+    Ok(documents.into_iter().map(Into::into).collect())
+  }
+
+  pub(crate) async fn count<T>(
+    &self,
+    mut conditions: Document,
+    mut options: Option<CountOptions>,
+    session: Option<&mut ClientSession>,
+  ) -> Result<u64>
+  where
+    T: Schema,
+  {
+    let collection = self.collection::<T>().await?;
+
+    self.middlewares.pre(
+      &T::collection_name(),
+      MiddlewareQuery::Count {
+        conditions: &mut conditions,
+        options: &mut options,
+      },
+    )?;
+
+    let count = match session {
+      Some(session) => {
+        collection
+          .count_documents_with_session(conditions, options, session)
+          .await?
+      }
+      None => collection.count_documents(conditions, options).await?,
+    };
+
+    self
+      .middlewares
+      .post(&T::collection_name(), MiddlewareResult::Count(count))?;
+
+    Ok(count)
+  }
+
+  pub(crate) async fn delete_many<T>(
+    &self,
+    mut conditions: Document,
+    mut options: Option<DeleteOptions>,
+    session: Option<&mut ClientSession>,
+  ) -> Result<DeleteResult>
+  where
+    T: Schema,
+  {
+    let collection = self.collection::<T>().await?;
+
+    self.middlewares.pre(
+      &T::collection_name(),
+      MiddlewareQuery::DeleteMany {
+        conditions: &mut conditions,
+        options: &mut options,
+      },
+    )?;
+
+    let result = match session {
+      Some(session) => {
+        collection
+          .delete_many_with_session(conditions, options, session)
+          .await?
+      }
+      None => collection.delete_many(conditions, options).await?,
+    };
+
+    self
+      .middlewares
+      .post(&T::collection_name(), MiddlewareResult::DeleteMany(&result))?;
+
+    Ok(result)
+  }
+
+  pub(crate) async fn ensure_indexes<T>(&self) -> Result<()>
+  where
+    T: Schema,
+  {
+    let collection_name = self.collection_name::<T>()?;
+    if let Some(schema) = self.get_schema(&collection_name) {
+      self.sync_schema_indexes(schema, false).await?;
+    }
+
+    Ok(())
+  }
+
+  async fn sync_schema_indexes(&self, schema: &SchemaData, drop_undeclared: bool) -> Result<()> {
+    let collection = self
+      .database
+      .collection::<Document>(schema.get_name().as_str());
+
+    let existing = match collection.list_indexes(None).await {
+      Ok(cursor) => cursor.try_collect::<Vec<_>>().await?,
+      Err(error) if is_namespace_not_found(&error) => Vec::new(),
+      Err(error) => return Err(error.into()),
+    };
+
+    let changes = IndexChanges::new(schema.get_indexes(), existing, drop_undeclared);
+    for name in changes.dropped {
+      collection.drop_index(name, None).await?;
+    }
+
+    for index in changes.modified {
+      self
+        .database
+        .run_command(doc! { "collMod": schema.get_name(), "index": index }, None)
+        .await?;
+    }
+
+    if !changes.created.is_empty() {
+      collection.create_indexes(changes.created, None).await?;
+    }
+
+    self.set_synced_indexes(schema.get_name());
+    Ok(())
+  }
+
+  pub(crate) async fn find_and_delete<T>(
+    &self,
+    conditions: Document,
+    options: Option<FindOptions>,
+    write_concern: Option<WriteConcern>,
+    session: Option<&mut ClientSession>,
+  ) -> Result<Vec<(bool, T)>>
+  where
+    T: Schema + crate::SchemaBefore + crate::SchemaAfter,
+  {
+    let mut extensions = self.extensions.clone();
+    let mut ctx = self.hook_context(HookOperation::Delete, session, &mut extensions);
+
+    let mut result = Vec::new();
+    for mut data in self
+      .find::<T>(conditions.clone(), options, ctx.session())
+      .await?
+    {
+      let remove = data.before_delete(&mut ctx).await?;
+      if remove {
+        self.middlewares.pre_remove(&data)?;
+      }
+
+      result.push((remove, data));
+    }
+
+    // Each document is deleted with the conditions, so the documents that changed and do not match them anymore
+    // (or were deleted by another client) are not reported as removed.
+    let options = DeleteOptions::builder()
+      .write_concern(write_concern)
+      .build();
+    for (removed, data) in result.iter_mut().filter(|(removed, _)| *removed) {
+      let conditions = doc! { "$and": [conditions.clone(), data.__get_id_query()] };
+      let deleted = self
+        .delete_many::<T>(conditions, Some(options.clone()), ctx.session())
+        .await?;
+
+      *removed = deleted.deleted_count > 0;
+    }
+
+    for (_, data) in result.iter_mut().filter(|(removed, _)| *removed) {
+      self.middlewares.post_remove(data)?;
+      data.after_delete(&mut ctx).await?;
+    }
+
+    Ok(result)
+  }
+
+  pub(crate) async fn find_cursor<T>(
+    &self,
+    mut conditions: Document,
+    mut options: Option<FindOptions>,
+  ) -> Result<mongodb::Cursor<Document>>
+  where
+    T: Schema,
+  {
+    let collection = self.collection::<T>().await?;
+
+    self.middlewares.pre(
+      &T::collection_name(),
+      MiddlewareQuery::Find {
+        conditions: &mut conditions,
+        options: &mut options,
+      },
+    )?;
+
+    Ok(collection.find(Some(conditions), options).await?)
+  }
+
+  pub(crate) async fn find_stream<T>(
+    &self,
+    conditions: Document,
+    options: Option<FindOptions>,
+  ) -> Result<super::Cursor<T>>
+  where
+    T: Schema + crate::SchemaAfter + 'static,
+  {
+    let cursor = self.find_cursor::<T>(conditions, options).await?;
+
+    let state = (cursor, self.clone(), self.extensions.clone());
+    let stream =
+      futures::stream::unfold(state, |(mut cursor, builder, mut extensions)| async move {
+        let doc = cursor.next().await?;
+
+        let data = async {
+          let mut data: T = T::from_document(doc?)?;
+          data
+            .after_find(&mut builder.hook_context(HookOperation::Find, None, &mut extensions))
+            .await?;
+
+          Ok(data)
+        }
+        .await;
+
+        Some((data, (cursor, builder, extensions)))
+      });
+
+    Ok(super::Cursor::from_stream(stream.boxed()))
+  }
+
+  pub(crate) async fn find<T>(
+    &self,
+    mut conditions: Document,
+    mut options: Option<FindOptions>,
+    session: Option<&mut ClientSession>,
+  ) -> Result<Vec<T>>
+  where
+    T: Schema + crate::SchemaAfter,
+  {
+    let collection = self.collection::<T>().await?;
+
+    self.middlewares.pre(
+      &T::collection_name(),
+      MiddlewareQuery::Find {
+        conditions: &mut conditions,
+        options: &mut options,
+      },
+    )?;
+
+    let mut extensions = self.extensions.clone();
+    let mut ctx = self.hook_context(HookOperation::Find, session, &mut extensions);
+
+    let documents: Vec<Document> = match ctx.session() {
+      Some(session) => {
+        collection
+          .find_with_session(Some(conditions), options, session)
+          .await?
+          .stream(session)
+          .try_collect()
+          .await?
+      }
+      None => {
+        collection
+          .find(Some(conditions), options)
+          .await?
+          .try_collect()
+          .await?
+      }
+    };
+
+    self
+      .middlewares
+      .post(&T::collection_name(), MiddlewareResult::Find(&documents))?;
+
+    let mut result = Vec::new();
+    for doc in documents {
+      let mut data: T = T::from_document(doc)?;
+      data.after_find(&mut ctx).await?;
+
+      result.push(data);
+    }
+
+    Ok(result)
+  }
+
+  pub(crate) async fn find_one<T>(
+    &self,
+    mut conditions: Document,
+    mut options: Option<FindOneOptions>,
+    session: Option<&mut ClientSession>,
+  ) -> Result<Option<T>>
+  where
+    T: Schema + crate::SchemaAfter,
+  {
+    let collection = self.collection::<T>().await?;
+
+    self.middlewares.pre(
+      &T::collection_name(),
+      MiddlewareQuery::FindOne {
+        conditions: &mut conditions,
+        options: &mut options,
+      },
+    )?;
+
+    let mut extensions = self.extensions.clone();
+    let mut ctx = self.hook_context(HookOperation::Find, session, &mut extensions);
+
+    let document = match ctx.session() {
+      Some(session) => {
+        collection
+          .find_one_with_session(Some(conditions), options, session)
+          .await?
+      }
+      None => collection.find_one(Some(conditions), options).await?,
+    };
+
+    self.middlewares.post(
+      &T::collection_name(),
+      MiddlewareResult::FindOne(document.as_ref()),
+    )?;
+
+    let mut data: Option<T> = match document {
+      Some(document) => Some(T::from_document(document)?),
+      None => None,
+    };
+
+    if let Some(data) = data.as_mut() {
+      data.after_find(&mut ctx).await?;
+    }
+
+    Ok(data)
+  }
+
+  pub(crate) async fn find_one_and_delete<T>(
+    &self,
+    conditions: Document,
+    options: Option<FindOneAndDeleteOptions>,
+    session: Option<&mut ClientSession>,
+  ) -> Result<(bool, Option<T>)>
+  where
+    T: Schema + crate::SchemaBefore + crate::SchemaAfter,
+  {
+    let collection = self.collection::<T>().await?;
+
+    let mut extensions = self.extensions.clone();
+    let mut ctx = self.hook_context(HookOperation::Delete, session, &mut extensions);
+
+    let find_options = find_one_and_delete_find_options(&options);
+    let mut data = match self
+      .find_one::<T>(conditions.clone(), find_options, ctx.session())
+      .await?
+    {
+      Some(data) => data,
+      None => return Ok((false, None)),
+    };
+
+    if !data.before_delete(&mut ctx).await? {
+      return Ok((false, Some(data)));
+    }
+
+    self.middlewares.pre_remove(&data)?;
+
+    // The document is not removed if it changed and does not match the conditions anymore.
+    let mut conditions = doc! { "$and": [conditions, data.__get_id_query()] };
+    let mut options = options;
+
+    self.middlewares.pre(
+      &T::collection_name(),
+      MiddlewareQuery::FindOneAndDelete {
+        conditions: &mut conditions,
+        options: &mut options,
+      },
+    )?;
+
+    let document = match ctx.session() {
+      Some(session) => {
+        collection
+          .find_one_and_delete_with_session(conditions, options, session)
+          .await?
+      }
+      None => collection.find_one_and_delete(conditions, options).await?,
+    };
+
+    self.middlewares.post(
+      &T::collection_name(),
+      MiddlewareResult::FindOneAndDelete(document.as_ref()),
+    )?;
+
+    let mut data: T = match document {
+      Some(document) => T::from_document(document)?,
+      None => return Ok((false, Some(data))),
+    };
+
+    self.middlewares.post_remove(&data)?;
+    data.after_delete(&mut ctx).await?;
+
+    Ok((true, Some(data)))
+  }
+
+  pub(crate) async fn find_one_and_update<T>(
+    &self,
+    mut conditions: Document,
+    mut data: Document,
+    mut options: Option<FindOneAndUpdateOptions>,
+    session: Option<&mut ClientSession>,
+  ) -> Result<Option<T>>
+  where
+    T: Schema + crate::SchemaBefore + crate::SchemaAfter,
+  {
+    let collection = self.collection::<T>().await?;
+
+    let mut extensions = self.extensions.clone();
+    let mut ctx = self.hook_context(HookOperation::FindOneAndUpdate, session, &mut extensions);
+
+    T::before_find_one_and_update(&conditions, &mut data, &mut ctx).await?;
+
+    T::__update_timestamps(&mut data);
+
+    self.middlewares.pre(
+      &T::collection_name(),
+      MiddlewareQuery::FindOneAndUpdate {
+        conditions: &mut conditions,
+        update: &mut data,
+        options: &mut options,
+      },
+    )?;
+
+    let document = match ctx.session() {
+      Some(session) => {
+        collection
+          .find_one_and_update_with_session(conditions, data, options, session)
+          .await?
+      }
+      None => {
+        collection
+          .find_one_and_update(conditions, data, options)
+          .await?
+      }
+    };
+
+    self.middlewares.post(
+      &T::collection_name(),
+      MiddlewareResult::FindOneAndUpdate(document.as_ref()),
+    )?;
+
+    let mut result: Option<T> = match document {
+      Some(document) => Some(T::from_document(document)?),
+      None => None,
+    };
+
+    if let Some(result) = result.as_mut() {
+      result.after_find(&mut ctx).await?;
+    }
+
+    Ok(result)
+  }
+
+  pub(crate) async fn populate<T>(
+    &self,
+    mut data: T,
+    field: &str,
+    read_concern: Option<ReadConcern>,
+    mut session: Option<&mut ClientSession>,
+  ) -> Result<T>
+  where
+    T: Schema,
+  {
+    let find_options = FindOptions::builder()
+      .read_concern(read_concern.clone())
+      .build();
+    let find_one_options = FindOneOptions::builder().read_concern(read_concern).build();
+
+    for population in self.populations(&data, field)? {
+      let collection = self
+        .database
+        .collection::<Document>(population.collection_name.as_str());
+
+      let mut conditions = population.conditions;
+
+      if population.many {
+        let mut options = Some(find_options.clone());
+
+        self.middlewares.pre(
+          &population.collection_name,
+          MiddlewareQuery::Find {
+            conditions: &mut conditions,
+            options: &mut options,
+          },
+        )?;
+
+        let documents: Vec<Document> = match session.as_deref_mut() {
+          Some(session) => {
+            collection
+              .find_with_session(Some(conditions), options, session)
+              .await?
+              .stream(session)
+              .try_collect()
+              .await?
+          }
+          None => {
+            collection
+              .find(Some(conditions), options)
+              .await?
+              .try_collect()
+              .await?
+          }
+        };
+
+        self.middlewares.post(
+          &population.collection_name,
+          MiddlewareResult::Find(&documents),
+        )?;
+
+        data.__set_relations(field, bson!(documents))?;
+      } else {
+        let mut options = Some(find_one_options.clone());
+
+        self.middlewares.pre(
+          &population.collection_name,
+          MiddlewareQuery::FindOne {
+            conditions: &mut conditions,
+            options: &mut options,
+          },
+        )?;
+
+        let doc = match session.as_deref_mut() {
+          Some(session) => {
+            collection
+              .find_one_with_session(Some(conditions), options, session)
+              .await?
+          }
+          None => collection.find_one(Some(conditions), options).await?,
+        };
+
+        self.middlewares.post(
+          &population.collection_name,
+          MiddlewareResult::FindOne(doc.as_ref()),
+        )?;
+
+        if let Some(doc) = doc {
+          data.__set_relations(field, Bson::Document(doc))?;
+        }
+      }
+    }
+
+    Ok(data)
+  }
+
+  pub(crate) async fn remove<T>(
+    &self,
+    data: &T,
+    write_concern: Option<WriteConcern>,
+    session: Option<&mut ClientSession>,
+  ) -> Result<bool>
+  where
+    T: Schema + crate::SchemaBefore + crate::SchemaAfter,
+  {
+    let collection = self.collection::<T>().await?;
+
+    let mut data = data.clone();
+    let mut extensions = self.extensions.clone();
+    let mut ctx = self.hook_context(HookOperation::Delete, session, &mut extensions);
+
+    if !data.before_delete(&mut ctx).await? {
+      return Ok(false);
+    }
+
+    self.middlewares.pre_remove(&data)?;
+
+    let options = Some(
+      DeleteOptions::builder()
+        .write_concern(write_concern)
+        .build(),
+    );
+    let result = match ctx.session() {
+      Some(session) => {
+        collection
+          .delete_one_with_session(data.__get_id_query(), options, session)
+          .await?
+      }
+      None => {
+        collection
+          .delete_one(data.__get_id_query(), options)
+          .await?
+      }
+    };
+
+    if result.deleted_count != 1 {
+      return Ok(false);
+    }
+
+    self.middlewares.post_remove(&data)?;
+    data.after_delete(&mut ctx).await?;
+    Ok(true)
+  }
+
+  pub(crate) async fn save<T>(
+    &self,
+    data: &mut T,
+    write_concern: Option<WriteConcern>,
+    mut session: Option<&mut ClientSession>,
+  ) -> Result<T>
+  where
+    T: Schema + crate::SchemaBefore + crate::SchemaAfter,
+  {
+    // The document is validated before querying the database (the async validators need the context of the
+    // operation, so they are called later).
+    data.validate()?;
+
+    let collection = self.collection::<T>().await?;
+
+    let mut extensions = self.extensions.clone();
+
+    self
+      .check_unique_fields(data, session.as_deref_mut())
+      .await?;
+
+    let exists = match session.as_deref_mut() {
+      Some(session) => {
+        collection
+          .find_one_with_session(Some(data.__get_id_query()), None, session)
+          .await?
+      }
+      None => {
+        collection
+          .find_one(Some(data.__get_id_query()), None)
+          .await?
+      }
+    }
+    .is_some();
+
+    if exists {
+      data.__set_timestamps(false);
+      let mut ctx = self.hook_context(HookOperation::Update, session, &mut extensions);
+      validation::validate_async(data, &mut ctx).await?;
+      data.before_update(&mut ctx).await?;
+
+      let id_query = data.__get_id_query();
+      let document = self.middlewares.pre_save(data, false)?;
+
+      let options = Some(
+        ReplaceOptions::builder()
+          .upsert(true)
+          .write_concern(write_concern)
+          .build(),
+      );
+      match ctx.session() {
+        Some(session) => {
+          collection
+            .replace_one_with_session(id_query, document.clone(), options, session)
+            .await?
+        }
+        None => {
+          collection
+            .replace_one(id_query, document.clone(), options)
+            .await?
+        }
+      };
+
+      self.middlewares.post_save::<T>(&document, false)?;
+      data.after_update(&mut ctx).await?;
+    } else {
+      data.__set_timestamps(true);
+      let mut ctx = self.hook_context(HookOperation::Create, session, &mut extensions);
+      validation::validate_async(data, &mut ctx).await?;
+      data.before_create(&mut ctx).await?;
+
+      let document = self.middlewares.pre_save(data, true)?;
+
+      let options = Some(
+        InsertOneOptions::builder()
+          .write_concern(write_concern)
+          .build(),
+      );
+
+      match ctx.session() {
+        Some(session) => {
+          collection
+            .insert_one_with_session(document.clone(), options, session)
+            .await?
+        }
+        None => collection.insert_one(document.clone(), options).await?,
+      };
+
+      self.middlewares.post_save::<T>(&document, true)?;
+      data.after_create(&mut ctx).await?;
+    }
+
+    Ok(data.clone())
+  }
+
+  pub(crate) async fn update_many<T>(
+    &self,
+    mut conditions: Document,
+    mut data: Document,
+    mut options: Option<UpdateOptions>,
+    session: Option<&mut ClientSession>,
+  ) -> Result<UpdateResult>
+  where
+    T: Schema,
+  {
+    let collection = self.collection::<T>().await?;
+
+    T::__update_timestamps(&mut data);
+
+    self.middlewares.pre(
+      &T::collection_name(),
+      MiddlewareQuery::UpdateMany {
+        conditions: &mut conditions,
+        update: &mut data,
+        options: &mut options,
+      },
+    )?;
+
+    let result = match session {
+      Some(session) => {
+        collection
+          .update_many_with_session(conditions, data, options, session)
+          .await?
+      }
+      None => collection.update_many(conditions, data, options).await?,
+    };
+
+    self
+      .middlewares
+      .post(&T::collection_name(), MiddlewareResult::UpdateMany(&result))?;
+
+    Ok(result)
   }
 }
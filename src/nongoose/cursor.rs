@@ -0,0 +1,55 @@
+use std::{
+  pin::Pin,
+  task::{Context, Poll},
+};
+
+use futures::{stream::BoxStream, Stream, StreamExt};
+use mongodb::bson::Document;
+
+use crate::error::Result;
+
+/// Lazy cursor over the documents returned by a query.
+///
+/// Each document is deserialized only when it is consumed, so the whole result set is never loaded in memory.
+///
+/// `Cursor<T>` is a `futures::Stream<Item = nongoose::Result<T>>`. The blocking API returns a
+/// `nongoose::blocking::Cursor<T>` instead, which is an `Iterator`.
+pub struct Cursor<T> {
+  inner: CursorInner<T>,
+}
+
+enum CursorInner<T> {
+  Map(Box<mongodb::Cursor<Document>>, fn(Document) -> Result<T>),
+  Stream(BoxStream<'static, Result<T>>),
+}
+
+impl<T> Cursor<T> {
+  pub(crate) fn new(cursor: mongodb::Cursor<Document>, map: fn(Document) -> Result<T>) -> Self {
+    Self {
+      inner: CursorInner::Map(Box::new(cursor), map),
+    }
+  }
+
+  /// Cursor over a stream that runs the `SchemaAfter` functions of each document.
+  pub(crate) fn from_stream(stream: BoxStream<'static, Result<T>>) -> Self {
+    Self {
+      inner: CursorInner::Stream(stream),
+    }
+  }
+}
+
+impl<T> Stream for Cursor<T> {
+  type Item = Result<T>;
+
+  fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+    match &mut self.inner {
+      CursorInner::Map(cursor, map) => {
+        let map = *map;
+        cursor
+          .poll_next_unpin(cx)
+          .map(|doc| doc.map(|doc| doc.map_err(Into::into).and_then(map)))
+      }
+      CursorInner::Stream(stream) => stream.poll_next_unpin(cx),
+    }
+  }
+}
@@ -0,0 +1,699 @@
+use std::marker::PhantomData;
+
+#[cfg(any(feature = "sync", feature = "tokio-runtime"))]
+use mongodb::results::{DeleteResult, UpdateResult};
+#[cfg(feature = "sync")]
+use mongodb::sync;
+use mongodb::{
+  bson::{doc, Document},
+  options::{
+    AggregateOptions, Collation, CountOptions, DeleteOptions, FindOneAndDeleteOptions,
+    FindOneAndUpdateOptions, FindOneOptions, FindOptions, ReadConcern, UpdateOptions, WriteConcern,
+  },
+};
+
+use super::{builder::DefaultDatabase, NongooseBuilder, Query};
+#[cfg(feature = "sync")]
+use crate::blocking;
+use crate::{error::Result, Schema};
+
+/// Handle over the collection of a Schema, created with `Nongoose.model::<Schema>()`.
+///
+/// It has the same methods as `Nongoose` without the Schema generic, and it can be cloned and stored (e.g. in the
+/// state of an application). It also carries defaults that are used by all its operations: read concern, write
+/// concern, collation and projection. The options passed to a method take precedence over the defaults.
+///
+/// # Example
+/// ```rust,no_run,ignore
+/// let users = nongoose
+///   .model::<User>()
+///   .write_concern(WriteConcern::builder().w(Acknowledgment::Majority).build())
+///   .select(doc! { "password": 0 });
+///
+/// let user = users.find_by_id(&id).await?;
+/// let adults = users.count(doc! { "age": { "$gte": 18 } }, None).await?;
+/// ```
+///
+/// `D` is the database of the `Nongoose` instance that created the model: `nongoose::Database` for the
+/// async API, or `nongoose::blocking::Database` for the blocking one (see `nongoose::blocking::Model`).
+#[derive(Clone)]
+pub struct Model<T, D = DefaultDatabase>
+where
+  T: Schema,
+{
+  builder: NongooseBuilder<D>,
+  collation: Option<Collation>,
+  projection: Option<Document>,
+  read_concern: Option<ReadConcern>,
+  write_concern: Option<WriteConcern>,
+  _schema: PhantomData<fn() -> T>,
+}
+
+impl<T, D> Model<T, D>
+where
+  T: Schema,
+{
+  pub(crate) fn new(builder: NongooseBuilder<D>) -> Self {
+    Self {
+      builder,
+      collation: None,
+      projection: None,
+      read_concern: None,
+      write_concern: None,
+      _schema: PhantomData,
+    }
+  }
+
+  /// Sets the default read concern of the operations.
+  pub fn read_concern(mut self, read_concern: ReadConcern) -> Self {
+    self.read_concern = Some(read_concern);
+    self
+  }
+
+  /// Sets the default write concern of the operations.
+  pub fn write_concern(mut self, write_concern: WriteConcern) -> Self {
+    self.write_concern = Some(write_concern);
+    self
+  }
+
+  /// Sets the default [collation](https://docs.mongodb.com/manual/reference/collation/) of the operations.
+  pub fn collation(mut self, collation: Collation) -> Self {
+    self.collation = Some(collation);
+    self
+  }
+
+  /// Sets the default projection of the documents found.
+  ///
+  /// The excluded fields must be optional (or have a serde default) in the Schema to be deserialized.
+  pub fn select(mut self, projection: Document) -> Self {
+    self.projection = Some(projection);
+    self
+  }
+
+  /// Creates a lazy and chainable query with the defaults of the model.
+  pub fn query(&self) -> Query<T, D>
+  where
+    D: Clone,
+  {
+    let mut query = Query::new(self.builder.clone());
+
+    if let Some(collation) = self.collation.clone() {
+      query = query.collation(collation);
+    }
+
+    if let Some(projection) = self.projection.clone() {
+      query = query.select(projection);
+    }
+
+    if let Some(read_concern) = self.read_concern.clone() {
+      query = query.read_concern(read_concern);
+    }
+
+    query
+  }
+
+  // Internals
+  fn aggregate_options(&self, options: Option<AggregateOptions>) -> Option<AggregateOptions> {
+    let mut options = options.unwrap_or_default();
+    if options.collation.is_none() {
+      options.collation = self.collation.clone();
+    }
+
+    if options.read_concern.is_none() {
+      options.read_concern = self.read_concern.clone();
+    }
+
+    if options.write_concern.is_none() {
+      options.write_concern = self.write_concern.clone();
+    }
+
+    Some(options)
+  }
+
+  fn count_options(&self, options: Option<CountOptions>) -> Option<CountOptions> {
+    let mut options = options.unwrap_or_default();
+    if options.collation.is_none() {
+      options.collation = self.collation.clone();
+    }
+
+    if options.read_concern.is_none() {
+      options.read_concern = self.read_concern.clone();
+    }
+
+    Some(options)
+  }
+
+  fn delete_options(&self, options: Option<DeleteOptions>) -> Option<DeleteOptions> {
+    let mut options = options.unwrap_or_default();
+    if options.collation.is_none() {
+      options.collation = self.collation.clone();
+    }
+
+    if options.write_concern.is_none() {
+      options.write_concern = self.write_concern.clone();
+    }
+
+    Some(options)
+  }
+
+  fn find_options(&self, options: Option<FindOptions>) -> Option<FindOptions> {
+    let mut options = options.unwrap_or_default();
+    if options.collation.is_none() {
+      options.collation = self.collation.clone();
+    }
+
+    if options.projection.is_none() {
+      options.projection = self.projection.clone();
+    }
+
+    if options.read_concern.is_none() {
+      options.read_concern = self.read_concern.clone();
+    }
+
+    Some(options)
+  }
+
+  fn find_one_and_delete_options(
+    &self,
+    options: Option<FindOneAndDeleteOptions>,
+  ) -> Option<FindOneAndDeleteOptions> {
+    let mut options = options.unwrap_or_default();
+    if options.collation.is_none() {
+      options.collation = self.collation.clone();
+    }
+
+    if options.projection.is_none() {
+      options.projection = self.projection.clone();
+    }
+
+    if options.write_concern.is_none() {
+      options.write_concern = self.write_concern.clone();
+    }
+
+    Some(options)
+  }
+
+  fn find_one_and_update_options(
+    &self,
+    options: Option<FindOneAndUpdateOptions>,
+  ) -> Option<FindOneAndUpdateOptions> {
+    let mut options = options.unwrap_or_default();
+    if options.collation.is_none() {
+      options.collation = self.collation.clone();
+    }
+
+    if options.projection.is_none() {
+      options.projection = self.projection.clone();
+    }
+
+    if options.write_concern.is_none() {
+      options.write_concern = self.write_concern.clone();
+    }
+
+    Some(options)
+  }
+
+  fn find_one_options(&self, options: Option<FindOneOptions>) -> Option<FindOneOptions> {
+    let mut options = options.unwrap_or_default();
+    if options.collation.is_none() {
+      options.collation = self.collation.clone();
+    }
+
+    if options.projection.is_none() {
+      options.projection = self.projection.clone();
+    }
+
+    if options.read_concern.is_none() {
+      options.read_concern = self.read_concern.clone();
+    }
+
+    Some(options)
+  }
+
+  fn update_options(&self, options: Option<UpdateOptions>) -> Option<UpdateOptions> {
+    let mut options = options.unwrap_or_default();
+    if options.collation.is_none() {
+      options.collation = self.collation.clone();
+    }
+
+    if options.write_concern.is_none() {
+      options.write_concern = self.write_concern.clone();
+    }
+
+    Some(options)
+  }
+}
+
+#[cfg(feature = "sync")]
+impl<T> Model<T, sync::Database>
+where
+  T: Schema,
+{
+  /// Same as `Nongoose.aggregate()`, on the collection of the model.
+  pub fn aggregate<R>(
+    &self,
+    pipeline: Vec<Document>,
+    options: Option<AggregateOptions>,
+  ) -> Result<Vec<R>>
+  where
+    R: From<Document>,
+  {
+    self
+      .builder
+      .aggregate_sync::<T, R>(pipeline, self.aggregate_options(options), None)
+  }
+
+  /// Same as `Nongoose.aggregate_stream()`, on the collection of the model.
+  pub fn aggregate_stream<R>(
+    &self,
+    pipeline: Vec<Document>,
+    options: Option<AggregateOptions>,
+  ) -> Result<blocking::Cursor<R>>
+  where
+    R: From<Document>,
+  {
+    let cursor = self
+      .builder
+      .aggregate_cursor_sync::<T>(pipeline, self.aggregate_options(options))?;
+
+    Ok(blocking::Cursor::new(cursor, |doc| Ok(doc.into())))
+  }
+
+  /// Same as `Nongoose.count()`, on the collection of the model.
+  pub fn count(&self, conditions: Document, options: Option<CountOptions>) -> Result<u64> {
+    self
+      .builder
+      .count_sync::<T>(conditions, self.count_options(options), None)
+  }
+
+  /// Same as `Nongoose.create()`, on the collection of the model.
+  pub fn create(&self, data: &T) -> Result<T>
+  where
+    T: blocking::SchemaBefore + blocking::SchemaAfter,
+  {
+    self.save(&mut data.clone())
+  }
+
+  /// Same as `Nongoose.delete_many()`, on the collection of the model.
+  pub fn delete_many(
+    &self,
+    conditions: Document,
+    options: Option<DeleteOptions>,
+  ) -> Result<DeleteResult> {
+    self
+      .builder
+      .delete_many_sync::<T>(conditions, self.delete_options(options), None)
+  }
+
+  /// Same as `Nongoose.find()`, on the collection of the model.
+  pub fn find(&self, conditions: Document, options: Option<FindOptions>) -> Result<Vec<T>>
+  where
+    T: blocking::SchemaAfter,
+  {
+    self
+      .builder
+      .find_sync(conditions, self.find_options(options), None)
+  }
+
+  /// Same as `Nongoose.find_and_remove()`, on the collection of the model.
+  pub fn find_and_remove(
+    &self,
+    conditions: Document,
+    options: Option<FindOptions>,
+  ) -> Result<Vec<(bool, T)>>
+  where
+    T: blocking::SchemaBefore + blocking::SchemaAfter,
+  {
+    self.builder.find_and_delete_sync(
+      conditions,
+      self.find_options(options),
+      self.write_concern.clone(),
+      None,
+    )
+  }
+
+  /// Same as `Nongoose.find_by_id()`, on the collection of the model.
+  pub fn find_by_id(&self, id: &T::Id) -> Result<Option<T>>
+  where
+    T: blocking::SchemaAfter,
+  {
+    self.find_one(doc! { "_id": id.clone().into() }, None)
+  }
+
+  /// Same as `Nongoose.find_by_id_and_remove()`, on the collection of the model.
+  pub fn find_by_id_and_remove(&self, id: &T::Id) -> Result<(bool, Option<T>)>
+  where
+    T: blocking::SchemaBefore + blocking::SchemaAfter,
+  {
+    self.find_one_and_remove(doc! { "_id": id.clone().into() }, None)
+  }
+
+  /// Same as `Nongoose.find_by_id_and_update()`, on the collection of the model.
+  pub fn find_by_id_and_update(
+    &self,
+    id: &T::Id,
+    data: Document,
+    options: Option<FindOneAndUpdateOptions>,
+  ) -> Result<Option<T>>
+  where
+    T: blocking::SchemaBefore + blocking::SchemaAfter,
+  {
+    self.find_one_and_update(doc! { "_id": id.clone().into() }, data, options)
+  }
+
+  /// Same as `Nongoose.find_one()`, on the collection of the model.
+  pub fn find_one(&self, conditions: Document, options: Option<FindOneOptions>) -> Result<Option<T>>
+  where
+    T: blocking::SchemaAfter,
+  {
+    self
+      .builder
+      .find_one_sync(conditions, self.find_one_options(options), None)
+  }
+
+  /// Same as `Nongoose.find_one_and_remove()`, on the collection of the model.
+  pub fn find_one_and_remove(
+    &self,
+    conditions: Document,
+    options: Option<FindOneAndDeleteOptions>,
+  ) -> Result<(bool, Option<T>)>
+  where
+    T: blocking::SchemaBefore + blocking::SchemaAfter,
+  {
+    self.builder.find_one_and_delete_sync(
+      conditions,
+      self.find_one_and_delete_options(options),
+      None,
+    )
+  }
+
+  /// Same as `Nongoose.find_one_and_update()`, on the collection of the model.
+  pub fn find_one_and_update(
+    &self,
+    conditions: Document,
+    data: Document,
+    options: Option<FindOneAndUpdateOptions>,
+  ) -> Result<Option<T>>
+  where
+    T: blocking::SchemaBefore + blocking::SchemaAfter,
+  {
+    self.builder.find_one_and_update_sync(
+      conditions,
+      data,
+      self.find_one_and_update_options(options),
+      None,
+    )
+  }
+
+  /// Same as `Nongoose.find_stream()`, on the collection of the model.
+  pub fn find_stream(
+    &self,
+    conditions: Document,
+    options: Option<FindOptions>,
+  ) -> Result<blocking::Cursor<T>>
+  where
+    T: blocking::SchemaAfter + 'static,
+  {
+    self
+      .builder
+      .find_stream_sync(conditions, self.find_options(options))
+  }
+
+  /// Same as `Nongoose.populate()`.
+  pub fn populate(&self, data: T, field: &str) -> Result<T> {
+    self
+      .builder
+      .populate_sync(data, field, self.read_concern.clone(), None)
+  }
+
+  /// Same as `Nongoose.remove()`, on the collection of the model.
+  pub fn remove(&self, data: &T) -> Result<bool>
+  where
+    T: blocking::SchemaBefore + blocking::SchemaAfter,
+  {
+    self
+      .builder
+      .remove_sync(data, self.write_concern.clone(), None)
+  }
+
+  /// Same as `Nongoose.save()`, on the collection of the model.
+  pub fn save(&self, data: &mut T) -> Result<T>
+  where
+    T: blocking::SchemaBefore + blocking::SchemaAfter,
+  {
+    self
+      .builder
+      .save_sync(data, self.write_concern.clone(), None)
+  }
+
+  /// Same as `Nongoose.update_many()`, on the collection of the model.
+  pub fn update_many(
+    &self,
+    conditions: Document,
+    data: Document,
+    options: Option<UpdateOptions>,
+  ) -> Result<UpdateResult> {
+    self
+      .builder
+      .update_many_sync::<T>(conditions, data, self.update_options(options), None)
+  }
+}
+
+#[cfg(feature = "tokio-runtime")]
+impl<T> Model<T, mongodb::Database>
+where
+  T: Schema,
+{
+  /// Same as `Nongoose.aggregate()`, on the collection of the model.
+  pub async fn aggregate<R>(
+    &self,
+    pipeline: Vec<Document>,
+    options: Option<AggregateOptions>,
+  ) -> Result<Vec<R>>
+  where
+    R: From<Document>,
+  {
+    self
+      .builder
+      .aggregate::<T, R>(pipeline, self.aggregate_options(options), None)
+      .await
+  }
+
+  /// Same as `Nongoose.aggregate_stream()`, on the collection of the model.
+  pub async fn aggregate_stream<R>(
+    &self,
+    pipeline: Vec<Document>,
+    options: Option<AggregateOptions>,
+  ) -> Result<super::Cursor<R>>
+  where
+    R: From<Document>,
+  {
+    let cursor = self
+      .builder
+      .aggregate_cursor::<T>(pipeline, self.aggregate_options(options))
+      .await?;
+
+    Ok(super::Cursor::new(cursor, |doc| Ok(doc.into())))
+  }
+
+  /// Same as `Nongoose.count()`, on the collection of the model.
+  pub async fn count(&self, conditions: Document, options: Option<CountOptions>) -> Result<u64> {
+    self
+      .builder
+      .count::<T>(conditions, self.count_options(options), None)
+      .await
+  }
+
+  /// Same as `Nongoose.create()`, on the collection of the model.
+  pub async fn create(&self, data: &T) -> Result<T>
+  where
+    T: crate::SchemaBefore + crate::SchemaAfter,
+  {
+    self.save(&mut data.clone()).await
+  }
+
+  /// Same as `Nongoose.delete_many()`, on the collection of the model.
+  pub async fn delete_many(
+    &self,
+    conditions: Document,
+    options: Option<DeleteOptions>,
+  ) -> Result<DeleteResult> {
+    self
+      .builder
+      .delete_many::<T>(conditions, self.delete_options(options), None)
+      .await
+  }
+
+  /// Same as `Nongoose.find()`, on the collection of the model.
+  pub async fn find(&self, conditions: Document, options: Option<FindOptions>) -> Result<Vec<T>>
+  where
+    T: crate::SchemaAfter,
+  {
+    self
+      .builder
+      .find(conditions, self.find_options(options), None)
+      .await
+  }
+
+  /// Same as `Nongoose.find_and_remove()`, on the collection of the model.
+  pub async fn find_and_remove(
+    &self,
+    conditions: Document,
+    options: Option<FindOptions>,
+  ) -> Result<Vec<(bool, T)>>
+  where
+    T: crate::SchemaBefore + crate::SchemaAfter,
+  {
+    self
+      .builder
+      .find_and_delete(
+        conditions,
+        self.find_options(options),
+        self.write_concern.clone(),
+        None,
+      )
+      .await
+  }
+
+  /// Same as `Nongoose.find_by_id()`, on the collection of the model.
+  pub async fn find_by_id(&self, id: &T::Id) -> Result<Option<T>>
+  where
+    T: crate::SchemaAfter,
+  {
+    self.find_one(doc! { "_id": id.clone().into() }, None).await
+  }
+
+  /// Same as `Nongoose.find_by_id_and_remove()`, on the collection of the model.
+  pub async fn find_by_id_and_remove(&self, id: &T::Id) -> Result<(bool, Option<T>)>
+  where
+    T: crate::SchemaBefore + crate::SchemaAfter,
+  {
+    self
+      .find_one_and_remove(doc! { "_id": id.clone().into() }, None)
+      .await
+  }
+
+  /// Same as `Nongoose.find_by_id_and_update()`, on the collection of the model.
+  pub async fn find_by_id_and_update(
+    &self,
+    id: &T::Id,
+    data: Document,
+    options: Option<FindOneAndUpdateOptions>,
+  ) -> Result<Option<T>>
+  where
+    T: crate::SchemaBefore + crate::SchemaAfter,
+  {
+    self
+      .find_one_and_update(doc! { "_id": id.clone().into() }, data, options)
+      .await
+  }
+
+  /// Same as `Nongoose.find_one()`, on the collection of the model.
+  pub async fn find_one(
+    &self,
+    conditions: Document,
+    options: Option<FindOneOptions>,
+  ) -> Result<Option<T>>
+  where
+    T: crate::SchemaAfter,
+  {
+    self
+      .builder
+      .find_one(conditions, self.find_one_options(options), None)
+      .await
+  }
+
+  /// Same as `Nongoose.find_one_and_remove()`, on the collection of the model.
+  pub async fn find_one_and_remove(
+    &self,
+    conditions: Document,
+    options: Option<FindOneAndDeleteOptions>,
+  ) -> Result<(bool, Option<T>)>
+  where
+    T: crate::SchemaBefore + crate::SchemaAfter,
+  {
+    self
+      .builder
+      .find_one_and_delete(conditions, self.find_one_and_delete_options(options), None)
+      .await
+  }
+
+  /// Same as `Nongoose.find_one_and_update()`, on the collection of the model.
+  pub async fn find_one_and_update(
+    &self,
+    conditions: Document,
+    data: Document,
+    options: Option<FindOneAndUpdateOptions>,
+  ) -> Result<Option<T>>
+  where
+    T: crate::SchemaBefore + crate::SchemaAfter,
+  {
+    self
+      .builder
+      .find_one_and_update(
+        conditions,
+        data,
+        self.find_one_and_update_options(options),
+        None,
+      )
+      .await
+  }
+
+  /// Same as `Nongoose.find_stream()`, on the collection of the model.
+  pub async fn find_stream(
+    &self,
+    conditions: Document,
+    options: Option<FindOptions>,
+  ) -> Result<super::Cursor<T>>
+  where
+    T: crate::SchemaAfter + 'static,
+  {
+    self
+      .builder
+      .find_stream(conditions, self.find_options(options))
+      .await
+  }
+
+  /// Same as `Nongoose.populate()`.
+  pub async fn populate(&self, data: T, field: &str) -> Result<T> {
+    self
+      .builder
+      .populate(data, field, self.read_concern.clone(), None)
+      .await
+  }
+
+  /// Same as `Nongoose.remove()`, on the collection of the model.
+  pub async fn remove(&self, data: &T) -> Result<bool>
+  where
+    T: crate::SchemaBefore + crate::SchemaAfter,
+  {
+    self
+      .builder
+      .remove(data, self.write_concern.clone(), None)
+      .await
+  }
+
+  /// Same as `Nongoose.save()`, on the collection of the model.
+  pub async fn save(&self, data: &mut T) -> Result<T>
+  where
+    T: crate::SchemaBefore + crate::SchemaAfter,
+  {
+    self
+      .builder
+      .save(data, self.write_concern.clone(), None)
+      .await
+  }
+
+  /// Same as `Nongoose.update_many()`, on the collection of the model.
+  pub async fn update_many(
+    &self,
+    conditions: Document,
+    data: Document,
+    options: Option<UpdateOptions>,
+  ) -> Result<UpdateResult> {
+    self
+      .builder
+      .update_many::<T>(conditions, data, self.update_options(options), None)
+      .await
+  }
+}
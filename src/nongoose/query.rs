@@ -0,0 +1,303 @@
+use std::marker::PhantomData;
+
+use mongodb::{
+  bson::{doc, Document},
+  options::{Collation, CountOptions, FindOneOptions, FindOptions, ReadConcern},
+};
+
+use super::{builder::DefaultDatabase, NongooseBuilder};
+#[cfg(feature = "sync")]
+use crate::blocking;
+use crate::{error::Result, Schema};
+
+/// Lazy and chainable query over the collection of a Schema.
+///
+/// Nothing is sent to the database until `Query.find()`, `Query.find_one()` or `Query.count()` is called.
+///
+/// # Example
+/// ```rust,no_run,ignore
+/// // Find the 10 oldest users over 18 years of age, returning only their usernames
+/// let users = nongoose
+///   .query::<User>()
+///   .filter(doc! { "age": { "$gte": 18 } })
+///   .sort(doc! { "age": -1 })
+///   .limit(10)
+///   .select(doc! { "username": 1 })
+///   .find();
+/// ```
+///
+/// `D` is the database of the `Nongoose` instance that created the query: `nongoose::Database` for the
+/// async API, or `nongoose::blocking::Database` for the blocking one (see `nongoose::blocking::Query`).
+#[derive(Clone)]
+pub struct Query<T, D = DefaultDatabase>
+where
+  T: Schema,
+{
+  builder: NongooseBuilder<D>,
+  conditions: Document,
+  sort: Option<Document>,
+  skip: Option<u64>,
+  limit: Option<u64>,
+  projection: Option<Document>,
+  collation: Option<Collation>,
+  read_concern: Option<ReadConcern>,
+  _schema: PhantomData<T>,
+}
+
+impl<T, D> Query<T, D>
+where
+  T: Schema,
+{
+  pub(crate) fn new(builder: NongooseBuilder<D>) -> Self {
+    Self {
+      builder,
+      conditions: Document::new(),
+      sort: None,
+      skip: None,
+      limit: None,
+      projection: None,
+      collation: None,
+      read_concern: None,
+      _schema: PhantomData,
+    }
+  }
+
+  /// Adds `conditions` to the query filter.
+  ///
+  /// If a field is already filtered, both filters are combined with `$and`.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// // { "$and": [{ "age": { "$gte": 18 } }, { "age": { "$lte": 30 } }] }
+  /// nongoose
+  ///   .query::<User>()
+  ///   .filter(doc! { "age": { "$gte": 18 } })
+  ///   .filter(doc! { "age": { "$lte": 30 } });
+  /// ```
+  pub fn filter(mut self, conditions: Document) -> Self {
+    if self
+      .conditions
+      .keys()
+      .any(|key| conditions.contains_key(key))
+    {
+      self.conditions = doc! { "$and": [self.conditions, conditions] };
+    } else {
+      self.conditions.extend(conditions);
+    }
+
+    self
+  }
+
+  /// Adds fields to the order of the documents (`1` ascending, `-1` descending).
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// nongoose.query::<User>().sort(doc! { "age": -1 }).sort(doc! { "username": 1 });
+  /// ```
+  pub fn sort(mut self, sort: Document) -> Self {
+    match self.sort.as_mut() {
+      Some(current) => current.extend(sort),
+      None => self.sort = Some(sort),
+    }
+
+    self
+  }
+
+  /// Sets the number of documents to skip.
+  pub fn skip(mut self, skip: u64) -> Self {
+    self.skip = Some(skip);
+    self
+  }
+
+  /// Sets the maximum number of documents to return.
+  pub fn limit(mut self, limit: u64) -> Self {
+    self.limit = Some(limit);
+    self
+  }
+
+  /// Adds fields to the projection of the documents returned.
+  ///
+  /// The excluded fields must be optional (or have a serde default) in the Schema to be deserialized.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// nongoose.query::<User>().select(doc! { "password": 0 });
+  /// ```
+  pub fn select(mut self, projection: Document) -> Self {
+    match self.projection.as_mut() {
+      Some(current) => current.extend(projection),
+      None => self.projection = Some(projection),
+    }
+
+    self
+  }
+
+  /// Sets the [collation](https://docs.mongodb.com/manual/reference/collation/) used to compare strings.
+  pub fn collation(mut self, collation: Collation) -> Self {
+    self.collation = Some(collation);
+    self
+  }
+
+  /// Sets the read concern of the query.
+  pub fn read_concern(mut self, read_concern: ReadConcern) -> Self {
+    self.read_concern = Some(read_concern);
+    self
+  }
+
+  fn find_options(&self) -> FindOptions {
+    FindOptions::builder()
+      .sort(self.sort.clone())
+      .skip(self.skip)
+      .limit(self.limit.map(|limit| limit as i64))
+      .projection(self.projection.clone())
+      .collation(self.collation.clone())
+      .read_concern(self.read_concern.clone())
+      .build()
+  }
+
+  fn find_one_options(&self) -> FindOneOptions {
+    FindOneOptions::builder()
+      .sort(self.sort.clone())
+      .skip(self.skip)
+      .projection(self.projection.clone())
+      .collation(self.collation.clone())
+      .read_concern(self.read_concern.clone())
+      .build()
+  }
+
+  fn count_options(&self) -> CountOptions {
+    CountOptions::builder()
+      .skip(self.skip)
+      .limit(self.limit)
+      .collation(self.collation.clone())
+      .read_concern(self.read_concern.clone())
+      .build()
+  }
+}
+
+#[cfg(feature = "sync")]
+impl<T> Query<T, mongodb::sync::Database>
+where
+  T: Schema,
+{
+  /// Executes the query and returns the documents found.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// match nongoose.query::<User>().filter(doc! { "age": { "$gte": 18 } }).find() {
+  ///   Ok(users) => println!("Found {} users!", users.len()),
+  ///   Err(error) => eprintln!("Error finding users: {}", error),
+  /// }
+  /// ```
+  pub fn find(&self) -> Result<Vec<T>>
+  where
+    T: blocking::SchemaAfter,
+  {
+    self
+      .builder
+      .find_sync(self.conditions.clone(), Some(self.find_options()), None)
+  }
+
+  /// Executes the query and returns the first document found.
+  ///
+  /// The `limit` of the query is ignored.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// match nongoose.query::<User>().sort(doc! { "age": -1 }).find_one() {
+  ///   Ok(Some(user)) => println!("Oldest user: {}", user.username),
+  ///   Ok(None) => println!("There are no users"),
+  ///   Err(error) => eprintln!("Error finding the user: {}", error),
+  /// }
+  /// ```
+  pub fn find_one(&self) -> Result<Option<T>>
+  where
+    T: blocking::SchemaAfter,
+  {
+    self
+      .builder
+      .find_one_sync(self.conditions.clone(), Some(self.find_one_options()), None)
+  }
+
+  /// Counts the documents that match the query.
+  ///
+  /// The `sort` and `select` of the query are ignored.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// match nongoose.query::<User>().filter(doc! { "age": { "$gte": 18 } }).count() {
+  ///   Ok(users) => println!("Found {} users!", users),
+  ///   Err(error) => eprintln!("Error counting users: {}", error),
+  /// }
+  /// ```
+  pub fn count(&self) -> Result<u64> {
+    self
+      .builder
+      .count_sync::<T>(self.conditions.clone(), Some(self.count_options()), None)
+  }
+}
+
+#[cfg(feature = "tokio-runtime")]
+impl<T> Query<T, mongodb::Database>
+where
+  T: Schema,
+{
+  /// Executes the query and returns the documents found.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// match nongoose.query::<User>().filter(doc! { "age": { "$gte": 18 } }).find().await {
+  ///   Ok(users) => println!("Found {} users!", users.len()),
+  ///   Err(error) => eprintln!("Error finding users: {}", error),
+  /// }
+  /// ```
+  pub async fn find(&self) -> Result<Vec<T>>
+  where
+    T: crate::SchemaAfter,
+  {
+    self
+      .builder
+      .find(self.conditions.clone(), Some(self.find_options()), None)
+      .await
+  }
+
+  /// Executes the query and returns the first document found.
+  ///
+  /// The `limit` of the query is ignored.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// match nongoose.query::<User>().sort(doc! { "age": -1 }).find_one().await {
+  ///   Ok(Some(user)) => println!("Oldest user: {}", user.username),
+  ///   Ok(None) => println!("There are no users"),
+  ///   Err(error) => eprintln!("Error finding the user: {}", error),
+  /// }
+  /// ```
+  pub async fn find_one(&self) -> Result<Option<T>>
+  where
+    T: crate::SchemaAfter,
+  {
+    self
+      .builder
+      .find_one(self.conditions.clone(), Some(self.find_one_options()), None)
+      .await
+  }
+
+  /// Counts the documents that match the query.
+  ///
+  /// The `sort` and `select` of the query are ignored.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// match nongoose.query::<User>().filter(doc! { "age": { "$gte": 18 } }).count().await {
+  ///   Ok(users) => println!("Found {} users!", users),
+  ///   Err(error) => eprintln!("Error counting users: {}", error),
+  /// }
+  /// ```
+  pub async fn count(&self) -> Result<u64> {
+    self
+      .builder
+      .count::<T>(self.conditions.clone(), Some(self.count_options()), None)
+      .await
+  }
+}
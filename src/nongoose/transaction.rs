@@ -0,0 +1,329 @@
+use std::{future::Future, sync::Arc, time::Instant};
+
+use futures::lock::{Mutex, MutexGuard};
+use mongodb::{
+  bson::{doc, Document},
+  error::{TRANSIENT_TRANSACTION_ERROR, UNKNOWN_TRANSACTION_COMMIT_RESULT},
+  options::{
+    AggregateOptions, CountOptions, DeleteOptions, FindOneAndDeleteOptions,
+    FindOneAndUpdateOptions, FindOneOptions, FindOptions, TransactionOptions, UpdateOptions,
+  },
+  results::{DeleteResult, UpdateResult},
+};
+
+use super::{NongooseBuilder, TRANSACTION_TIMEOUT};
+use crate::{error::Result, ClientSession, Database, Schema, SchemaAfter, SchemaBefore};
+
+/// Multi-document transaction, created by `Nongoose.transaction()`.
+///
+/// It has the same methods as `Nongoose`, but all of them are executed inside the transaction. It can be
+/// cloned to be moved into the futures of the transaction; all the clones share the same session.
+#[derive(Clone)]
+pub struct Transaction {
+  builder: NongooseBuilder<Database>,
+  session: Arc<Mutex<ClientSession>>,
+}
+
+impl Transaction {
+  pub(crate) fn new(builder: NongooseBuilder<Database>, session: ClientSession) -> Self {
+    Self {
+      builder,
+      session: Arc::new(Mutex::new(session)),
+    }
+  }
+
+  /// Session of the transaction, to run other MongoDB operations inside it.
+  ///
+  /// The other methods of the transaction wait until the returned guard is dropped.
+  pub async fn session(&self) -> MutexGuard<'_, ClientSession> {
+    self.session.lock().await
+  }
+
+  /// Same as `Nongoose.aggregate()`, inside the transaction.
+  pub async fn aggregate<S, T>(
+    &self,
+    pipeline: Vec<Document>,
+    options: Option<AggregateOptions>,
+  ) -> Result<Vec<T>>
+  where
+    S: Schema,
+    T: From<Document>,
+  {
+    let mut session = self.session.lock().await;
+
+    self
+      .builder
+      .aggregate::<S, T>(pipeline, options, Some(&mut session))
+      .await
+  }
+
+  /// Same as `Nongoose.count()`, inside the transaction.
+  pub async fn count<T>(&self, conditions: Document, options: Option<CountOptions>) -> Result<u64>
+  where
+    T: Schema,
+  {
+    let mut session = self.session.lock().await;
+
+    self
+      .builder
+      .count::<T>(conditions, options, Some(&mut session))
+      .await
+  }
+
+  /// Same as `Nongoose.create()`, inside the transaction.
+  pub async fn create<T>(&self, data: &T) -> Result<T>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    self.save(&mut data.clone()).await
+  }
+
+  /// Same as `Nongoose.delete_many()`, inside the transaction.
+  pub async fn delete_many<T>(
+    &self,
+    conditions: Document,
+    options: Option<DeleteOptions>,
+  ) -> Result<DeleteResult>
+  where
+    T: Schema,
+  {
+    let mut session = self.session.lock().await;
+
+    self
+      .builder
+      .delete_many::<T>(conditions, options, Some(&mut session))
+      .await
+  }
+
+  /// Same as `Nongoose.find()`, inside the transaction. The `HookContext` of the `SchemaBefore` and `SchemaAfter`
+  /// functions has the session of the transaction.
+  pub async fn find<T>(&self, conditions: Document, options: Option<FindOptions>) -> Result<Vec<T>>
+  where
+    T: Schema + SchemaAfter,
+  {
+    let mut session = self.session.lock().await;
+
+    self
+      .builder
+      .find(conditions, options, Some(&mut session))
+      .await
+  }
+
+  /// Same as `Nongoose.find_and_remove()`, inside the transaction. The `HookContext` of the `SchemaBefore` and
+  /// `SchemaAfter` functions has the session of the transaction.
+  pub async fn find_and_remove<T>(
+    &self,
+    conditions: Document,
+    options: Option<FindOptions>,
+  ) -> Result<Vec<(bool, T)>>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    let mut session = self.session.lock().await;
+
+    self
+      .builder
+      .find_and_delete(conditions, options, None, Some(&mut session))
+      .await
+  }
+
+  /// Same as `Nongoose.find_by_id()`, inside the transaction.
+  pub async fn find_by_id<T>(&self, id: &T::Id) -> Result<Option<T>>
+  where
+    T: Schema + SchemaAfter,
+  {
+    self.find_one(doc! { "_id": id.clone().into() }, None).await
+  }
+
+  /// Same as `Nongoose.find_by_id_and_remove()`, inside the transaction.
+  pub async fn find_by_id_and_remove<T>(&self, id: &T::Id) -> Result<(bool, Option<T>)>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    self
+      .find_one_and_remove(doc! { "_id": id.clone().into() }, None)
+      .await
+  }
+
+  /// Same as `Nongoose.find_by_id_and_update()`, inside the transaction.
+  pub async fn find_by_id_and_update<T>(
+    &self,
+    id: &T::Id,
+    data: Document,
+    options: Option<FindOneAndUpdateOptions>,
+  ) -> Result<Option<T>>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    self
+      .find_one_and_update(doc! { "_id": id.clone().into() }, data, options)
+      .await
+  }
+
+  /// Same as `Nongoose.find_one()`, inside the transaction. The `HookContext` of the `SchemaBefore` and
+  /// `SchemaAfter` functions has the session of the transaction.
+  pub async fn find_one<T>(
+    &self,
+    conditions: Document,
+    options: Option<FindOneOptions>,
+  ) -> Result<Option<T>>
+  where
+    T: Schema + SchemaAfter,
+  {
+    let mut session = self.session.lock().await;
+
+    self
+      .builder
+      .find_one(conditions, options, Some(&mut session))
+      .await
+  }
+
+  /// Same as `Nongoose.find_one_and_remove()`, inside the transaction. The `HookContext` of the `SchemaBefore` and
+  /// `SchemaAfter` functions has the session of the transaction.
+  pub async fn find_one_and_remove<T>(
+    &self,
+    conditions: Document,
+    options: Option<FindOneAndDeleteOptions>,
+  ) -> Result<(bool, Option<T>)>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    let mut session = self.session.lock().await;
+
+    self
+      .builder
+      .find_one_and_delete(conditions, options, Some(&mut session))
+      .await
+  }
+
+  /// Same as `Nongoose.find_one_and_update()`, inside the transaction. The `HookContext` of the `SchemaBefore` and
+  /// `SchemaAfter` functions has the session of the transaction.
+  pub async fn find_one_and_update<T>(
+    &self,
+    conditions: Document,
+    data: Document,
+    options: Option<FindOneAndUpdateOptions>,
+  ) -> Result<Option<T>>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    let mut session = self.session.lock().await;
+
+    self
+      .builder
+      .find_one_and_update(conditions, data, options, Some(&mut session))
+      .await
+  }
+
+  /// Same as `Nongoose.populate()`, inside the transaction.
+  pub async fn populate<T>(&self, data: T, field: &str) -> Result<T>
+  where
+    T: Schema,
+  {
+    let mut session = self.session.lock().await;
+
+    self
+      .builder
+      .populate(data, field, None, Some(&mut session))
+      .await
+  }
+
+  /// Same as `Nongoose.remove()`, inside the transaction. The `HookContext` of the `SchemaBefore` and `SchemaAfter`
+  /// functions has the session of the transaction.
+  pub async fn remove<T>(&self, data: &T) -> Result<bool>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    let mut session = self.session.lock().await;
+
+    self.builder.remove(data, None, Some(&mut session)).await
+  }
+
+  /// Same as `Nongoose.save()`, inside the transaction. The `HookContext` of the `SchemaBefore` and `SchemaAfter`
+  /// functions has the session of the transaction.
+  pub async fn save<T>(&self, data: &mut T) -> Result<T>
+  where
+    T: Schema + SchemaBefore + SchemaAfter,
+  {
+    let mut session = self.session.lock().await;
+
+    self.builder.save(data, None, Some(&mut session)).await
+  }
+
+  /// Same as `Nongoose.update_many()`, inside the transaction.
+  pub async fn update_many<T>(
+    &self,
+    conditions: Document,
+    data: Document,
+    options: Option<UpdateOptions>,
+  ) -> Result<UpdateResult>
+  where
+    T: Schema,
+  {
+    let mut session = self.session.lock().await;
+
+    self
+      .builder
+      .update_many::<T>(conditions, data, options, Some(&mut session))
+      .await
+  }
+
+  // Internals
+  pub(crate) async fn run<F, Fut, R>(
+    self,
+    options: Option<TransactionOptions>,
+    mut callback: F,
+  ) -> Result<R>
+  where
+    F: FnMut(Transaction) -> Fut,
+    Fut: Future<Output = Result<R>>,
+  {
+    let start = Instant::now();
+
+    'transaction: loop {
+      self
+        .session
+        .lock()
+        .await
+        .start_transaction(options.clone())
+        .await?;
+
+      let result = match callback(self.clone()).await {
+        Ok(result) => result,
+        Err(error) => {
+          // The server aborts the transaction anyway, so the abort error is ignored.
+          let _ = self.session.lock().await.abort_transaction().await;
+
+          if error.contains_label(TRANSIENT_TRANSACTION_ERROR)
+            && start.elapsed() < TRANSACTION_TIMEOUT
+          {
+            continue 'transaction;
+          }
+
+          return Err(error);
+        }
+      };
+
+      loop {
+        match self.session.lock().await.commit_transaction().await {
+          Ok(()) => return Ok(result),
+          Err(error) => {
+            if start.elapsed() >= TRANSACTION_TIMEOUT {
+              return Err(error.into());
+            }
+
+            if error.contains_label(UNKNOWN_TRANSACTION_COMMIT_RESULT) {
+              continue;
+            }
+
+            if error.contains_label(TRANSIENT_TRANSACTION_ERROR) {
+              continue 'transaction;
+            }
+
+            return Err(error.into());
+          }
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,90 @@
+use std::{fmt, sync::Arc};
+
+use crate::{
+  middleware::{DocumentMiddleware, Middlewares, QueryMiddleware},
+  SchemaData,
+};
+
+/// Plugin
+///
+/// A plugin packages a cross-cutting behavior (e.g. timestamps, auditing, soft deletes or tenancy) once, to be
+/// applied to every Schema registered in a Nongoose instance with `NongooseBuilder.plugin()`.
+///
+/// # Example
+/// ```rust,no_run,ignore
+/// struct Timestamps;
+///
+/// impl Plugin for Timestamps {
+///   fn setup(&self, setup: &mut PluginSetup) {
+///     setup.add_document_middleware(UpdatedAt);
+///   }
+///
+///   fn add_schema(&self, schema: &mut SchemaData) {
+///     schema.add_index(IndexModel::builder().keys(doc! { "updated_at": -1 }).build());
+///   }
+/// }
+///
+/// let nongoose = Nongoose::builder(database)
+///   .plugin(Timestamps)
+///   .add_schema::<User>()
+///   .build();
+/// ```
+pub trait Plugin: Send + Sync {
+  /// Register the middlewares of the plugin. It is called once, by `NongooseBuilder.plugin()`.
+  fn setup(&self, _setup: &mut PluginSetup) {}
+
+  /// Called for every Schema registered in the Nongoose instance (before or after the plugin), e.g. to add
+  /// indexes to the Schema.
+  fn add_schema(&self, _schema: &mut SchemaData) {}
+}
+
+/// Middlewares registered by `Plugin.setup()`.
+pub struct PluginSetup<'a> {
+  middlewares: &'a mut Middlewares,
+}
+
+impl<'a> PluginSetup<'a> {
+  pub(crate) fn new(middlewares: &'a mut Middlewares) -> Self {
+    Self { middlewares }
+  }
+
+  /// Register a query middleware (see `NongooseBuilder.add_query_middleware()`).
+  pub fn add_query_middleware<M>(&mut self, middleware: M) -> &mut Self
+  where
+    M: QueryMiddleware + 'static,
+  {
+    self.middlewares.add(Arc::new(middleware));
+    self
+  }
+
+  /// Register a document middleware (see `NongooseBuilder.add_document_middleware()`).
+  pub fn add_document_middleware<M>(&mut self, middleware: M) -> &mut Self
+  where
+    M: DocumentMiddleware + 'static,
+  {
+    self.middlewares.add_document(Arc::new(middleware));
+    self
+  }
+}
+
+/// Plugins registered in a Nongoose instance.
+#[derive(Clone, Default)]
+pub(crate) struct Plugins(Vec<Arc<dyn Plugin>>);
+
+impl Plugins {
+  pub(crate) fn add(&mut self, plugin: Arc<dyn Plugin>) {
+    self.0.push(plugin);
+  }
+
+  pub(crate) fn add_schema(&self, schema: &mut SchemaData) {
+    for plugin in self.0.iter() {
+      plugin.add_schema(schema);
+    }
+  }
+}
+
+impl fmt::Debug for Plugins {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_tuple("Plugins").field(&self.0.len()).finish()
+  }
+}
@@ -2,4 +2,48 @@
 #[cfg(feature = "tokio-runtime")]
 pub use async_trait::async_trait;
 #[doc(hidden)]
-pub use once_cell::sync::OnceCell;
+#[cfg(feature = "tokio-runtime")]
+pub use futures::future::BoxFuture;
+
+/// Implement the `SchemaAfter` traits of the enabled APIs with their default functions (used by `#[derive(Schema)]`,
+/// unless the Schema has the `after_hooks` attribute).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __schema_after {
+  ($schema:ty) => {
+    $crate::__schema_after_async!($schema);
+    $crate::__schema_after_blocking!($schema);
+  };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "tokio-runtime")]
+macro_rules! __schema_after_async {
+  ($schema:ty) => {
+    impl $crate::SchemaAfter for $schema {}
+  };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "tokio-runtime"))]
+macro_rules! __schema_after_async {
+  ($schema:ty) => {};
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "sync")]
+macro_rules! __schema_after_blocking {
+  ($schema:ty) => {
+    impl $crate::blocking::SchemaAfter for $schema {}
+  };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "sync"))]
+macro_rules! __schema_after_blocking {
+  ($schema:ty) => {};
+}
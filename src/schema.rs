@@ -1,288 +1,176 @@
+#[cfg(feature = "tokio-runtime")]
+mod after;
+#[cfg(feature = "tokio-runtime")]
 mod before;
+mod context;
 mod data;
 
 /// Schema types:
 ///
 /// - Type of the relation.
 /// - Data of the relation.
+/// - Path of a field.
 pub mod types;
 
+/// Types of the fields of `#[schema(timestamps)]`.
+#[doc(hidden)]
+pub mod timestamps;
+
+/// Validation of the Schema fields (`#[schema(min, max, min_length, max_length, regex, one_of, required)]`).
+pub mod validation;
+
+#[cfg(feature = "tokio-runtime")]
+pub use after::SchemaAfter;
+#[cfg(feature = "tokio-runtime")]
 pub use before::SchemaBefore;
+#[cfg(feature = "tokio-runtime")]
+pub use context::HookContext;
+pub use context::{Extensions, HookOperation};
 pub use data::SchemaData;
 use mongodb::{
-  bson::{bson, doc, Bson, Document},
-  options::ReplaceOptions,
-  sync::Database,
+  bson::{doc, from_bson, Bson, Document},
+  IndexModel,
 };
-#[cfg(feature = "tokio-runtime")]
-use tokio::task::spawn_blocking;
+use serde::{de::DeserializeOwned, Serialize};
 
-use crate::error::Result;
-
-use self::types::SchemaRelationType;
+use crate::error::{Error, Result};
 
 /// Schema
 ///
-/// This trait is defined through the [`async-trait`](https://crates.io/crates/async-trait) macro.
-#[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
-pub trait Schema: SchemaBefore {
+/// A Schema is not bound to any database: its documents are saved, removed and populated through the `Nongoose`
+/// instance where it is registered (`Nongoose.save()`, `Nongoose.remove()` and `Nongoose.populate()`).
+pub trait Schema: DeserializeOwned + Serialize + Send + Into<Bson> + Clone {
   /// `_id` field of the Document.
   ///
   /// In the Schema is defined as `#[schema(id)]`
   type Id: Into<Bson> + Clone + Send;
 
-  #[doc(hidden)]
-  fn __get_database(database: Option<Database>) -> &'static Database;
+  /// Field paths of the Schema, generated by `#[derive(Schema)]`.
+  type Fields;
 
   /// Get the collection name.
   fn collection_name() -> String;
 
-  #[doc(hidden)]
-  fn __get_id(&self) -> Self::Id;
-
-  #[doc(hidden)]
-  fn __get_id_query(&self) -> Document {
-    doc! { "_id": self.__get_id().into() }
-  }
-
-  #[doc(hidden)]
-  fn __to_document(&self) -> Result<Document> {
-    let bson: Bson = self.into();
-
-    match bson.as_document() {
-      Some(doc) => Ok(doc.clone()),
-      None => unreachable!(),
-    }
-  }
-
-  #[doc(hidden)]
-  fn __check_unique_fields(&self) -> Result<()>;
-
-  #[doc(hidden)]
-  fn __relations() -> Vec<types::SchemaRelation>;
-
-  #[doc(hidden)]
-  fn __get_relations(&self) -> Option<Vec<types::SchemaRelation>>;
-
-  #[doc(hidden)]
-  fn __set_relations(&mut self, field: &str, new_value: Bson) -> Result<()>;
-
-  #[doc(hidden)]
-  fn __populate_sync(&mut self, field: &str) -> Result<Self> {
-    let database = Self::__get_database(None);
-
-    if let Some(relations) = self.__get_relations() {
-      for relation in relations.iter() {
-        if relation.field_ident == field {
-          let collection_name = &relation.schema_name;
-
-          if relation.relation_type == SchemaRelationType::OneToOne
-            || relation.relation_type == SchemaRelationType::ManyToOne
-          {
-            if let Some(data) = database
-              .collection::<Document>(collection_name.as_str())
-              .find_one(Some(doc! { "_id": relation.field_value.clone() }), None)?
-            {
-              self.__set_relations(field, Bson::Document(data))?;
-            }
-          } else if relation.relation_type == SchemaRelationType::OneToMany {
-            if let Some(schema) = crate::nongoose::globals::get_schema(collection_name) {
-              for schema_relation in schema.get_relations().iter() {
-                if schema_relation.relation_type != SchemaRelationType::ManyToOne {
-                  continue;
-                }
-
-                if schema_relation.schema_name == Self::collection_name() {
-                  let documents: Vec<mongodb::error::Result<Document>> = database
-                    .collection::<Document>(collection_name.as_str())
-                    .find(
-                      Some(doc! { schema_relation.field_id(): self.__get_id().into() }),
-                      None,
-                    )?
-                    .collect();
-
-                  let mut data = Vec::new();
-                  for doc in documents {
-                    let doc = doc?;
-                    data.push(doc);
-                  }
-
-                  self.__set_relations(field, bson!(data))?;
-                  break;
-                }
-              }
-            }
-          }
-        }
-      }
-    }
-
-    Ok(self.clone())
-  }
-
-  /// Populates fields on an existing schema.
+  /// Get the paths of the Schema fields in the database (respecting `#[serde(rename)]`).
   ///
   /// # Example
   /// ```rust,no_run,ignore
-  /// // Populate the role of the user
-  /// match user.clone().populate("role") {
-  ///   Ok(u) => user = u,
-  ///   Err(error) => eprintln!("Error populating user: {}", error),
-  /// }
+  /// let users = nongoose
+  ///   .query::<User>()
+  ///   .filter(User::fields().age.gte(18))
+  ///   .sort(User::fields().username.asc())
+  ///   .find();
   /// ```
-  #[cfg(feature = "sync")]
-  fn populate(mut self, field: &str) -> Result<Self> {
-    self.__populate_sync(field)
-  }
+  fn fields() -> Self::Fields;
 
-  /// Populates fields on an existing schema.
+  /// Validate the fields of the Schema with the rules of their attributes (e.g. `#[schema(min = 0, max = 150)]`).
   ///
-  /// # Example
-  /// ```rust,no_run,ignore
-  /// // Populate the role of the user
-  /// match user.clone().populate("role").await {
-  ///   Ok(u) => user = u,
-  ///   Err(error) => eprintln!("Error populating user: {}", error),
-  /// }
-  /// ```
-  #[cfg(feature = "tokio-runtime")]
-  async fn populate(mut self, field: &'static str) -> Result<Self>
-  where
-    Self: 'static,
-  {
-    spawn_blocking(move || self.__populate_sync(field)).await?
+  /// It is called by `Nongoose.save()` before querying the database and calling the `SchemaBefore` functions and
+  /// the document middlewares, so they never receive an invalid document, and returns `Error::Validation` with
+  /// every failing field and rule.
+  fn validate(&self) -> Result<()> {
+    let errors = self.__validate();
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(Error::Validation(errors))
+    }
   }
 
-  /// Removes this document from the db.
+  /// Create a Schema from a BSON document, setting the default value of the fields that the document does not
+  /// have (`#[schema(default)]`).
   ///
-  /// # Example
-  /// ```rust,no_run,ignore
-  /// match user.remove() {
-  ///   Ok(true) => println!("The user was deleted!"),
-  ///   Ok(false) => println!("The user could not be deleted!"),
-  ///   Err(error) => eprintln!("Error deleting the user: {}", error),
-  /// }
-  /// ```
-  #[cfg(feature = "sync")]
-  fn remove(&self) -> Result<bool> {
-    let db = Self::__get_database(None);
-    let collection = db.collection::<Document>(Self::collection_name().as_str());
-
-    let result = collection.delete_one(self.__get_id_query(), None)?;
-    Ok(result.deleted_count == 1)
-  }
-
-  /// Removes this document from the db.
+  /// It is used to read the documents of the collection, so the documents saved before adding a field with a
+  /// default value can still be read. It can also create a new document from some of its fields.
   ///
   /// # Example
   /// ```rust,no_run,ignore
-  /// match user.remove().await {
-  ///   Ok(true) => println!("The user was deleted!"),
-  ///   Ok(false) => println!("The user could not be deleted!"),
-  ///   Err(error) => eprintln!("Error deleting the user: {}", error),
-  /// }
+  /// let user = User::from_document(doc! { "_id": ObjectId::new(), "username": "dsolartec" })?;
   /// ```
-  #[cfg(feature = "tokio-runtime")]
-  async fn remove(&self) -> Result<bool> {
-    let db = Self::__get_database(None);
-    let collection = db.collection::<Document>(Self::collection_name().as_str());
+  fn from_document(mut document: Document) -> Result<Self> {
+    Self::__apply_defaults(&mut document)?;
+    Ok(from_bson(Bson::Document(document))?)
+  }
 
-    let id = self.__get_id_query();
+  #[doc(hidden)]
+  fn __validate(&self) -> Vec<validation::ValidationError>;
 
-    let result = spawn_blocking(move || collection.delete_one(id, None)).await??;
-    Ok(result.deleted_count == 1)
+  #[doc(hidden)]
+  #[cfg(feature = "tokio-runtime")]
+  fn __validate_async<'a>(
+    &'a self,
+    _ctx: &'a mut HookContext<'_>,
+  ) -> futures::future::BoxFuture<'a, Vec<validation::ValidationError>> {
+    Box::pin(async { Vec::new() })
   }
 
-  /// Saves this document by inserting a new document into the database if it does not exist before, or sends an `replace_one` operation with the modifications to the database.
-  ///
-  /// If the document needs to be inserted to the database, the `SchemaBefore.before_create()` method is called before insert the document;
-  /// otherwise, `SchemaBefore.before_update()` is called before replace the document.
-  ///
-  /// # Example
-  /// ```rust,no_run,ignore
-  /// match user.save() {
-  ///   Ok(u) => user = u,
-  ///   Err(error) => eprintln!("Error saving user: {}", error),
-  /// }
-  /// ```
-  #[cfg(feature = "sync")]
-  fn save(&mut self) -> Result<Self> {
-    let db = Self::__get_database(None);
-    let collection = db.collection::<Document>(Self::collection_name().as_str());
+  /// Whether the Schema has async validators (`#[schema(validate_async = "...")]`).
+  #[doc(hidden)]
+  fn __has_async_validators() -> bool {
+    false
+  }
 
-    self.__check_unique_fields()?;
+  #[doc(hidden)]
+  fn __apply_defaults(document: &mut Document) -> Result<()>;
 
-    if collection
-      .find_one(Some(self.__get_id_query()), None)?
-      .is_some()
-    {
-      self.before_update(db)?;
+  /// Apply the default values to a populated document (or to the documents of an array).
+  #[doc(hidden)]
+  fn __apply_defaults_bson(value: &mut Bson) -> Result<()> {
+    match value {
+      Bson::Document(document) => Self::__apply_defaults(document),
+      Bson::Array(values) => values.iter_mut().try_for_each(Self::__apply_defaults_bson),
+      _ => Ok(()),
+    }
+  }
 
-      let id_query = self.__get_id_query();
-      let document = self.__to_document()?;
+  /// Paths of the creation and update times (`#[schema(timestamps)]`).
+  #[doc(hidden)]
+  fn __timestamps() -> Option<(&'static str, &'static str)> {
+    None
+  }
 
-      collection.replace_one(
-        id_query,
-        document,
-        Some(ReplaceOptions::builder().upsert(true).build()),
-      )?;
-    } else {
-      self.before_create(db)?;
+  /// Set the creation (if the document is new) and update times (`#[schema(timestamps)]`).
+  #[doc(hidden)]
+  fn __set_timestamps(&mut self, _is_new: bool) {}
 
-      let document = self.__to_document()?;
-      collection.insert_one(document, None)?;
+  /// Set the timestamps in an update document.
+  #[doc(hidden)]
+  fn __update_timestamps(update: &mut Document) {
+    if let Some((created_at, updated_at)) = Self::__timestamps() {
+      timestamps::update(update, created_at, updated_at);
     }
-
-    Ok(self.clone())
   }
 
-  /// Saves this document by inserting a new document into the database if it does not exist before, or sends an `replace_one` operation with the modifications to the database.
-  ///
-  /// If the document needs to be inserted to the database, the `SchemaBefore.before_create()` method is called before insert the document;
-  /// otherwise, `SchemaBefore.before_update()` is called before replace the document.
-  ///
-  /// # Example
-  /// ```rust,no_run,ignore
-  /// match user.save().await {
-  ///   Ok(u) => user = u,
-  ///   Err(error) => eprintln!("Error saving user: {}", error),
-  /// }
-  /// ```
-  #[cfg(feature = "tokio-runtime")]
-  async fn save(&mut self) -> Result<Self> {
-    let db = Self::__get_database(None);
-    let collection = db.collection::<Document>(Self::collection_name().as_str());
-
-    self.__check_unique_fields()?;
+  #[doc(hidden)]
+  fn __get_id(&self) -> Self::Id;
 
-    let id_query = self.__get_id_query();
-    let result = spawn_blocking(move || collection.find_one(Some(id_query), None)).await??;
+  #[doc(hidden)]
+  fn __get_id_query(&self) -> Document {
+    doc! { "_id": self.__get_id().into() }
+  }
 
-    if result.is_some() {
-      self.before_update(db).await?;
+  #[doc(hidden)]
+  fn __to_document(&self) -> Result<Document> {
+    let bson: Bson = self.into();
 
-      let collection = db.collection::<Document>(Self::collection_name().as_str());
+    match bson.as_document() {
+      Some(doc) => Ok(doc.clone()),
+      None => unreachable!(),
+    }
+  }
 
-      let id_query = self.__get_id_query();
-      let document = self.__to_document()?;
+  #[doc(hidden)]
+  fn __unique_fields(&self) -> Vec<(Document, String, String)>;
 
-      spawn_blocking(move || {
-        collection.replace_one(
-          id_query,
-          document,
-          Some(ReplaceOptions::builder().upsert(true).build()),
-        )
-      })
-      .await??;
-    } else {
-      self.before_create(db).await?;
+  #[doc(hidden)]
+  fn __indexes() -> Vec<IndexModel>;
 
-      let collection = db.collection::<Document>(Self::collection_name().as_str());
+  #[doc(hidden)]
+  fn __relations() -> Vec<types::SchemaRelation>;
 
-      let document = self.__to_document()?;
-      spawn_blocking(move || collection.insert_one(document, None)).await??;
-    }
+  #[doc(hidden)]
+  fn __get_relations(&self) -> Option<Vec<types::SchemaRelation>>;
 
-    Ok(self.clone())
-  }
+  #[doc(hidden)]
+  fn __set_relations(&mut self, field: &str, new_value: Bson) -> Result<()>;
 }
@@ -0,0 +1,77 @@
+use crate::{error::Result, HookContext, Schema};
+
+/// Schema after functions
+///
+/// This trait is defined through the [`async-trait`](https://crates.io/crates/async-trait) macro.
+/// These are called once the operation is done in the database. They receive the `HookContext` of the
+/// operation (its database, session, Nongoose instance and extensions). With the blocking API,
+/// implement `nongoose::blocking::SchemaAfter` instead.
+///
+/// `#[derive(Schema)]` implements it with the default functions, unless the Schema has the `after_hooks`
+/// attribute (`#[schema(after_hooks)]`).
+#[async_trait::async_trait]
+pub trait SchemaAfter: Schema {
+  /// Executes a custom action after insert the document to the database (e.g. emit a domain event).
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// #[async_trait::async_trait]
+  /// impl SchemaAfter for User {
+  ///   async fn after_create(&mut self, _ctx: &mut HookContext<'_>) -> Result<()> {
+  ///     Ok(())
+  ///   }
+  /// }
+  /// ```
+  async fn after_create(&mut self, _ctx: &mut HookContext<'_>) -> Result<()> {
+    Ok(())
+  }
+
+  /// Executes a custom action after delete the document from the database (e.g. invalidate a cache). It is not
+  /// called if `SchemaBefore.before_delete()` returned `false`.
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// #[async_trait::async_trait]
+  /// impl SchemaAfter for User {
+  ///   async fn after_delete(&mut self, _ctx: &mut HookContext<'_>) -> Result<()> {
+  ///     Ok(())
+  ///   }
+  /// }
+  /// ```
+  async fn after_delete(&mut self, _ctx: &mut HookContext<'_>) -> Result<()> {
+    Ok(())
+  }
+
+  /// Executes a custom action after load the document from the database, before it is returned (e.g. decrypt or
+  /// normalize a field). It is called for each document returned by the find functions (with `find_stream()`, when the
+  /// document is consumed).
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// #[async_trait::async_trait]
+  /// impl SchemaAfter for User {
+  ///   async fn after_find(&mut self, _ctx: &mut HookContext<'_>) -> Result<()> {
+  ///     self.username = self.username.to_lowercase();
+  ///     Ok(())
+  ///   }
+  /// }
+  /// ```
+  async fn after_find(&mut self, _ctx: &mut HookContext<'_>) -> Result<()> {
+    Ok(())
+  }
+
+  /// Executes a custom action after replace the document in the database (called on `Nongoose.save()`).
+  ///
+  /// # Example
+  /// ```rust,no_run,ignore
+  /// #[async_trait::async_trait]
+  /// impl SchemaAfter for User {
+  ///   async fn after_update(&mut self, _ctx: &mut HookContext<'_>) -> Result<()> {
+  ///     Ok(())
+  ///   }
+  /// }
+  /// ```
+  async fn after_update(&mut self, _ctx: &mut HookContext<'_>) -> Result<()> {
+    Ok(())
+  }
+}
@@ -1,103 +1,92 @@
-use mongodb::{bson::Bson, sync::Database};
-use serde::{de::DeserializeOwned, Serialize};
+use mongodb::bson::Document;
 
-use crate::error::Result;
+use crate::{error::Result, HookContext, Schema};
 
 /// Schema before functions
 ///
 /// This trait is defined through the [`async-trait`](https://crates.io/crates/async-trait) macro.
-#[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
-pub trait SchemaBefore: DeserializeOwned + Serialize + Send + Into<Bson> + Clone {
-  /// Executes a custom validation before insert the document to the database.
-  ///
-  /// # Example
-  /// ```rust,no_run,ignore
-  /// impl SchemaBefore for User {
-  ///   fn before_create(&mut self, _db: &Database) -> Result<()> {
-  ///     Ok(())
-  ///   }
-  /// }
-  /// ```
-  #[cfg(feature = "sync")]
-  fn before_create(&mut self, _db: &Database) -> Result<()> {
-    Ok(())
-  }
-
+/// The functions receive the `HookContext` of the operation (its database, session, Nongoose instance and
+/// extensions). With the blocking API, implement `nongoose::blocking::SchemaBefore` instead.
+#[async_trait::async_trait]
+pub trait SchemaBefore: Schema {
   /// Executes a custom validation before insert the document to the database.
   ///
   /// # Example
   /// ```rust,no_run,ignore
   /// #[async_trait::async_trait]
-  /// impl SchemaBefore for User {
-  ///   async fn before_create(&mut self, _db: &Database) -> Result<()> {
+  /// impl SchemaBefore for Post {
+  ///   async fn before_create(&mut self, ctx: &mut HookContext<'_>) -> Result<()> {
+  ///     let users = ctx.database().collection::<Document>("users");
+  ///     let query = doc! { "_id": self.author_id };
+  ///     let update = doc! { "$inc": { "posts": 1 } };
+  ///
+  ///     // Inside a transaction, update the author with its session
+  ///     match ctx.session() {
+  ///       Some(session) => users.update_one_with_session(query, update, None, session).await?,
+  ///       None => users.update_one(query, update, None).await?,
+  ///     };
+  ///
   ///     Ok(())
   ///   }
   /// }
   /// ```
-  #[cfg(feature = "tokio-runtime")]
-  async fn before_create(&mut self, _db: &Database) -> Result<()> {
+  async fn before_create(&mut self, _ctx: &mut HookContext<'_>) -> Result<()> {
     Ok(())
   }
 
-  /// Executes a custom validation before delete the document from the database.
+  /// Executes a custom validation before delete the document from the database. If it returns `false`, the
+  /// document is not removed.
   ///
   /// # Example
   /// ```rust,no_run,ignore
+  /// #[async_trait::async_trait]
   /// impl SchemaBefore for User {
-  ///   fn before_delete(&mut self, _db: &Database) -> Result<bool> {
+  ///   async fn before_delete(&mut self, _ctx: &mut HookContext<'_>) -> Result<bool> {
   ///     Ok(true)
   ///   }
   /// }
   /// ```
-  #[cfg(feature = "sync")]
-  fn before_delete(&mut self, _db: &Database) -> Result<bool> {
+  async fn before_delete(&mut self, _ctx: &mut HookContext<'_>) -> Result<bool> {
     Ok(true)
   }
 
-  /// Executes a custom validation before delete the document from the database.
+  /// Executes a custom validation before update a document with `Nongoose.find_one_and_update()` (or
+  /// `Nongoose.find_by_id_and_update()`). It receives the conditions and the update document, which can be
+  /// modified (e.g. to set an `updated_at` field); if it returns an error, the document is not updated.
   ///
   /// # Example
   /// ```rust,no_run,ignore
   /// #[async_trait::async_trait]
   /// impl SchemaBefore for User {
-  ///   async fn before_delete(&mut self, _db: &Database) -> Result<bool> {
-  ///     Ok(true)
-  ///   }
-  /// }
-  /// ```
-  #[cfg(feature = "tokio-runtime")]
-  async fn before_delete(&mut self, _db: &Database) -> Result<bool> {
-    Ok(true)
-  }
-
-  /// Executes a custom validation before replace the document in the database (called on `Schema.save()`).
+  ///   async fn before_find_one_and_update(_conditions: &Document, update: &mut Document, _ctx: &mut HookContext<'_>) -> Result<()> {
+  ///     if update.contains_key("$unset") {
+  ///       return Err(Error::NoImplemented);
+  ///     }
   ///
-  /// # Example
-  /// ```rust,no_run,ignore
-  /// impl SchemaBefore for User {
-  ///   fn before_update(&mut self, _db: &Database) -> Result<()> {
   ///     Ok(())
   ///   }
   /// }
   /// ```
-  #[cfg(feature = "sync")]
-  fn before_update(&mut self, _db: &Database) -> Result<()> {
+  async fn before_find_one_and_update(
+    _conditions: &Document,
+    _update: &mut Document,
+    _ctx: &mut HookContext<'_>,
+  ) -> Result<()> {
     Ok(())
   }
 
-  /// Executes a custom validation before replace the document in the database (called on `Schema.save()`).
+  /// Executes a custom validation before replace the document in the database (called on `Nongoose.save()`).
   ///
   /// # Example
   /// ```rust,no_run,ignore
   /// #[async_trait::async_trait]
   /// impl SchemaBefore for User {
-  ///   async fn before_update(&mut self, _db: &Database) -> Result<()> {
+  ///   async fn before_update(&mut self, _ctx: &mut HookContext<'_>) -> Result<()> {
   ///     Ok(())
   ///   }
   /// }
   /// ```
-  #[cfg(feature = "tokio-runtime")]
-  async fn before_update(&mut self, _db: &Database) -> Result<()> {
+  async fn before_update(&mut self, _ctx: &mut HookContext<'_>) -> Result<()> {
     Ok(())
   }
 }
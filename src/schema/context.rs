@@ -0,0 +1,160 @@
+use std::{
+  any::{Any, TypeId},
+  collections::HashMap,
+  fmt,
+  sync::Arc,
+};
+
+#[cfg(feature = "tokio-runtime")]
+use crate::{ClientSession, Database, Nongoose, NongooseBuilder};
+
+/// Operation that calls the functions of `SchemaBefore` and `SchemaAfter`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum HookOperation {
+  /// Insert of a new document (`save()` and `create()`).
+  Create,
+
+  /// Replace of an existing document (`save()`).
+  Update,
+
+  /// Remove of a document (`remove()` and the `*_and_remove()` functions).
+  Delete,
+
+  /// Find of documents (`find()`, `find_one()` and `find_by_id()`).
+  Find,
+
+  /// Update of a document with `find_one_and_update()` (or `find_by_id_and_update()`).
+  FindOneAndUpdate,
+}
+
+/// Request-scoped data, indexed by its type (e.g. the id of the current user).
+///
+/// The extensions of a Nongoose instance are set with `Nongoose.with_extension()` and every operation receives
+/// a copy of them in its `HookContext`, shared by the `SchemaBefore` and `SchemaAfter` functions it calls.
+///
+/// # Example
+/// ```rust,no_run,ignore
+/// struct CurrentUser(ObjectId);
+///
+/// let nongoose = nongoose.with_extension(CurrentUser(user.id));
+///
+/// // Inside a hook
+/// if let Some(CurrentUser(user_id)) = ctx.extensions().get::<CurrentUser>() {
+///   self.updated_by = *user_id;
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct Extensions(HashMap<TypeId, Arc<dyn Any + Send + Sync>>);
+
+impl Extensions {
+  /// Create an empty map of extensions.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Insert a value, replacing the previous value of the same type.
+  pub fn insert<T>(&mut self, value: T)
+  where
+    T: Any + Send + Sync,
+  {
+    self.0.insert(TypeId::of::<T>(), Arc::new(value));
+  }
+
+  /// Get the value of a type.
+  pub fn get<T>(&self) -> Option<&T>
+  where
+    T: Any + Send + Sync,
+  {
+    self
+      .0
+      .get(&TypeId::of::<T>())
+      .and_then(|value| value.downcast_ref::<T>())
+  }
+
+  /// Verify if there is a value of a type.
+  pub fn contains<T>(&self) -> bool
+  where
+    T: Any + Send + Sync,
+  {
+    self.0.contains_key(&TypeId::of::<T>())
+  }
+
+  /// Remove the value of a type, returning `true` if there was one.
+  pub fn remove<T>(&mut self) -> bool
+  where
+    T: Any + Send + Sync,
+  {
+    self.0.remove(&TypeId::of::<T>()).is_some()
+  }
+}
+
+impl fmt::Debug for Extensions {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_tuple("Extensions").field(&self.0.len()).finish()
+  }
+}
+
+/// Context of the functions of `SchemaBefore` and `SchemaAfter`.
+///
+/// It is created for each operation, so the extensions inserted by a `SchemaBefore` function are available to
+/// the `SchemaAfter` functions of the same operation. With the blocking API, see `nongoose::blocking::HookContext`.
+#[cfg(feature = "tokio-runtime")]
+pub struct HookContext<'a> {
+  operation: HookOperation,
+  builder: &'a NongooseBuilder<Database>,
+  session: Option<&'a mut ClientSession>,
+  extensions: &'a mut Extensions,
+}
+
+#[cfg(feature = "tokio-runtime")]
+impl<'a> HookContext<'a> {
+  pub(crate) fn new(
+    operation: HookOperation,
+    builder: &'a NongooseBuilder<Database>,
+    session: Option<&'a mut ClientSession>,
+    extensions: &'a mut Extensions,
+  ) -> Self {
+    Self {
+      operation,
+      builder,
+      session,
+      extensions,
+    }
+  }
+
+  /// Operation that calls the function.
+  pub fn operation(&self) -> HookOperation {
+    self.operation
+  }
+
+  /// Database of the Nongoose instance.
+  pub fn database(&self) -> &Database {
+    &self.builder.database
+  }
+
+  /// Session of the transaction, if the operation runs inside one (see `Nongoose.transaction()`).
+  pub fn session(&mut self) -> Option<&mut ClientSession> {
+    self.session.as_deref_mut()
+  }
+
+  /// Nongoose instance of the operation, to run typed queries (outside the transaction, use `session()` to
+  /// run operations inside it). Its extensions are the ones of the operation, so the nested operations receive
+  /// them too.
+  pub fn nongoose(&self) -> Nongoose {
+    let mut builder = self.builder.clone();
+    builder.extensions = self.extensions.clone();
+
+    Nongoose { builder }
+  }
+
+  /// Extensions of the operation.
+  pub fn extensions(&self) -> &Extensions {
+    self.extensions
+  }
+
+  /// Mutable extensions of the operation.
+  pub fn extensions_mut(&mut self) -> &mut Extensions {
+    self.extensions
+  }
+}
@@ -1,9 +1,13 @@
+use mongodb::{bson::to_document, IndexModel};
+
 use crate::types::SchemaRelation;
 
-#[derive(Clone, Debug, PartialEq)]
+/// Schema registered in a Nongoose instance (see `NongooseBuilder.add_schema()`).
+#[derive(Clone, Debug)]
 pub struct SchemaData {
   name: String,
   relations: Vec<SchemaRelation>,
+  indexes: Vec<IndexModel>,
 }
 
 impl SchemaData {
@@ -14,14 +18,43 @@ impl SchemaData {
     Self {
       name: T::collection_name(),
       relations: T::__relations(),
+      indexes: T::__indexes(),
     }
   }
 
+  /// Get the collection name of the Schema.
   pub fn get_name(&self) -> String {
     self.name.clone()
   }
 
+  /// Get the relations of the Schema.
   pub fn get_relations(&self) -> Vec<SchemaRelation> {
     self.relations.clone()
   }
+
+  /// Get the indexes of the Schema (declared with `#[schema(index)]` or added by the plugins), created by
+  /// `Nongoose.ensure_indexes()` and `NongooseBuilder.sync_indexes()` (or `NongooseBuilder.auto_index(true)`).
+  pub fn get_indexes(&self) -> Vec<IndexModel> {
+    self.indexes.clone()
+  }
+
+  /// Add an index to the Schema (e.g. by `Plugin.add_schema()`).
+  pub fn add_index(&mut self, index: IndexModel) {
+    self.indexes.push(index);
+  }
+}
+
+impl PartialEq for SchemaData {
+  fn eq(&self, other: &Self) -> bool {
+    // `IndexModel` does not implement `PartialEq`, so the indexes are compared as documents.
+    let indexes = |data: &Self| {
+      data
+        .indexes
+        .iter()
+        .map(|index| to_document(index).ok())
+        .collect::<Vec<_>>()
+    };
+
+    self.name == other.name && self.relations == other.relations && indexes(self) == indexes(other)
+  }
 }
@@ -0,0 +1,68 @@
+use mongodb::bson::{doc, Bson, DateTime, Document};
+
+/// Type of a field of `#[schema(timestamps)]`: `bson::DateTime`, `chrono::DateTime<Utc>` (stored as a BSON date
+/// with `bson::serde_helpers::chrono_datetime_as_bson_datetime`) or an `Option` of them.
+pub trait Timestamp {
+  fn from_datetime(datetime: DateTime) -> Self;
+}
+
+impl Timestamp for DateTime {
+  fn from_datetime(datetime: DateTime) -> Self {
+    datetime
+  }
+}
+
+impl Timestamp for chrono::DateTime<chrono::Utc> {
+  fn from_datetime(datetime: DateTime) -> Self {
+    datetime.to_chrono()
+  }
+}
+
+impl<T> Timestamp for Option<T>
+where
+  T: Timestamp,
+{
+  fn from_datetime(datetime: DateTime) -> Self {
+    Some(T::from_datetime(datetime))
+  }
+}
+
+/// Set the timestamps in an update document (`update_many` and `find_one_and_update`): the update time with
+/// `$currentDate` and, for the upserts, the creation time with `$setOnInsert`. The timestamps already set by the
+/// update are not changed.
+pub(crate) fn update(update: &mut Document, created_at: &str, updated_at: &str) {
+  // Replacement documents and aggregation pipelines are not changed.
+  if update.is_empty() || !update.keys().all(|key| key.starts_with('$')) {
+    return;
+  }
+
+  let updates = |update: &Document, path: &str| {
+    update
+      .values()
+      .any(|value| matches!(value, Bson::Document(fields) if fields.contains_key(path)))
+  };
+
+  if !updates(update, updated_at) {
+    insert_operator(update, "$currentDate", updated_at, Bson::Boolean(true));
+  }
+
+  if !updates(update, created_at) {
+    insert_operator(
+      update,
+      "$setOnInsert",
+      created_at,
+      Bson::DateTime(DateTime::now()),
+    );
+  }
+}
+
+fn insert_operator(update: &mut Document, operator: &str, path: &str, value: Bson) {
+  match update.get_mut(operator) {
+    Some(Bson::Document(fields)) => {
+      fields.insert(path, value);
+    }
+    _ => {
+      update.insert(operator, doc! { path: value });
+    }
+  }
+}
@@ -1,4 +1,6 @@
-use mongodb::bson::Bson;
+use std::{fmt, marker::PhantomData};
+
+use mongodb::bson::{doc, Bson, Document};
 
 use crate::error::{Error, Result};
 
@@ -72,3 +74,143 @@ impl SchemaRelation {
     format!("{}_id", self.field_ident)
   }
 }
+
+/// Path of a field of the Schema `T` in the database.
+///
+/// The field paths are generated by `#[derive(Schema)]` and can be accessed using `Schema::fields()`.
+///
+/// # Example
+/// ```rust,no_run,ignore
+/// let fields = User::fields();
+///
+/// // { "username": "nongoose" }
+/// fields.username.eq("nongoose");
+///
+/// // { "age": { "$gte": 18 } }
+/// fields.age.gte(18);
+///
+/// // { "age": -1 }
+/// fields.age.desc();
+/// ```
+pub struct SchemaField<T> {
+  path: &'static str,
+  _schema: PhantomData<fn() -> T>,
+}
+
+impl<T> SchemaField<T> {
+  #[doc(hidden)]
+  pub const fn new(path: &'static str) -> Self {
+    Self {
+      path,
+      _schema: PhantomData,
+    }
+  }
+
+  /// Name of the field in the database.
+  pub fn path(&self) -> &'static str {
+    self.path
+  }
+
+  fn operator(&self, operator: &str, value: Bson) -> Document {
+    doc! { self.path: { operator: value } }
+  }
+
+  /// Matches documents where the field is equal to `value`.
+  pub fn eq(&self, value: impl Into<Bson>) -> Document {
+    doc! { self.path: value.into() }
+  }
+
+  /// Matches documents where the field is not equal to `value` (`$ne`).
+  pub fn ne(&self, value: impl Into<Bson>) -> Document {
+    self.operator("$ne", value.into())
+  }
+
+  /// Matches documents where the field is greater than `value` (`$gt`).
+  pub fn gt(&self, value: impl Into<Bson>) -> Document {
+    self.operator("$gt", value.into())
+  }
+
+  /// Matches documents where the field is greater than or equal to `value` (`$gte`).
+  pub fn gte(&self, value: impl Into<Bson>) -> Document {
+    self.operator("$gte", value.into())
+  }
+
+  /// Matches documents where the field is less than `value` (`$lt`).
+  pub fn lt(&self, value: impl Into<Bson>) -> Document {
+    self.operator("$lt", value.into())
+  }
+
+  /// Matches documents where the field is less than or equal to `value` (`$lte`).
+  pub fn lte(&self, value: impl Into<Bson>) -> Document {
+    self.operator("$lte", value.into())
+  }
+
+  /// Matches documents where the field is equal to any of `values` (`$in`).
+  pub fn is_in<V>(&self, values: impl IntoIterator<Item = V>) -> Document
+  where
+    V: Into<Bson>,
+  {
+    let values: Vec<Bson> = values.into_iter().map(Into::into).collect();
+    self.operator("$in", Bson::Array(values))
+  }
+
+  /// Matches documents where the field is not equal to any of `values` (`$nin`).
+  pub fn not_in<V>(&self, values: impl IntoIterator<Item = V>) -> Document
+  where
+    V: Into<Bson>,
+  {
+    let values: Vec<Bson> = values.into_iter().map(Into::into).collect();
+    self.operator("$nin", Bson::Array(values))
+  }
+
+  /// Matches documents that contain (or not) the field (`$exists`).
+  pub fn exists(&self, exists: bool) -> Document {
+    self.operator("$exists", Bson::Boolean(exists))
+  }
+
+  /// Ascending sort by the field.
+  pub fn asc(&self) -> Document {
+    doc! { self.path: 1 }
+  }
+
+  /// Descending sort by the field.
+  pub fn desc(&self) -> Document {
+    doc! { self.path: -1 }
+  }
+
+  /// Projection including the field.
+  pub fn include(&self) -> Document {
+    doc! { self.path: 1 }
+  }
+
+  /// Projection excluding the field.
+  pub fn exclude(&self) -> Document {
+    doc! { self.path: 0 }
+  }
+}
+
+impl<T> Clone for SchemaField<T> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T> Copy for SchemaField<T> {}
+
+impl<T> fmt::Debug for SchemaField<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_tuple("SchemaField").field(&self.path).finish()
+  }
+}
+
+impl<T> fmt::Display for SchemaField<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.path)
+  }
+}
+
+impl<T> AsRef<str> for SchemaField<T> {
+  fn as_ref(&self) -> &str {
+    self.path
+  }
+}
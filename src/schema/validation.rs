@@ -0,0 +1,177 @@
+use std::{
+  collections::HashMap,
+  fmt,
+  sync::{Mutex, OnceLock},
+};
+
+use regex::Regex;
+
+#[cfg(feature = "tokio-runtime")]
+use crate::schema::HookContext;
+use crate::{
+  error::{Error, Result},
+  schema::Schema,
+};
+
+/// Rule of a Schema field that failed the validation (see `Schema.validate()`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidationError {
+  /// Path of the field in the database.
+  pub field: String,
+
+  /// Failed rule (e.g. `min`, `max_length` or `required`).
+  pub rule: String,
+
+  /// Description of the failure.
+  pub message: String,
+}
+
+impl ValidationError {
+  /// Create a validation error of a field.
+  pub fn new<F, R, M>(field: F, rule: R, message: M) -> Self
+  where
+    F: Into<String>,
+    R: Into<String>,
+    M: Into<String>,
+  {
+    Self {
+      field: field.into(),
+      rule: rule.into(),
+      message: message.into(),
+    }
+  }
+}
+
+impl fmt::Display for ValidationError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} ({}): {}", self.field, self.rule, self.message)
+  }
+}
+
+/// Call the async validators of a document (`#[schema(validate_async = "...")]`), once it passed
+/// `Schema.validate()`, returning their failures in an `Error::Validation`.
+#[cfg(feature = "tokio-runtime")]
+pub(crate) async fn validate_async<T>(data: &mut T, ctx: &mut HookContext<'_>) -> Result<()>
+where
+  T: Schema,
+{
+  let errors = data.__validate_async(ctx).await;
+
+  if errors.is_empty() {
+    Ok(())
+  } else {
+    Err(Error::Validation(errors))
+  }
+}
+
+/// Validate a document with the blocking API, which cannot call the async validators of the Schema, so it returns
+/// `Error::AsyncValidatorsNotSupported` instead of saving a document without them.
+#[cfg(feature = "sync")]
+pub(crate) fn validate_blocking<T>(data: &T) -> Result<()>
+where
+  T: Schema,
+{
+  data.validate()?;
+
+  if T::__has_async_validators() {
+    return Err(Error::AsyncValidatorsNotSupported {
+      collection: T::collection_name(),
+    });
+  }
+
+  Ok(())
+}
+
+/// Length of a value checked by the `min_length` and `max_length` rules (the number of characters of a string or
+/// the number of items of a collection).
+#[doc(hidden)]
+pub trait Length {
+  fn length(&self) -> usize;
+}
+
+impl Length for str {
+  fn length(&self) -> usize {
+    self.chars().count()
+  }
+}
+
+impl Length for String {
+  fn length(&self) -> usize {
+    self.as_str().length()
+  }
+}
+
+impl<T> Length for [T] {
+  fn length(&self) -> usize {
+    self.len()
+  }
+}
+
+impl<T> Length for Vec<T> {
+  fn length(&self) -> usize {
+    self.len()
+  }
+}
+
+#[doc(hidden)]
+pub fn min<T, L>(value: &T, min: L) -> bool
+where
+  T: PartialOrd<L>,
+{
+  *value >= min
+}
+
+#[doc(hidden)]
+pub fn max<T, L>(value: &T, max: L) -> bool
+where
+  T: PartialOrd<L>,
+{
+  *value <= max
+}
+
+#[doc(hidden)]
+pub fn min_length<T>(value: &T, min: usize) -> bool
+where
+  T: Length + ?Sized,
+{
+  value.length() >= min
+}
+
+#[doc(hidden)]
+pub fn max_length<T>(value: &T, max: usize) -> bool
+where
+  T: Length + ?Sized,
+{
+  value.length() <= max
+}
+
+#[doc(hidden)]
+pub fn one_of<T, L>(value: &T, values: &[L]) -> bool
+where
+  T: PartialEq<L>,
+{
+  values.iter().any(|v| value == v)
+}
+
+/// The regular expressions are compiled once and cached by pattern (`#[derive(Schema)]` rejects the invalid
+/// patterns at compile time).
+#[doc(hidden)]
+pub fn regex<T>(value: &T, pattern: &'static str) -> bool
+where
+  T: AsRef<str> + ?Sized,
+{
+  static CACHE: OnceLock<Mutex<HashMap<&'static str, Regex>>> = OnceLock::new();
+
+  let mut cache = CACHE
+    .get_or_init(Default::default)
+    .lock()
+    .unwrap_or_else(|error| error.into_inner());
+
+  cache
+    .entry(pattern)
+    .or_insert_with(|| match Regex::new(pattern) {
+      Ok(regex) => regex,
+      Err(error) => panic!("Invalid regex of the Schema ({}): {}", pattern, error),
+    })
+    .is_match(value.as_ref())
+}
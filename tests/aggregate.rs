@@ -1,7 +1,11 @@
+#[cfg(feature = "sync")]
+use nongoose::blocking;
 use nongoose::{
   bson::{doc, oid::ObjectId, Document, Regex},
-  schema_relations, Client, Nongoose, Schema, SchemaBefore,
+  schema_relations, Schema,
 };
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, Nongoose, SchemaBefore};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Schema, Serialize)]
@@ -28,7 +32,11 @@ impl User {
   }
 }
 
-#[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for User {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
 impl SchemaBefore for User {}
 
 #[schema_relations]
@@ -56,7 +64,11 @@ impl Post {
   }
 }
 
-#[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Post {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
 impl SchemaBefore for Post {}
 
 #[schema_relations]
@@ -90,7 +102,11 @@ impl PostComment {
   }
 }
 
-#[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for PostComment {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
 impl SchemaBefore for PostComment {}
 
 #[derive(Debug)]
@@ -122,8 +138,33 @@ impl From<Document> for SearchResult {
   }
 }
 
-#[cfg(test)]
-fn get_instance() -> Nongoose {
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> blocking::Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  blocking::Nongoose::builder(client.database("nongoose_blocking"))
+    .add_schema::<User>()
+    .add_schema::<Post>()
+    .add_schema::<PostComment>()
+    .build()
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> Nongoose {
   // Get database url.
   let url = match std::env::var("DATABASE_URL") {
     Ok(url) => url,
@@ -133,7 +174,7 @@ fn get_instance() -> Nongoose {
   };
 
   // Get MongoDB connection.
-  let client = match Client::with_uri_str(&url) {
+  let client = match Client::with_uri_str(&url).await {
     Ok(client) => client,
     Err(e) => {
       panic!("Error connecting to the database: {}", e);
@@ -150,32 +191,44 @@ fn get_instance() -> Nongoose {
 #[cfg(feature = "tokio-runtime")]
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 async fn aggregate() {
-  let nongoose = get_instance();
+  let nongoose = get_instance().await;
 
   // Create default users.
-  let daniel = User::new("daniel", "Daniel Solarte", 19).save().await;
+  let daniel = nongoose
+    .create(&User::new("daniel", "Daniel Solarte", 19))
+    .await;
   assert!(daniel.is_ok());
 
-  let robert = User::new("robert", "Robert", 25).save().await;
+  let robert = nongoose.create(&User::new("robert", "Robert", 25)).await;
   assert!(robert.is_ok());
 
   let daniel = daniel.unwrap();
   let robert = robert.unwrap();
 
   // Create default posts.
-  let nongoose_released = Post::new("Nongoose v0.1.0 released!", &robert).save().await;
+  let nongoose_released = nongoose
+    .create(&Post::new("Nongoose v0.1.0 released!", &robert))
+    .await;
   assert!(nongoose_released.is_ok());
 
   let nongoose_released = nongoose_released.unwrap();
 
   // Create default post comments.
-  let comment1 = PostComment::new("Hello! First comment", &nongoose_released, &daniel)
-    .save()
+  let comment1 = nongoose
+    .create(&PostComment::new(
+      "Hello! First comment",
+      &nongoose_released,
+      &daniel,
+    ))
     .await;
   assert!(comment1.is_ok());
 
-  let comment2 = PostComment::new("OMG, aggregations finally! :)", &nongoose_released, &robert)
-    .save()
+  let comment2 = nongoose
+    .create(&PostComment::new(
+      "OMG, aggregations finally! :)",
+      &nongoose_released,
+      &robert,
+    ))
     .await;
   assert!(comment2.is_ok());
 
@@ -220,38 +273,45 @@ async fn aggregate() {
   let result = result.unwrap();
   assert_eq!(result.len(), 1);
 
-  let result_0 = result.get(0).unwrap();
+  let result_0 = result.first().unwrap();
   assert_eq!(result_0.posts_with_comments.len(), 1);
   assert_eq!(result_0.users_with_comments.len(), 2);
 }
 
 #[cfg(feature = "sync")]
 #[cfg_attr(feature = "sync", test)]
-fn aggregate() {
-  let nongoose = get_instance();
+fn aggregate_blocking() {
+  let nongoose = get_blocking_instance();
 
   // Create default users.
-  let daniel = User::new("daniel", "Daniel Solarte", 19).save();
+  let daniel = nongoose.create(&User::new("daniel", "Daniel Solarte", 19));
   assert!(daniel.is_ok());
 
-  let robert = User::new("robert", "Robert", 25).save();
+  let robert = nongoose.create(&User::new("robert", "Robert", 25));
   assert!(robert.is_ok());
 
   let daniel = daniel.unwrap();
   let robert = robert.unwrap();
 
   // Create default posts.
-  let nongoose_released = Post::new("Nongoose v0.1.0 released!", &robert).save();
+  let nongoose_released = nongoose.create(&Post::new("Nongoose v0.1.0 released!", &robert));
   assert!(nongoose_released.is_ok());
 
   let nongoose_released = nongoose_released.unwrap();
 
   // Create default post comments.
-  let comment1 = PostComment::new("Hello! First comment", &nongoose_released, &daniel).save();
+  let comment1 = nongoose.create(&PostComment::new(
+    "Hello! First comment",
+    &nongoose_released,
+    &daniel,
+  ));
   assert!(comment1.is_ok());
 
-  let comment2 =
-    PostComment::new("OMG, aggregations finally! :)", &nongoose_released, &robert).save();
+  let comment2 = nongoose.create(&PostComment::new(
+    "OMG, aggregations finally! :)",
+    &nongoose_released,
+    &robert,
+  ));
   assert!(comment2.is_ok());
 
   // Aggregation
@@ -293,7 +353,7 @@ fn aggregate() {
   let result = result.unwrap();
   assert_eq!(result.len(), 1);
 
-  let result_0 = result.get(0).unwrap();
+  let result_0 = result.first().unwrap();
   assert_eq!(result_0.posts_with_comments.len(), 1);
   assert_eq!(result_0.users_with_comments.len(), 2);
 }
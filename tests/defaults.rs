@@ -0,0 +1,180 @@
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{
+  bson::{doc, oid::ObjectId, Document},
+  Schema,
+};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, Database, Nongoose, SchemaBefore};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Schema, Serialize)]
+#[schema(name = "settings_defaults")]
+struct Settings {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub username: String,
+  #[schema(default = "default_theme")]
+  pub theme: String,
+  #[schema(default)]
+  #[serde(rename = "fontSize")]
+  pub font_size: u32,
+  #[schema(default)]
+  pub tags: Vec<String>,
+}
+
+fn default_theme() -> String {
+  String::from("light")
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Settings {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Settings {}
+
+/// Document saved before adding the fields with default values.
+fn old_document(id: ObjectId) -> Document {
+  doc! { "_id": id, "username": "dsolartec" }
+}
+
+fn assert_defaults(settings: Settings, id: ObjectId) {
+  assert_eq!(
+    settings,
+    Settings {
+      id,
+      username: String::from("dsolartec"),
+      theme: String::from("light"),
+      font_size: 0,
+      tags: Vec::new(),
+    }
+  );
+}
+
+#[test]
+fn from_document() {
+  let id = ObjectId::new();
+
+  let settings = Settings::from_document(old_document(id));
+  assert!(settings.is_ok());
+  assert_defaults(settings.unwrap(), id);
+
+  // The values of the document are kept
+  let settings = Settings::from_document(doc! {
+    "_id": id,
+    "username": "dsolartec",
+    "theme": "dark",
+    "fontSize": 16,
+    "tags": ["rust"],
+  });
+  assert!(settings.is_ok());
+
+  let settings = settings.unwrap();
+  assert_eq!(settings.theme, "dark");
+  assert_eq!(settings.font_size, 16);
+  assert_eq!(settings.tags, vec![String::from("rust")]);
+
+  // The fields without default values are still required
+  assert!(Settings::from_document(doc! { "_id": id }).is_err());
+}
+
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> (blocking::Nongoose, blocking::Database) {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  let database = client.database("nongoose_blocking");
+  let nongoose = blocking::Nongoose::builder(database.clone())
+    .add_schema::<Settings>()
+    .build();
+
+  (nongoose, database)
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> (Nongoose, Database) {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match Client::with_uri_str(&url).await {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  let database = client.database("nongoose");
+  let nongoose = Nongoose::builder(database.clone())
+    .add_schema::<Settings>()
+    .build();
+
+  (nongoose, database)
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn defaults_blocking() {
+  let (nongoose, database) = get_blocking_instance();
+
+  let id = ObjectId::new();
+  let result = database
+    .collection::<Document>(&Settings::collection_name())
+    .insert_one(old_document(id), None);
+  assert!(result.is_ok());
+
+  let settings = nongoose.find_by_id::<Settings>(&id);
+  assert!(settings.is_ok());
+
+  let settings = settings.unwrap();
+  assert!(settings.is_some());
+  assert_defaults(settings.unwrap(), id);
+
+  let settings = nongoose.find::<Settings>(doc! { "_id": id }, None);
+  assert!(settings.is_ok());
+  assert_defaults(settings.unwrap().remove(0), id);
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn defaults() {
+  let (nongoose, database) = get_instance().await;
+
+  let id = ObjectId::new();
+  let result = database
+    .collection::<Document>(&Settings::collection_name())
+    .insert_one(old_document(id), None)
+    .await;
+  assert!(result.is_ok());
+
+  let settings = nongoose.find_by_id::<Settings>(&id).await;
+  assert!(settings.is_ok());
+
+  let settings = settings.unwrap();
+  assert!(settings.is_some());
+  assert_defaults(settings.unwrap(), id);
+
+  let settings = nongoose.find::<Settings>(doc! { "_id": id }, None).await;
+  assert!(settings.is_ok());
+  assert_defaults(settings.unwrap().remove(0), id);
+}
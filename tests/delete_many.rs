@@ -0,0 +1,164 @@
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{
+  bson::{doc, oid::ObjectId},
+  Schema,
+};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, Nongoose, SchemaBefore};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "tokio-runtime")]
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "actors_delete_many")]
+struct Actor {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub fullname: String,
+  pub age: u64,
+}
+
+#[cfg(feature = "sync")]
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "actors_delete_many_blocking")]
+struct BlockingActor {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub fullname: String,
+  pub age: u64,
+}
+
+#[cfg(feature = "tokio-runtime")]
+impl Actor {
+  pub fn new(fullname: &str, age: u64) -> Self {
+    Self {
+      id: ObjectId::new(),
+      fullname: String::from(fullname),
+      age,
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl BlockingActor {
+  pub fn new(fullname: &str, age: u64) -> Self {
+    Self {
+      id: ObjectId::new(),
+      fullname: String::from(fullname),
+      age,
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for BlockingActor {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Actor {}
+
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> blocking::Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  blocking::Nongoose::builder(client.database("nongoose"))
+    .add_schema::<BlockingActor>()
+    .build()
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match Client::with_uri_str(&url).await {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  Nongoose::builder(client.database("nongoose"))
+    .add_schema::<Actor>()
+    .build()
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn delete_many_blocking() {
+  let nongoose = get_blocking_instance();
+
+  for actor in [
+    BlockingActor::new("Emma Stone", 32),
+    BlockingActor::new("Leonardo DiCaprio", 46),
+    BlockingActor::new("Tom Hanks", 65),
+  ]
+  .iter()
+  {
+    assert!(nongoose.create(actor).is_ok());
+  }
+
+  let result = nongoose.delete_many::<BlockingActor>(doc! { "age": { "$gte": 40 } }, None);
+  assert!(result.is_ok());
+  assert_eq!(result.unwrap().deleted_count, 2);
+
+  let count = nongoose.count::<BlockingActor>(doc! {}, None);
+  assert!(count.is_ok());
+  assert_eq!(count.unwrap(), 1);
+
+  let result = nongoose.delete_many::<BlockingActor>(doc! {}, None);
+  assert!(result.is_ok());
+  assert_eq!(result.unwrap().deleted_count, 1);
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn delete_many() {
+  let nongoose = get_instance().await;
+
+  for actor in [
+    Actor::new("Emma Stone", 32),
+    Actor::new("Leonardo DiCaprio", 46),
+    Actor::new("Tom Hanks", 65),
+  ]
+  .iter()
+  {
+    assert!(nongoose.create(actor).await.is_ok());
+  }
+
+  let result = nongoose
+    .delete_many::<Actor>(doc! { "age": { "$gte": 40 } }, None)
+    .await;
+  assert!(result.is_ok());
+  assert_eq!(result.unwrap().deleted_count, 2);
+
+  let count = nongoose.count::<Actor>(doc! {}, None).await;
+  assert!(count.is_ok());
+  assert_eq!(count.unwrap(), 1);
+
+  let result = nongoose.delete_many::<Actor>(doc! {}, None).await;
+  assert!(result.is_ok());
+  assert_eq!(result.unwrap().deleted_count, 1);
+}
@@ -0,0 +1,252 @@
+use std::time::Duration;
+
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{
+  bson::{doc, oid::ObjectId, DateTime, Document},
+  IndexModel, Schema,
+};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, Database, Nongoose, SchemaBefore};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Schema, Serialize)]
+#[schema(name = "sessions_expires")]
+struct Session {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub user_id: ObjectId,
+  #[schema(expires_after = "1h30m")]
+  #[serde(rename = "expiresAt")]
+  pub expires_at: DateTime,
+}
+
+// Same collection, with a shorter time to live
+#[derive(Clone, Debug, Deserialize, PartialEq, Schema, Serialize)]
+#[schema(name = "sessions_expires", expires_after_seconds = 60)]
+struct ShortSession {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub user_id: ObjectId,
+  #[schema(expires)]
+  #[serde(rename = "expiresAt")]
+  pub expires_at: DateTime,
+}
+
+// Its TTL index is created by `auto_index`
+#[derive(Clone, Debug, Deserialize, PartialEq, Schema, Serialize)]
+#[schema(name = "tokens_expires")]
+struct Token {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  #[schema(expires_after = "1h")]
+  #[serde(rename = "expiresAt")]
+  pub expires_at: DateTime,
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Session {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Session {}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for ShortSession {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for ShortSession {}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Token {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Token {}
+
+fn ttl_index(indexes: &[IndexModel]) -> Option<Duration> {
+  indexes
+    .iter()
+    .find(|index| index.keys == doc! { "expiresAt": 1 })
+    .and_then(|index| index.options.as_ref())
+    .and_then(|options| options.expire_after)
+}
+
+// The blocking test uses another database, so the TTL index is not modified by both tests at the same time.
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> blocking::Database {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  client.database("nongoose_blocking")
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> Database {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match Client::with_uri_str(&url).await {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  client.database("nongoose")
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn expires_blocking() {
+  let database = get_blocking_instance();
+  let collection = database.collection::<Document>("sessions_expires");
+
+  let list_indexes = || {
+    collection
+      .list_indexes(None)
+      .unwrap()
+      .collect::<Result<Vec<IndexModel>, _>>()
+      .unwrap()
+  };
+
+  // The TTL index is declared in the Schema
+  let builder = blocking::Nongoose::builder(database.clone()).add_schema::<Session>();
+  assert_eq!(
+    ttl_index(&builder.schemas[0].get_indexes()),
+    Some(Duration::from_secs(5400))
+  );
+
+  assert!(builder.sync_indexes(false).is_ok());
+  assert_eq!(ttl_index(&list_indexes()), Some(Duration::from_secs(5400)));
+
+  // The time to live is updated
+  let nongoose = blocking::Nongoose::builder(database.clone())
+    .add_schema::<ShortSession>()
+    .build();
+  assert!(nongoose.ensure_indexes::<ShortSession>().is_ok());
+
+  let indexes = list_indexes();
+  assert_eq!(ttl_index(&indexes), Some(Duration::from_secs(60)));
+  assert_eq!(indexes.len(), 2);
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn expires() {
+  use futures::TryStreamExt;
+
+  let database = get_instance().await;
+  let collection = database.collection::<Document>("sessions_expires");
+
+  // The TTL index is declared in the Schema
+  let builder = Nongoose::builder(database.clone()).add_schema::<Session>();
+  assert_eq!(
+    ttl_index(&builder.schemas[0].get_indexes()),
+    Some(Duration::from_secs(5400))
+  );
+
+  assert!(builder.sync_indexes(false).await.is_ok());
+
+  let indexes = collection
+    .list_indexes(None)
+    .await
+    .unwrap()
+    .try_collect::<Vec<IndexModel>>()
+    .await
+    .unwrap();
+  assert_eq!(ttl_index(&indexes), Some(Duration::from_secs(5400)));
+
+  // The time to live is updated
+  let nongoose = Nongoose::builder(database.clone())
+    .add_schema::<ShortSession>()
+    .build();
+  assert!(nongoose.ensure_indexes::<ShortSession>().await.is_ok());
+
+  let indexes = collection
+    .list_indexes(None)
+    .await
+    .unwrap()
+    .try_collect::<Vec<IndexModel>>()
+    .await
+    .unwrap();
+  assert_eq!(ttl_index(&indexes), Some(Duration::from_secs(60)));
+  assert_eq!(indexes.len(), 2);
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn expires_auto_index_blocking() {
+  let database = get_blocking_instance();
+  let nongoose = blocking::Nongoose::builder(database.clone())
+    .add_schema::<Token>()
+    .auto_index(true)
+    .build();
+
+  // The first operation that uses the Schema creates the TTL index (`auto_index`)
+  let token = Token {
+    id: ObjectId::new(),
+    expires_at: DateTime::now(),
+  };
+  assert!(nongoose.create(&token).is_ok());
+
+  let indexes = database
+    .collection::<Document>("tokens_expires")
+    .list_indexes(None)
+    .unwrap()
+    .collect::<Result<Vec<IndexModel>, _>>()
+    .unwrap();
+  assert_eq!(ttl_index(&indexes), Some(Duration::from_secs(3600)));
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn expires_auto_index() {
+  use futures::TryStreamExt;
+
+  let database = get_instance().await;
+  let nongoose = Nongoose::builder(database.clone())
+    .add_schema::<Token>()
+    .auto_index(true)
+    .build();
+
+  // The first operation that uses the Schema creates the TTL index (`auto_index`)
+  let token = Token {
+    id: ObjectId::new(),
+    expires_at: DateTime::now(),
+  };
+  assert!(nongoose.create(&token).await.is_ok());
+
+  let indexes = database
+    .collection::<Document>("tokens_expires")
+    .list_indexes(None)
+    .await
+    .unwrap()
+    .try_collect::<Vec<IndexModel>>()
+    .await
+    .unwrap();
+  assert_eq!(ttl_index(&indexes), Some(Duration::from_secs(3600)));
+}
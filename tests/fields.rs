@@ -0,0 +1,70 @@
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+#[cfg(feature = "tokio-runtime")]
+use nongoose::SchemaBefore;
+use nongoose::{
+  bson::{doc, oid::ObjectId},
+  schema_relations, Schema,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+struct User {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  #[serde(rename = "name")]
+  pub username: String,
+  pub age: i64,
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for User {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for User {}
+
+#[schema_relations]
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BlogPost {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+
+  #[schema(many_to_one = "User")]
+  #[serde(skip_serializing)]
+  pub author: Option<User>,
+
+  pub post_title: String,
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for BlogPost {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for BlogPost {}
+
+#[test]
+fn fields() {
+  let fields = User::fields();
+  assert_eq!(fields.id.path(), "_id");
+  assert_eq!(fields.username.path(), "name");
+  assert_eq!(fields.age.path(), "age");
+
+  assert_eq!(fields.username.eq("nongoose"), doc! { "name": "nongoose" });
+  assert_eq!(fields.age.gte(18), doc! { "age": { "$gte": 18 } });
+  assert_eq!(
+    fields.age.is_in([18, 21]),
+    doc! { "age": { "$in": [18, 21] } }
+  );
+  assert_eq!(fields.age.desc(), doc! { "age": -1 });
+  assert_eq!(fields.username.exclude(), doc! { "name": 0 });
+
+  let fields = BlogPost::fields();
+  assert_eq!(fields.id.path(), "_id");
+  assert_eq!(fields.author_id.path(), "authorId");
+  assert_eq!(fields.post_title.path(), "postTitle");
+}
@@ -1,10 +1,15 @@
+#[cfg(feature = "sync")]
+use nongoose::blocking;
 use nongoose::{
   bson::{doc, oid::ObjectId, Regex},
   options::FindOptions,
-  Client, Nongoose, Schema, SchemaBefore,
+  Schema,
 };
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, Nongoose, SchemaBefore};
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "tokio-runtime")]
 #[derive(Clone, Debug, Deserialize, Schema, Serialize)]
 struct Actor {
   #[schema(id)]
@@ -14,6 +19,17 @@ struct Actor {
   pub age: u64,
 }
 
+#[cfg(feature = "sync")]
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+struct BlockingActor {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub fullname: String,
+  pub age: u64,
+}
+
+#[cfg(feature = "tokio-runtime")]
 impl Actor {
   pub fn new(fullname: &str, age: u64) -> Self {
     Self {
@@ -24,11 +40,49 @@ impl Actor {
   }
 }
 
-#[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
+#[cfg(feature = "sync")]
+impl BlockingActor {
+  pub fn new(fullname: &str, age: u64) -> Self {
+    Self {
+      id: ObjectId::new(),
+      fullname: String::from(fullname),
+      age,
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for BlockingActor {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
 impl SchemaBefore for Actor {}
 
-#[cfg(test)]
-fn get_instance() -> Nongoose {
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> blocking::Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  blocking::Nongoose::builder(client.database("nongoose"))
+    .add_schema::<BlockingActor>()
+    .build()
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> Nongoose {
   // Get database url.
   let url = match std::env::var("DATABASE_URL") {
     Ok(url) => url,
@@ -38,7 +92,7 @@ fn get_instance() -> Nongoose {
   };
 
   // Get MongoDB connection.
-  let client = match Client::with_uri_str(&url) {
+  let client = match Client::with_uri_str(&url).await {
     Ok(client) => client,
     Err(e) => {
       panic!("Error connecting to the database: {}", e);
@@ -52,25 +106,25 @@ fn get_instance() -> Nongoose {
 
 #[cfg(feature = "sync")]
 #[cfg_attr(feature = "sync", test)]
-fn find() {
-  let nongoose = get_instance();
+fn find_blocking() {
+  let nongoose = get_blocking_instance();
 
-  let tom_hanks = Actor::new("Tom Hanks", 65).save();
+  let tom_hanks = nongoose.create(&BlockingActor::new("Tom Hanks", 65));
   assert!(tom_hanks.is_ok());
 
-  let will_smith = Actor::new("Will Smith", 53).save();
+  let will_smith = nongoose.create(&BlockingActor::new("Will Smith", 53));
   assert!(will_smith.is_ok());
 
-  let leonardo_dicaprio = Actor::new("Leonardo DiCaprio", 46).save();
+  let leonardo_dicaprio = nongoose.create(&BlockingActor::new("Leonardo DiCaprio", 46));
   assert!(leonardo_dicaprio.is_ok());
 
-  let jeniffer_lopez = Actor::new("Jeniffer Lopez", 52).save();
+  let jeniffer_lopez = nongoose.create(&BlockingActor::new("Jeniffer Lopez", 52));
   assert!(jeniffer_lopez.is_ok());
 
-  let tom_cruise = Actor::new("Tom Cruise", 59).save();
+  let tom_cruise = nongoose.create(&BlockingActor::new("Tom Cruise", 59));
   assert!(tom_cruise.is_ok());
 
-  let emma_stone = Actor::new("Emma Stone", 32).save();
+  let emma_stone = nongoose.create(&BlockingActor::new("Emma Stone", 32));
   assert!(emma_stone.is_ok());
 
   // Unwrap actors
@@ -82,7 +136,7 @@ fn find() {
   let emma_stone = emma_stone.unwrap();
 
   // Find by id
-  let by_id = nongoose.find_by_id::<Actor>(&emma_stone.id);
+  let by_id = nongoose.find_by_id::<BlockingActor>(&emma_stone.id);
   assert!(by_id.is_ok());
 
   let by_id = by_id.unwrap();
@@ -92,7 +146,7 @@ fn find() {
   assert_eq!(by_id.fullname, emma_stone.fullname);
 
   // Find one by age
-  let age_32 = nongoose.find_one::<Actor>(doc! { "age": 32 }, None);
+  let age_32 = nongoose.find_one::<BlockingActor>(doc! { "age": 32 }, None);
   assert!(age_32.is_ok());
 
   let age_32 = age_32.unwrap();
@@ -102,7 +156,8 @@ fn find() {
   assert_eq!(age_32.fullname, emma_stone.fullname);
 
   // Find actors between 40 and 49 years old
-  let actors_40s_age = nongoose.find::<Actor>(doc! { "age": { "$gte": 40, "$lte": 49 } }, None);
+  let actors_40s_age =
+    nongoose.find::<BlockingActor>(doc! { "age": { "$gte": 40, "$lte": 49 } }, None);
   assert!(actors_40s_age.is_ok());
 
   let actors_40s_age = actors_40s_age.unwrap();
@@ -110,7 +165,7 @@ fn find() {
   assert_eq!(actors_40s_age[0].fullname, leonardo_dicaprio.fullname);
 
   // Find actors between 50 and 59 years old
-  let actors_50s_age = nongoose.find::<Actor>(
+  let actors_50s_age = nongoose.find::<BlockingActor>(
     doc! { "age": { "$gte": 50, "$lte": 59 }},
     Some(FindOptions::builder().sort(doc! { "age": 1 }).build()),
   );
@@ -123,7 +178,7 @@ fn find() {
   assert_eq!(actors_50s_age[2].fullname, tom_cruise.fullname);
 
   // Find actors with the word "Tom" in their name
-  let tom_actors = nongoose.find::<Actor>(
+  let tom_actors = nongoose.find::<BlockingActor>(
     doc! { "fullname": Regex { pattern: String::from("^Tom"), options: String::new() } },
     Some(FindOptions::builder().sort(doc! { "age": -1 }).build()),
   );
@@ -138,24 +193,24 @@ fn find() {
 #[cfg(feature = "tokio-runtime")]
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 async fn find() {
-  let nongoose = get_instance();
+  let nongoose = get_instance().await;
 
-  let tom_hanks = Actor::new("Tom Hanks", 65).save().await;
+  let tom_hanks = nongoose.create(&Actor::new("Tom Hanks", 65)).await;
   assert!(tom_hanks.is_ok());
 
-  let will_smith = Actor::new("Will Smith", 53).save().await;
+  let will_smith = nongoose.create(&Actor::new("Will Smith", 53)).await;
   assert!(will_smith.is_ok());
 
-  let leonardo_dicaprio = Actor::new("Leonardo DiCaprio", 46).save().await;
+  let leonardo_dicaprio = nongoose.create(&Actor::new("Leonardo DiCaprio", 46)).await;
   assert!(leonardo_dicaprio.is_ok());
 
-  let jeniffer_lopez = Actor::new("Jeniffer Lopez", 52).save().await;
+  let jeniffer_lopez = nongoose.create(&Actor::new("Jeniffer Lopez", 52)).await;
   assert!(jeniffer_lopez.is_ok());
 
-  let tom_cruise = Actor::new("Tom Cruise", 59).save().await;
+  let tom_cruise = nongoose.create(&Actor::new("Tom Cruise", 59)).await;
   assert!(tom_cruise.is_ok());
 
-  let emma_stone = Actor::new("Emma Stone", 32).save().await;
+  let emma_stone = nongoose.create(&Actor::new("Emma Stone", 32)).await;
   assert!(emma_stone.is_ok());
 
   // Unwrap actors
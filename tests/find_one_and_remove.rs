@@ -0,0 +1,327 @@
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{
+  bson::{doc, oid::ObjectId},
+  options::FindOneAndDeleteOptions,
+  Schema,
+};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, HookContext, Nongoose, SchemaBefore};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "tokio-runtime")]
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "actors_find_one_and_remove")]
+struct Actor {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub fullname: String,
+  pub age: u64,
+}
+
+#[cfg(feature = "sync")]
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "actors_find_one_and_remove_blocking")]
+struct BlockingActor {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub fullname: String,
+  pub age: u64,
+}
+
+#[cfg(feature = "tokio-runtime")]
+impl Actor {
+  pub fn new(fullname: &str, age: u64) -> Self {
+    Self {
+      id: ObjectId::new(),
+      fullname: String::from(fullname),
+      age,
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl BlockingActor {
+  pub fn new(fullname: &str, age: u64) -> Self {
+    Self {
+      id: ObjectId::new(),
+      fullname: String::from(fullname),
+      age,
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for BlockingActor {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Actor {}
+
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "tickets_find_one_and_remove")]
+struct Ticket {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub attempts: u64,
+}
+
+impl Ticket {
+  pub fn new() -> Self {
+    Self {
+      id: ObjectId::new(),
+      attempts: 0,
+    }
+  }
+}
+
+// The document is modified in the database before removing it.
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Ticket {
+  fn before_delete(&mut self, ctx: &mut blocking::HookContext<'_>) -> nongoose::Result<bool> {
+    ctx.nongoose().update_many::<Ticket>(
+      doc! { "_id": self.id },
+      doc! { "$inc": { "attempts": 1 } },
+      None,
+    )?;
+
+    Ok(true)
+  }
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Ticket {
+  async fn before_delete(&mut self, ctx: &mut HookContext<'_>) -> nongoose::Result<bool> {
+    ctx
+      .nongoose()
+      .update_many::<Ticket>(
+        doc! { "_id": self.id },
+        doc! { "$inc": { "attempts": 1 } },
+        None,
+      )
+      .await?;
+
+    Ok(true)
+  }
+}
+
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> blocking::Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  blocking::Nongoose::builder(client.database("nongoose"))
+    .add_schema::<BlockingActor>()
+    .add_schema::<Ticket>()
+    .build()
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match Client::with_uri_str(&url).await {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  Nongoose::builder(client.database("nongoose"))
+    .add_schema::<Actor>()
+    .add_schema::<Ticket>()
+    .build()
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn find_one_and_remove_blocking() {
+  let nongoose = get_blocking_instance();
+
+  let actors = [
+    BlockingActor::new("Emma Stone", 32),
+    BlockingActor::new("Leonardo DiCaprio", 46),
+    BlockingActor::new("Tom Hanks", 65),
+  ];
+
+  for actor in actors.iter() {
+    assert!(nongoose.create(actor).is_ok());
+  }
+
+  // The returned document is the removed one (the oldest actor)
+  let oldest = nongoose.find_one_and_remove::<BlockingActor>(
+    doc! { "age": { "$gte": 30 } },
+    Some(
+      FindOneAndDeleteOptions::builder()
+        .sort(doc! { "age": -1 })
+        .build(),
+    ),
+  );
+  assert!(oldest.is_ok());
+
+  let (removed, oldest) = oldest.unwrap();
+  assert!(removed);
+  assert!(oldest.is_some());
+  assert_eq!(oldest.unwrap().id, actors[2].id);
+
+  let count = nongoose.count::<BlockingActor>(doc! {}, None);
+  assert!(count.is_ok());
+  assert_eq!(count.unwrap(), 2);
+
+  // Nothing to remove
+  let result = nongoose.find_by_id_and_remove::<BlockingActor>(&actors[2].id);
+  assert!(result.is_ok());
+
+  let (removed, result) = result.unwrap();
+  assert!(!removed);
+  assert!(result.is_none());
+
+  let result = nongoose.find_by_id_and_remove::<BlockingActor>(&actors[0].id);
+  assert!(result.is_ok());
+  assert!(result.unwrap().0);
+
+  let result = nongoose.find_by_id_and_remove::<BlockingActor>(&actors[1].id);
+  assert!(result.is_ok());
+  assert!(result.unwrap().0);
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn find_one_and_remove() {
+  let nongoose = get_instance().await;
+
+  let actors = [
+    Actor::new("Emma Stone", 32),
+    Actor::new("Leonardo DiCaprio", 46),
+    Actor::new("Tom Hanks", 65),
+  ];
+
+  for actor in actors.iter() {
+    assert!(nongoose.create(actor).await.is_ok());
+  }
+
+  // The returned document is the removed one (the oldest actor)
+  let oldest = nongoose
+    .find_one_and_remove::<Actor>(
+      doc! { "age": { "$gte": 30 } },
+      Some(
+        FindOneAndDeleteOptions::builder()
+          .sort(doc! { "age": -1 })
+          .build(),
+      ),
+    )
+    .await;
+  assert!(oldest.is_ok());
+
+  let (removed, oldest) = oldest.unwrap();
+  assert!(removed);
+  assert!(oldest.is_some());
+  assert_eq!(oldest.unwrap().id, actors[2].id);
+
+  let count = nongoose.count::<Actor>(doc! {}, None).await;
+  assert!(count.is_ok());
+  assert_eq!(count.unwrap(), 2);
+
+  // Nothing to remove
+  let result = nongoose.find_by_id_and_remove::<Actor>(&actors[2].id).await;
+  assert!(result.is_ok());
+
+  let (removed, result) = result.unwrap();
+  assert!(!removed);
+  assert!(result.is_none());
+
+  let result = nongoose.find_by_id_and_remove::<Actor>(&actors[0].id).await;
+  assert!(result.is_ok());
+  assert!(result.unwrap().0);
+
+  let result = nongoose.find_by_id_and_remove::<Actor>(&actors[1].id).await;
+  assert!(result.is_ok());
+  assert!(result.unwrap().0);
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn find_one_and_remove_modified_blocking() {
+  let nongoose = get_blocking_instance();
+
+  let ticket = Ticket::new();
+  assert!(nongoose.create(&ticket).is_ok());
+
+  // The document does not match the conditions anymore, so it is not removed
+  let result =
+    nongoose.find_one_and_remove::<Ticket>(doc! { "_id": ticket.id, "attempts": 0 }, None);
+  assert!(result.is_ok());
+
+  let (removed, result) = result.unwrap();
+  assert!(!removed);
+  assert_eq!(result.unwrap().attempts, 0);
+
+  let count = nongoose.count::<Ticket>(doc! { "_id": ticket.id }, None);
+  assert!(count.is_ok());
+  assert_eq!(count.unwrap(), 1);
+
+  // The returned document is the one removed by the server
+  let result = nongoose.find_by_id_and_remove::<Ticket>(&ticket.id);
+  assert!(result.is_ok());
+
+  let (removed, result) = result.unwrap();
+  assert!(removed);
+  assert_eq!(result.unwrap().attempts, 2);
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn find_one_and_remove_modified() {
+  let nongoose = get_instance().await;
+
+  let ticket = Ticket::new();
+  assert!(nongoose.create(&ticket).await.is_ok());
+
+  // The document does not match the conditions anymore, so it is not removed
+  let result = nongoose
+    .find_one_and_remove::<Ticket>(doc! { "_id": ticket.id, "attempts": 0 }, None)
+    .await;
+  assert!(result.is_ok());
+
+  let (removed, result) = result.unwrap();
+  assert!(!removed);
+  assert_eq!(result.unwrap().attempts, 0);
+
+  let count = nongoose
+    .count::<Ticket>(doc! { "_id": ticket.id }, None)
+    .await;
+  assert!(count.is_ok());
+  assert_eq!(count.unwrap(), 1);
+
+  // The returned document is the one removed by the server
+  let result = nongoose.find_by_id_and_remove::<Ticket>(&ticket.id).await;
+  assert!(result.is_ok());
+
+  let (removed, result) = result.unwrap();
+  assert!(removed);
+  assert_eq!(result.unwrap().attempts, 2);
+}
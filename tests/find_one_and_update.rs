@@ -0,0 +1,250 @@
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{
+  bson::{doc, oid::ObjectId, Document},
+  options::{FindOneAndUpdateOptions, ReturnDocument},
+  Error, Schema,
+};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, HookContext, Nongoose, SchemaBefore};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "actors_find_one_and_update")]
+struct Actor {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub fullname: String,
+  pub age: u64,
+  #[serde(default)]
+  pub updates: u64,
+}
+
+impl Actor {
+  pub fn new(fullname: &str, age: u64) -> Self {
+    Self {
+      id: ObjectId::new(),
+      fullname: String::from(fullname),
+      age,
+      updates: 0,
+    }
+  }
+
+  fn check_update(update: &mut Document) -> nongoose::Result<()> {
+    if update.contains_key("$unset") {
+      return Err(Error::NoImplemented);
+    }
+
+    update.insert("$inc", doc! { "updates": 1 });
+    Ok(())
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Actor {
+  fn before_find_one_and_update(
+    _conditions: &Document,
+    update: &mut Document,
+    _ctx: &mut blocking::HookContext<'_>,
+  ) -> nongoose::Result<()> {
+    Actor::check_update(update)
+  }
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Actor {
+  async fn before_find_one_and_update(
+    _conditions: &Document,
+    update: &mut Document,
+    _ctx: &mut HookContext<'_>,
+  ) -> nongoose::Result<()> {
+    Actor::check_update(update)
+  }
+}
+
+fn return_after() -> Option<FindOneAndUpdateOptions> {
+  Some(
+    FindOneAndUpdateOptions::builder()
+      .return_document(ReturnDocument::After)
+      .build(),
+  )
+}
+
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> blocking::Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  blocking::Nongoose::builder(client.database("nongoose_blocking"))
+    .add_schema::<Actor>()
+    .build()
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match Client::with_uri_str(&url).await {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  Nongoose::builder(client.database("nongoose"))
+    .add_schema::<Actor>()
+    .build()
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn find_one_and_update_blocking() {
+  let nongoose = get_blocking_instance();
+
+  let actor = nongoose.create(&Actor::new("Tom Hanks", 65));
+  assert!(actor.is_ok());
+
+  let actor = actor.unwrap();
+
+  // Returns the document before the update by default
+  let before =
+    nongoose.find_by_id_and_update::<Actor>(&actor.id, doc! { "$set": { "age": 66 } }, None);
+  assert!(before.is_ok());
+
+  let before = before.unwrap();
+  assert!(before.is_some());
+  assert_eq!(before.unwrap().age, 65);
+
+  // Returns the document after the update (with the update of the hook)
+  let after = nongoose.find_one_and_update::<Actor>(
+    doc! { "fullname": "Tom Hanks" },
+    doc! { "$set": { "age": 67 } },
+    return_after(),
+  );
+  assert!(after.is_ok());
+
+  let after = after.unwrap();
+  assert!(after.is_some());
+
+  let after = after.unwrap();
+  assert_eq!(after.age, 67);
+  assert_eq!(after.updates, 2);
+
+  // The hook rejects the update
+  let rejected =
+    nongoose.find_by_id_and_update::<Actor>(&actor.id, doc! { "$unset": { "age": "" } }, None);
+  assert!(matches!(rejected, Err(Error::NoImplemented)));
+
+  // Upsert
+  let id = ObjectId::new();
+  let upserted = nongoose.find_by_id_and_update::<Actor>(
+    &id,
+    doc! { "$set": { "fullname": "Emma Stone", "age": 32 } },
+    Some(
+      FindOneAndUpdateOptions::builder()
+        .upsert(true)
+        .return_document(ReturnDocument::After)
+        .build(),
+    ),
+  );
+  assert!(upserted.is_ok());
+
+  let upserted = upserted.unwrap();
+  assert!(upserted.is_some());
+  assert_eq!(upserted.unwrap().fullname, "Emma Stone");
+
+  assert!(nongoose.find_by_id_and_remove::<Actor>(&actor.id).is_ok());
+  assert!(nongoose.find_by_id_and_remove::<Actor>(&id).is_ok());
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn find_one_and_update() {
+  let nongoose = get_instance().await;
+
+  let actor = nongoose.create(&Actor::new("Tom Hanks", 65)).await;
+  assert!(actor.is_ok());
+
+  let actor = actor.unwrap();
+
+  // Returns the document before the update by default
+  let before = nongoose
+    .find_by_id_and_update::<Actor>(&actor.id, doc! { "$set": { "age": 66 } }, None)
+    .await;
+  assert!(before.is_ok());
+
+  let before = before.unwrap();
+  assert!(before.is_some());
+  assert_eq!(before.unwrap().age, 65);
+
+  // Returns the document after the update (with the update of the hook)
+  let after = nongoose
+    .find_one_and_update::<Actor>(
+      doc! { "fullname": "Tom Hanks" },
+      doc! { "$set": { "age": 67 } },
+      return_after(),
+    )
+    .await;
+  assert!(after.is_ok());
+
+  let after = after.unwrap();
+  assert!(after.is_some());
+
+  let after = after.unwrap();
+  assert_eq!(after.age, 67);
+  assert_eq!(after.updates, 2);
+
+  // The hook rejects the update
+  let rejected = nongoose
+    .find_by_id_and_update::<Actor>(&actor.id, doc! { "$unset": { "age": "" } }, None)
+    .await;
+  assert!(matches!(rejected, Err(Error::NoImplemented)));
+
+  // Upsert
+  let id = ObjectId::new();
+  let upserted = nongoose
+    .find_by_id_and_update::<Actor>(
+      &id,
+      doc! { "$set": { "fullname": "Emma Stone", "age": 32 } },
+      Some(
+        FindOneAndUpdateOptions::builder()
+          .upsert(true)
+          .return_document(ReturnDocument::After)
+          .build(),
+      ),
+    )
+    .await;
+  assert!(upserted.is_ok());
+
+  let upserted = upserted.unwrap();
+  assert!(upserted.is_some());
+  assert_eq!(upserted.unwrap().fullname, "Emma Stone");
+
+  assert!(nongoose
+    .find_by_id_and_remove::<Actor>(&actor.id)
+    .await
+    .is_ok());
+  assert!(nongoose.find_by_id_and_remove::<Actor>(&id).await.is_ok());
+}
@@ -0,0 +1,302 @@
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{
+  bson::{doc, oid::ObjectId},
+  HookOperation, Schema,
+};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, HookContext, Nongoose, SchemaAfter, SchemaBefore};
+use serde::{Deserialize, Serialize};
+
+// Request-scoped data passed with `Nongoose.with_extension()`
+struct CurrentUser(ObjectId);
+
+// Data passed from the before functions to the after functions
+struct Saved(HookOperation);
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Schema, Serialize)]
+#[schema(name = "notes_hook_context", after_hooks)]
+struct Note {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub content: String,
+  pub updated_by: Option<ObjectId>,
+  #[serde(skip)]
+  pub saved: Option<HookOperation>,
+  #[serde(skip)]
+  pub stored: u64,
+}
+
+impl Note {
+  pub fn new(content: &str) -> Self {
+    Self {
+      id: ObjectId::new(),
+      content: String::from(content),
+      updated_by: None,
+      saved: None,
+      stored: 0,
+    }
+  }
+}
+
+// Created by the after functions of `Note`, with a nested operation
+#[derive(Clone, Debug, Deserialize, PartialEq, Schema, Serialize)]
+#[schema(name = "revisions_hook_context")]
+struct Revision {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub note_id: ObjectId,
+  pub author: Option<ObjectId>,
+}
+
+impl Revision {
+  pub fn new(note_id: ObjectId) -> Self {
+    Self {
+      id: ObjectId::new(),
+      note_id,
+      author: None,
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Revision {
+  fn before_create(&mut self, ctx: &mut blocking::HookContext<'_>) -> nongoose::Result<()> {
+    self.author = ctx
+      .extensions()
+      .get::<CurrentUser>()
+      .map(|CurrentUser(id)| *id);
+
+    Ok(())
+  }
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Revision {
+  async fn before_create(&mut self, ctx: &mut HookContext<'_>) -> nongoose::Result<()> {
+    self.author = ctx
+      .extensions()
+      .get::<CurrentUser>()
+      .map(|CurrentUser(id)| *id);
+
+    Ok(())
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Note {
+  fn before_create(&mut self, ctx: &mut blocking::HookContext<'_>) -> nongoose::Result<()> {
+    blocking::SchemaBefore::before_update(self, ctx)
+  }
+
+  fn before_update(&mut self, ctx: &mut blocking::HookContext<'_>) -> nongoose::Result<()> {
+    self.updated_by = ctx
+      .extensions()
+      .get::<CurrentUser>()
+      .map(|CurrentUser(id)| *id);
+
+    let operation = ctx.operation();
+    ctx.extensions_mut().insert(Saved(operation));
+
+    Ok(())
+  }
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Note {
+  async fn before_create(&mut self, ctx: &mut HookContext<'_>) -> nongoose::Result<()> {
+    SchemaBefore::before_update(self, ctx).await
+  }
+
+  async fn before_update(&mut self, ctx: &mut HookContext<'_>) -> nongoose::Result<()> {
+    self.updated_by = ctx
+      .extensions()
+      .get::<CurrentUser>()
+      .map(|CurrentUser(id)| *id);
+
+    let operation = ctx.operation();
+    ctx.extensions_mut().insert(Saved(operation));
+
+    Ok(())
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaAfter for Note {
+  fn after_create(&mut self, ctx: &mut blocking::HookContext<'_>) -> nongoose::Result<()> {
+    blocking::SchemaAfter::after_update(self, ctx)
+  }
+
+  fn after_update(&mut self, ctx: &mut blocking::HookContext<'_>) -> nongoose::Result<()> {
+    self.saved = ctx
+      .extensions()
+      .get::<Saved>()
+      .map(|Saved(operation)| *operation);
+
+    self.stored = ctx
+      .nongoose()
+      .count::<Note>(doc! { "_id": self.id }, None)?;
+
+    ctx.nongoose().create(&Revision::new(self.id))?;
+
+    Ok(())
+  }
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaAfter for Note {
+  async fn after_create(&mut self, ctx: &mut HookContext<'_>) -> nongoose::Result<()> {
+    SchemaAfter::after_update(self, ctx).await
+  }
+
+  async fn after_update(&mut self, ctx: &mut HookContext<'_>) -> nongoose::Result<()> {
+    self.saved = ctx
+      .extensions()
+      .get::<Saved>()
+      .map(|Saved(operation)| *operation);
+
+    self.stored = ctx
+      .nongoose()
+      .count::<Note>(doc! { "_id": self.id }, None)
+      .await?;
+
+    ctx.nongoose().create(&Revision::new(self.id)).await?;
+
+    Ok(())
+  }
+}
+
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> blocking::Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  blocking::Nongoose::builder(client.database("nongoose_blocking"))
+    .add_schema::<Note>()
+    .add_schema::<Revision>()
+    .build()
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match Client::with_uri_str(&url).await {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  Nongoose::builder(client.database("nongoose"))
+    .add_schema::<Note>()
+    .add_schema::<Revision>()
+    .build()
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn hook_context_blocking() {
+  let nongoose = get_blocking_instance();
+
+  // Without extensions
+  let mut note = Note::new("Buy milk");
+
+  let note_saved = nongoose.save(&mut note);
+  assert!(note_saved.is_ok());
+
+  let note_saved = note_saved.unwrap();
+  assert_eq!(note_saved.updated_by, None);
+  assert_eq!(note_saved.saved, Some(HookOperation::Create));
+  assert_eq!(note_saved.stored, 1);
+
+  // With the current user
+  let user_id = ObjectId::new();
+  let user_nongoose = nongoose.with_extension(CurrentUser(user_id));
+
+  let note_saved = user_nongoose.save(&mut note);
+  assert!(note_saved.is_ok());
+
+  let note_saved = note_saved.unwrap();
+  assert_eq!(note_saved.updated_by, Some(user_id));
+  assert_eq!(note_saved.saved, Some(HookOperation::Update));
+
+  let note_found = nongoose.find_by_id::<Note>(&note.id);
+  assert!(note_found.is_ok());
+
+  let note_found = note_found.unwrap();
+  assert!(note_found.is_some());
+  assert_eq!(note_found.unwrap().updated_by, Some(user_id));
+
+  // The nested operations of the hooks receive the extensions
+  let revisions = nongoose.count::<Revision>(doc! { "note_id": note.id, "author": user_id }, None);
+  assert!(revisions.is_ok());
+  assert_eq!(revisions.unwrap(), 1);
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn hook_context() {
+  let nongoose = get_instance().await;
+
+  // Without extensions
+  let mut note = Note::new("Buy milk");
+
+  let note_saved = nongoose.save(&mut note).await;
+  assert!(note_saved.is_ok());
+
+  let note_saved = note_saved.unwrap();
+  assert_eq!(note_saved.updated_by, None);
+  assert_eq!(note_saved.saved, Some(HookOperation::Create));
+  assert_eq!(note_saved.stored, 1);
+
+  // With the current user
+  let user_id = ObjectId::new();
+  let user_nongoose = nongoose.with_extension(CurrentUser(user_id));
+
+  let note_saved = user_nongoose.save(&mut note).await;
+  assert!(note_saved.is_ok());
+
+  let note_saved = note_saved.unwrap();
+  assert_eq!(note_saved.updated_by, Some(user_id));
+  assert_eq!(note_saved.saved, Some(HookOperation::Update));
+
+  let note_found = nongoose.find_by_id::<Note>(&note.id).await;
+  assert!(note_found.is_ok());
+
+  let note_found = note_found.unwrap();
+  assert!(note_found.is_some());
+  assert_eq!(note_found.unwrap().updated_by, Some(user_id));
+
+  // The nested operations of the hooks receive the extensions
+  let revisions = nongoose
+    .count::<Revision>(doc! { "note_id": note.id, "author": user_id }, None)
+    .await;
+  assert!(revisions.is_ok());
+  assert_eq!(revisions.unwrap(), 1);
+}
@@ -0,0 +1,359 @@
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{
+  bson::{doc, oid::ObjectId, Document},
+  options::IndexOptions,
+  IndexModel, Schema,
+};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, Database, Nongoose, SchemaBefore};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Schema, Serialize)]
+#[schema(
+  name = "products_indexes",
+  index(fields = "category,-price", name = "category_price"),
+  index(fields = "sku", unique, sparse),
+  index(fields = "category:text", partial = "filters::in_stock")
+)]
+struct Product {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  #[schema(index)]
+  pub name: String,
+  pub category: String,
+  pub price: f64,
+  pub sku: Option<String>,
+  pub stock: i32,
+  #[schema(index = "desc")]
+  #[serde(rename = "createdAt")]
+  pub created_at: i64,
+}
+
+// Its unique index cannot be created while the collection has duplicated names
+#[derive(Clone, Debug, Deserialize, PartialEq, Schema, Serialize)]
+#[schema(name = "tags_indexes")]
+struct Tag {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  #[schema(unique)]
+  pub name: String,
+}
+
+// Its text index already exists in the collection with another name
+#[derive(Clone, Debug, Deserialize, PartialEq, Schema, Serialize)]
+#[schema(name = "articles_indexes", index(fields = "title:text"))]
+struct Article {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub title: String,
+}
+
+mod filters {
+  use nongoose::bson::{doc, Document};
+
+  pub fn in_stock() -> Document {
+    doc! { "stock": { "$gt": 0 } }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Product {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Product {}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Tag {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Tag {}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Article {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Article {}
+
+fn index_keys(indexes: Vec<IndexModel>) -> Vec<Document> {
+  indexes.into_iter().map(|index| index.keys).collect()
+}
+
+fn declared_keys() -> Vec<Document> {
+  vec![
+    doc! { "name": 1 },
+    doc! { "createdAt": -1 },
+    doc! { "category": 1, "price": -1 },
+    doc! { "sku": 1 },
+    doc! { "category": "text" },
+  ]
+}
+
+// The blocking test uses another database, so the indexes dropped by one test are not dropped while the other
+// one is running.
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> blocking::Database {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  client.database("nongoose_blocking")
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> Database {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match Client::with_uri_str(&url).await {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  client.database("nongoose")
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn indexes_blocking() {
+  let database = get_blocking_instance();
+  let builder = blocking::Nongoose::builder(database.clone()).add_schema::<Product>();
+
+  // The indexes are declared in the Schema
+  let indexes = builder.schemas[0].get_indexes();
+  assert_eq!(index_keys(indexes.clone()), declared_keys());
+
+  let options = indexes[3].options.clone().unwrap();
+  assert_eq!(options.unique, Some(true));
+  assert_eq!(options.sparse, Some(true));
+
+  let options = indexes[4].options.clone().unwrap();
+  assert_eq!(options.partial_filter_expression, Some(filters::in_stock()));
+
+  // Create the missing indexes, keeping the undeclared ones
+  let collection = database.collection::<Document>("products_indexes");
+  let undeclared = IndexModel::builder().keys(doc! { "stock": 1 }).build();
+  assert!(collection.create_index(undeclared, None).is_ok());
+
+  assert!(builder.sync_indexes(false).is_ok());
+
+  let names = collection.list_index_names();
+  assert!(names.is_ok());
+
+  let names = names.unwrap();
+  assert!(names.contains(&String::from("category_price")));
+  assert!(names.contains(&String::from("stock_1")));
+
+  // Nothing to create
+  let nongoose = builder.build();
+  assert!(nongoose.ensure_indexes::<Product>().is_ok());
+
+  // Drop the undeclared indexes
+  assert!(builder.sync_indexes(true).is_ok());
+
+  let indexes = collection.list_indexes(None);
+  assert!(indexes.is_ok());
+
+  let indexes = indexes
+    .unwrap()
+    .collect::<Result<Vec<IndexModel>, _>>()
+    .unwrap();
+  assert_eq!(indexes.len(), declared_keys().len() + 1);
+  assert!(!index_keys(indexes).contains(&doc! { "stock": 1 }));
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn indexes() {
+  use futures::TryStreamExt;
+
+  let database = get_instance().await;
+  let builder = Nongoose::builder(database.clone()).add_schema::<Product>();
+
+  // The indexes are declared in the Schema
+  let indexes = builder.schemas[0].get_indexes();
+  assert_eq!(index_keys(indexes.clone()), declared_keys());
+
+  let options = indexes[3].options.clone().unwrap();
+  assert_eq!(options.unique, Some(true));
+  assert_eq!(options.sparse, Some(true));
+
+  let options = indexes[4].options.clone().unwrap();
+  assert_eq!(options.partial_filter_expression, Some(filters::in_stock()));
+
+  // Create the missing indexes, keeping the undeclared ones
+  let collection = database.collection::<Document>("products_indexes");
+  let undeclared = IndexModel::builder().keys(doc! { "stock": 1 }).build();
+  assert!(collection.create_index(undeclared, None).await.is_ok());
+
+  assert!(builder.sync_indexes(false).await.is_ok());
+
+  let names = collection.list_index_names().await;
+  assert!(names.is_ok());
+
+  let names = names.unwrap();
+  assert!(names.contains(&String::from("category_price")));
+  assert!(names.contains(&String::from("stock_1")));
+
+  // Nothing to create
+  let nongoose = builder.build();
+  assert!(nongoose.ensure_indexes::<Product>().await.is_ok());
+
+  // Drop the undeclared indexes
+  assert!(builder.sync_indexes(true).await.is_ok());
+
+  let indexes = collection.list_indexes(None).await;
+  assert!(indexes.is_ok());
+
+  let indexes = indexes
+    .unwrap()
+    .try_collect::<Vec<IndexModel>>()
+    .await
+    .unwrap();
+  assert_eq!(indexes.len(), declared_keys().len() + 1);
+  assert!(!index_keys(indexes).contains(&doc! { "stock": 1 }));
+}
+
+fn duplicated_tags(name: &str) -> Vec<Document> {
+  vec![
+    doc! { "_id": ObjectId::new(), "name": name },
+    doc! { "_id": ObjectId::new(), "name": name },
+  ]
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn auto_index_blocking() {
+  let database = get_blocking_instance();
+  let name = ObjectId::new().to_hex();
+  assert!(database
+    .collection::<Document>("tags_indexes")
+    .insert_many(duplicated_tags(&name), None)
+    .is_ok());
+
+  let builder = blocking::Nongoose::builder(database.clone())
+    .add_schema::<Tag>()
+    .auto_index(true);
+  let nongoose = builder.build();
+
+  // The unique index is not created, but the operation does not fail
+  let tags = nongoose.find::<Tag>(doc! { "name": &name }, None);
+  assert!(tags.is_ok());
+  assert_eq!(tags.unwrap().len(), 2);
+
+  let tags = nongoose.find::<Tag>(doc! { "name": &name }, None);
+  assert!(tags.is_ok());
+
+  // `sync_indexes` returns the error
+  assert!(builder.sync_indexes(false).is_err());
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn auto_index() {
+  let database = get_instance().await;
+  let name = ObjectId::new().to_hex();
+  assert!(database
+    .collection::<Document>("tags_indexes")
+    .insert_many(duplicated_tags(&name), None)
+    .await
+    .is_ok());
+
+  let builder = Nongoose::builder(database.clone())
+    .add_schema::<Tag>()
+    .auto_index(true);
+  let nongoose = builder.build();
+
+  // The unique index is not created, but the operation does not fail
+  let tags = nongoose.find::<Tag>(doc! { "name": &name }, None).await;
+  assert!(tags.is_ok());
+  assert_eq!(tags.unwrap().len(), 2);
+
+  let tags = nongoose.find::<Tag>(doc! { "name": &name }, None).await;
+  assert!(tags.is_ok());
+
+  // `sync_indexes` returns the error
+  assert!(builder.sync_indexes(false).await.is_err());
+}
+
+fn article_search() -> IndexModel {
+  IndexModel::builder()
+    .keys(doc! { "title": "text" })
+    .options(
+      IndexOptions::builder()
+        .name(String::from("title_search"))
+        .build(),
+    )
+    .build()
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn text_indexes_blocking() {
+  let database = get_blocking_instance();
+  let collection = database.collection::<Document>("articles_indexes");
+  assert!(collection.drop(None).is_ok());
+  assert!(collection.create_index(article_search(), None).is_ok());
+
+  // The existing text index (stored as `{ _fts: "text", _ftsx: 1 }`) is the declared one
+  let builder = blocking::Nongoose::builder(database.clone()).add_schema::<Article>();
+  assert!(builder.sync_indexes(false).is_ok());
+  assert!(builder.sync_indexes(true).is_ok());
+
+  let names = collection.list_index_names();
+  assert!(names.is_ok());
+  assert_eq!(
+    names.unwrap(),
+    vec![String::from("_id_"), String::from("title_search")]
+  );
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn text_indexes() {
+  let database = get_instance().await;
+  let collection = database.collection::<Document>("articles_indexes");
+  assert!(collection.drop(None).await.is_ok());
+  assert!(collection
+    .create_index(article_search(), None)
+    .await
+    .is_ok());
+
+  // The existing text index (stored as `{ _fts: "text", _ftsx: 1 }`) is the declared one
+  let builder = Nongoose::builder(database.clone()).add_schema::<Article>();
+  assert!(builder.sync_indexes(false).await.is_ok());
+  assert!(builder.sync_indexes(true).await.is_ok());
+
+  let names = collection.list_index_names().await;
+  assert!(names.is_ok());
+  assert_eq!(
+    names.unwrap(),
+    vec![String::from("_id_"), String::from("title_search")]
+  );
+}
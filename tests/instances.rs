@@ -0,0 +1,160 @@
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{
+  bson::{doc, oid::ObjectId},
+  Schema,
+};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, Nongoose, SchemaBefore};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "tokio-runtime")]
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "actors_instances")]
+struct Actor {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub fullname: String,
+}
+
+#[cfg(feature = "sync")]
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "actors_instances_blocking")]
+struct BlockingActor {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub fullname: String,
+}
+
+#[cfg(feature = "tokio-runtime")]
+impl Actor {
+  pub fn new(fullname: &str) -> Self {
+    Self {
+      id: ObjectId::new(),
+      fullname: String::from(fullname),
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl BlockingActor {
+  pub fn new(fullname: &str) -> Self {
+    Self {
+      id: ObjectId::new(),
+      fullname: String::from(fullname),
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for BlockingActor {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Actor {}
+
+#[cfg(feature = "sync")]
+fn get_blocking_instance(database: &str) -> blocking::Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  blocking::Nongoose::builder(client.database(database))
+    .add_schema::<BlockingActor>()
+    .build()
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance(database: &str) -> Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match Client::with_uri_str(&url).await {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  Nongoose::builder(client.database(database))
+    .add_schema::<Actor>()
+    .build()
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn instances_blocking() {
+  let nongoose = get_blocking_instance("nongoose");
+  let tenant = get_blocking_instance("nongoose_tenant");
+
+  let actor = tenant.create(&BlockingActor::new("Tom Hanks"));
+  assert!(actor.is_ok());
+
+  let actor = actor.unwrap();
+
+  // The document only exists in the database of the tenant instance
+  let count = nongoose.count::<BlockingActor>(doc! {}, None);
+  assert!(count.is_ok());
+  assert_eq!(count.unwrap(), 0);
+
+  let count = tenant.count::<BlockingActor>(doc! {}, None);
+  assert!(count.is_ok());
+  assert_eq!(count.unwrap(), 1);
+
+  let removed = nongoose.remove(&actor);
+  assert!(removed.is_ok());
+  assert!(!removed.unwrap());
+
+  let removed = tenant.remove(&actor);
+  assert!(removed.is_ok());
+  assert!(removed.unwrap());
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn instances() {
+  let nongoose = get_instance("nongoose").await;
+  let tenant = get_instance("nongoose_tenant").await;
+
+  let actor = tenant.create(&Actor::new("Tom Hanks")).await;
+  assert!(actor.is_ok());
+
+  let actor = actor.unwrap();
+
+  // The document only exists in the database of the tenant instance
+  let count = nongoose.count::<Actor>(doc! {}, None).await;
+  assert!(count.is_ok());
+  assert_eq!(count.unwrap(), 0);
+
+  let count = tenant.count::<Actor>(doc! {}, None).await;
+  assert!(count.is_ok());
+  assert_eq!(count.unwrap(), 1);
+
+  let removed = nongoose.remove(&actor).await;
+  assert!(removed.is_ok());
+  assert!(!removed.unwrap());
+
+  let removed = tenant.remove(&actor).await;
+  assert!(removed.is_ok());
+  assert!(removed.unwrap());
+}
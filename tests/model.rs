@@ -0,0 +1,295 @@
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use std::time::Duration;
+
+use nongoose::{
+  bson::{doc, oid::ObjectId},
+  options::{Acknowledgment, Collation, WriteConcern},
+  Schema,
+};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, HookContext, Nongoose, SchemaBefore};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "tokio-runtime")]
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "actors_model")]
+struct Actor {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub fullname: String,
+  pub country: Option<String>,
+}
+
+#[cfg(feature = "sync")]
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "actors_model_blocking")]
+struct BlockingActor {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub fullname: String,
+  pub country: Option<String>,
+}
+
+#[cfg(feature = "tokio-runtime")]
+impl Actor {
+  pub fn new(fullname: &str, country: &str) -> Self {
+    Self {
+      id: ObjectId::new(),
+      fullname: String::from(fullname),
+      country: Some(String::from(country)),
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl BlockingActor {
+  pub fn new(fullname: &str, country: &str) -> Self {
+    Self {
+      id: ObjectId::new(),
+      fullname: String::from(fullname),
+      country: Some(String::from(country)),
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for BlockingActor {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Actor {}
+
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "reviews_model")]
+struct Review {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub actor: ObjectId,
+}
+
+impl Review {
+  pub fn new(actor: ObjectId) -> Self {
+    Self {
+      id: ObjectId::new(),
+      actor,
+    }
+  }
+}
+
+// The reviewed actor is updated with the Nongoose instance of the hook.
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Review {
+  fn before_create(&mut self, ctx: &mut blocking::HookContext<'_>) -> nongoose::Result<()> {
+    ctx.nongoose().update_many::<BlockingActor>(
+      doc! { "_id": self.actor },
+      doc! { "$set": { "country": "Reviewed" } },
+      None,
+    )?;
+
+    Ok(())
+  }
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Review {
+  async fn before_create(&mut self, ctx: &mut HookContext<'_>) -> nongoose::Result<()> {
+    ctx
+      .nongoose()
+      .update_many::<Actor>(
+        doc! { "_id": self.actor },
+        doc! { "$set": { "country": "Reviewed" } },
+        None,
+      )
+      .await?;
+
+    Ok(())
+  }
+}
+
+fn case_insensitive() -> Collation {
+  Collation::builder()
+    .locale("en")
+    .strength(nongoose::options::CollationStrength::Secondary)
+    .build()
+}
+
+/// Write concern that a single node replica set cannot satisfy.
+fn unsatisfiable() -> WriteConcern {
+  WriteConcern::builder()
+    .w(Acknowledgment::Nodes(2))
+    .w_timeout(Duration::from_millis(100))
+    .build()
+}
+
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> blocking::Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  blocking::Nongoose::builder(client.database("nongoose"))
+    .add_schema::<BlockingActor>()
+    .add_schema::<Review>()
+    .build()
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match Client::with_uri_str(&url).await {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  Nongoose::builder(client.database("nongoose"))
+    .add_schema::<Actor>()
+    .add_schema::<Review>()
+    .build()
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn model_blocking() {
+  let actors: blocking::Model<BlockingActor> = get_blocking_instance()
+    .model::<BlockingActor>()
+    .collation(case_insensitive())
+    .select(doc! { "fullname": 1 });
+
+  let actor = actors.create(&BlockingActor::new("Tom Hanks", "United States"));
+  assert!(actor.is_ok());
+
+  let actor = actor.unwrap();
+
+  // The default collation and projection of the model are used
+  let result = actors.find_one(doc! { "fullname": "tom hanks" }, None);
+  assert!(result.is_ok());
+
+  let result = result.unwrap();
+  assert!(result.is_some());
+
+  let result = result.unwrap();
+  assert_eq!(result.id, actor.id);
+  assert!(result.country.is_none());
+
+  // The defaults of the model are used by its queries too
+  let result = actors
+    .query()
+    .filter(doc! { "fullname": "TOM HANKS" })
+    .find();
+  assert!(result.is_ok());
+  assert_eq!(result.unwrap().len(), 1);
+
+  let removed = actors.remove(&actor);
+  assert!(removed.is_ok());
+  assert!(removed.unwrap());
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn model() {
+  let actors: nongoose::Model<Actor> = get_instance()
+    .await
+    .model::<Actor>()
+    .collation(case_insensitive())
+    .select(doc! { "fullname": 1 });
+
+  let actor = actors
+    .create(&Actor::new("Tom Hanks", "United States"))
+    .await;
+  assert!(actor.is_ok());
+
+  let actor = actor.unwrap();
+
+  // The default collation and projection of the model are used
+  let result = actors
+    .find_one(doc! { "fullname": "tom hanks" }, None)
+    .await;
+  assert!(result.is_ok());
+
+  let result = result.unwrap();
+  assert!(result.is_some());
+
+  let result = result.unwrap();
+  assert_eq!(result.id, actor.id);
+  assert!(result.country.is_none());
+
+  // The defaults of the model are used by its queries too
+  let result = actors
+    .query()
+    .filter(doc! { "fullname": "TOM HANKS" })
+    .find()
+    .await;
+  assert!(result.is_ok());
+  assert_eq!(result.unwrap().len(), 1);
+
+  let removed = actors.remove(&actor).await;
+  assert!(removed.is_ok());
+  assert!(removed.unwrap());
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn model_write_concern_blocking() {
+  let nongoose = get_blocking_instance();
+
+  let actor = nongoose.create(&BlockingActor::new("Meryl Streep", "United States"));
+  assert!(actor.is_ok());
+
+  let actor = actor.unwrap();
+
+  // The write concern of the model is used by its writes, but not by the hooks
+  let reviews = nongoose.model::<Review>().write_concern(unsatisfiable());
+  assert!(reviews.create(&Review::new(actor.id)).is_err());
+
+  let actor = nongoose.find_by_id::<BlockingActor>(&actor.id);
+  assert!(actor.is_ok());
+  assert_eq!(actor.unwrap().unwrap().country.as_deref(), Some("Reviewed"));
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn model_write_concern() {
+  let nongoose = get_instance().await;
+
+  let actor = nongoose
+    .create(&Actor::new("Meryl Streep", "United States"))
+    .await;
+  assert!(actor.is_ok());
+
+  let actor = actor.unwrap();
+
+  // The write concern of the model is used by its writes, but not by the hooks
+  let reviews = nongoose.model::<Review>().write_concern(unsatisfiable());
+  assert!(reviews.create(&Review::new(actor.id)).await.is_err());
+
+  let actor = nongoose.find_by_id::<Actor>(&actor.id).await;
+  assert!(actor.is_ok());
+  assert_eq!(actor.unwrap().unwrap().country.as_deref(), Some("Reviewed"));
+}
@@ -0,0 +1,211 @@
+use std::sync::{
+  atomic::{AtomicUsize, Ordering},
+  Arc,
+};
+
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{
+  bson::{doc, oid::ObjectId, Document},
+  DocumentMiddleware, IndexModel, Plugin, PluginSetup, Result, Schema, SchemaData,
+};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, Database, Nongoose, SchemaBefore};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Schema, Serialize)]
+#[schema(name = "actors_plugin")]
+struct Actor {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub name: String,
+  pub revision: i32,
+}
+
+impl Actor {
+  pub fn new(name: &str) -> Self {
+    Self {
+      id: ObjectId::new(),
+      name: String::from(name),
+      revision: 0,
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Actor {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Actor {}
+
+// Increment the revision of every saved document and count the removed documents
+#[derive(Clone, Default)]
+struct Revisions(Arc<AtomicUsize>);
+
+impl DocumentMiddleware for Revisions {
+  fn pre_save(&self, _collection: &str, document: &mut Document, _is_new: bool) -> Result<()> {
+    let revision = document.get_i32("revision")?;
+    document.insert("revision", revision + 1);
+
+    Ok(())
+  }
+
+  fn post_remove(&self, _collection: &str, _document: &Document) -> Result<()> {
+    self.0.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+  }
+}
+
+impl Plugin for Revisions {
+  fn setup(&self, setup: &mut PluginSetup) {
+    setup.add_document_middleware(self.clone());
+  }
+
+  fn add_schema(&self, schema: &mut SchemaData) {
+    schema.add_index(
+      IndexModel::builder()
+        .keys(doc! { "revision": -1 })
+        .options(
+          nongoose::options::IndexOptions::builder()
+            .name(format!("{}_revision", schema.get_name()))
+            .build(),
+        )
+        .build(),
+    );
+  }
+}
+
+#[cfg(feature = "sync")]
+fn get_blocking_instance(revisions: Revisions) -> (blocking::Nongoose, blocking::Database) {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  let nongoose = blocking::Nongoose::builder(client.database("nongoose_blocking"))
+    .plugin(revisions)
+    .add_schema::<Actor>()
+    .build();
+
+  (nongoose, client.database("nongoose_blocking"))
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance(revisions: Revisions) -> (Nongoose, Database) {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match Client::with_uri_str(&url).await {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  let nongoose = Nongoose::builder(client.database("nongoose"))
+    .plugin(revisions)
+    .add_schema::<Actor>()
+    .build();
+
+  (nongoose, client.database("nongoose"))
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn plugin_blocking() {
+  let revisions = Revisions::default();
+  let (nongoose, database) = get_blocking_instance(revisions.clone());
+
+  // The document middleware of the plugin increments the revision
+  let mut actor = Actor::new("Tom Holland");
+
+  let actor_saved = nongoose.save(&mut actor);
+  assert!(actor_saved.is_ok());
+  assert_eq!(actor_saved.unwrap().revision, 1);
+
+  let actor_saved = nongoose.save(&mut actor);
+  assert!(actor_saved.is_ok());
+  assert_eq!(actor_saved.unwrap().revision, 2);
+
+  let actor_found = nongoose.find_by_id::<Actor>(&actor.id);
+  assert!(actor_found.is_ok());
+  assert_eq!(actor_found.unwrap(), Some(actor.clone()));
+
+  // The index of the plugin is created
+  assert!(nongoose.ensure_indexes::<Actor>().is_ok());
+
+  let indexes = database
+    .collection::<Document>("actors_plugin")
+    .list_index_names();
+  assert!(indexes.is_ok());
+  assert!(indexes
+    .unwrap()
+    .contains(&String::from("actors_plugin_revision")));
+
+  // The document middleware of the plugin is called on remove
+  let removed = nongoose.remove(&actor);
+  assert!(removed.is_ok());
+  assert!(removed.unwrap());
+
+  assert_eq!(revisions.0.load(Ordering::SeqCst), 1);
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn plugin() {
+  let revisions = Revisions::default();
+  let (nongoose, database) = get_instance(revisions.clone()).await;
+
+  // The document middleware of the plugin increments the revision
+  let mut actor = Actor::new("Tom Holland");
+
+  let actor_saved = nongoose.save(&mut actor).await;
+  assert!(actor_saved.is_ok());
+  assert_eq!(actor_saved.unwrap().revision, 1);
+
+  let actor_saved = nongoose.save(&mut actor).await;
+  assert!(actor_saved.is_ok());
+  assert_eq!(actor_saved.unwrap().revision, 2);
+
+  let actor_found = nongoose.find_by_id::<Actor>(&actor.id).await;
+  assert!(actor_found.is_ok());
+  assert_eq!(actor_found.unwrap(), Some(actor.clone()));
+
+  // The index of the plugin is created
+  assert!(nongoose.ensure_indexes::<Actor>().await.is_ok());
+
+  let indexes = database
+    .collection::<Document>("actors_plugin")
+    .list_index_names()
+    .await;
+  assert!(indexes.is_ok());
+  assert!(indexes
+    .unwrap()
+    .contains(&String::from("actors_plugin_revision")));
+
+  // The document middleware of the plugin is called on remove
+  let removed = nongoose.remove(&actor).await;
+  assert!(removed.is_ok());
+  assert!(removed.unwrap());
+
+  assert_eq!(revisions.0.load(Ordering::SeqCst), 1);
+}
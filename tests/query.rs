@@ -0,0 +1,246 @@
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{
+  bson::{doc, oid::ObjectId},
+  Schema,
+};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, Nongoose, SchemaBefore};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "tokio-runtime")]
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "actors_query")]
+struct Actor {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  #[serde(default)]
+  pub fullname: String,
+  pub age: u64,
+}
+
+#[cfg(feature = "sync")]
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "actors_query_blocking")]
+struct BlockingActor {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  #[serde(default)]
+  pub fullname: String,
+  pub age: u64,
+}
+
+#[cfg(feature = "tokio-runtime")]
+impl Actor {
+  pub fn new(fullname: &str, age: u64) -> Self {
+    Self {
+      id: ObjectId::new(),
+      fullname: String::from(fullname),
+      age,
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl BlockingActor {
+  pub fn new(fullname: &str, age: u64) -> Self {
+    Self {
+      id: ObjectId::new(),
+      fullname: String::from(fullname),
+      age,
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for BlockingActor {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Actor {}
+
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> blocking::Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  blocking::Nongoose::builder(client.database("nongoose"))
+    .add_schema::<BlockingActor>()
+    .build()
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match Client::with_uri_str(&url).await {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  Nongoose::builder(client.database("nongoose"))
+    .add_schema::<Actor>()
+    .build()
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn query_blocking() {
+  let nongoose = get_blocking_instance();
+
+  assert!(nongoose
+    .create(&BlockingActor::new("Tom Hanks", 65))
+    .is_ok());
+  assert!(nongoose
+    .create(&BlockingActor::new("Will Smith", 53))
+    .is_ok());
+  assert!(nongoose
+    .create(&BlockingActor::new("Leonardo DiCaprio", 46))
+    .is_ok());
+  assert!(nongoose
+    .create(&BlockingActor::new("Jeniffer Lopez", 52))
+    .is_ok());
+  assert!(nongoose
+    .create(&BlockingActor::new("Tom Cruise", 59))
+    .is_ok());
+  assert!(nongoose
+    .create(&BlockingActor::new("Emma Stone", 32))
+    .is_ok());
+
+  // Actors over 50 years old (sorted by age)
+  let over_50s = nongoose
+    .query::<BlockingActor>()
+    .filter(doc! { "age": { "$gte": 50 } })
+    .sort(doc! { "age": 1 });
+
+  let count = over_50s.count();
+  assert!(count.is_ok());
+  assert_eq!(count.unwrap(), 4);
+
+  let actors = over_50s.find();
+  assert!(actors.is_ok());
+
+  let actors = actors.unwrap();
+  assert_eq!(actors.len(), 4);
+  assert_eq!(actors[0].fullname, "Jeniffer Lopez");
+  assert_eq!(actors[3].fullname, "Tom Hanks");
+
+  // Actors between 50 and 59 years old (composed filter)
+  let actors = over_50s
+    .clone()
+    .filter(doc! { "age": { "$lte": 59 } })
+    .skip(1)
+    .limit(1)
+    .find();
+  assert!(actors.is_ok());
+
+  let actors = actors.unwrap();
+  assert_eq!(actors.len(), 1);
+  assert_eq!(actors[0].fullname, "Will Smith");
+
+  // Youngest actor without its name
+  let youngest = nongoose
+    .query::<BlockingActor>()
+    .sort(doc! { "age": 1 })
+    .select(doc! { "fullname": 0 })
+    .find_one();
+  assert!(youngest.is_ok());
+
+  let youngest = youngest.unwrap();
+  assert!(youngest.is_some());
+
+  let youngest = youngest.unwrap();
+  assert_eq!(youngest.age, 32);
+  assert!(youngest.fullname.is_empty());
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn query() {
+  let nongoose = get_instance().await;
+
+  assert!(nongoose.create(&Actor::new("Tom Hanks", 65)).await.is_ok());
+  assert!(nongoose.create(&Actor::new("Will Smith", 53)).await.is_ok());
+  assert!(nongoose
+    .create(&Actor::new("Leonardo DiCaprio", 46))
+    .await
+    .is_ok());
+  assert!(nongoose
+    .create(&Actor::new("Jeniffer Lopez", 52))
+    .await
+    .is_ok());
+  assert!(nongoose.create(&Actor::new("Tom Cruise", 59)).await.is_ok());
+  assert!(nongoose.create(&Actor::new("Emma Stone", 32)).await.is_ok());
+
+  // Actors over 50 years old (sorted by age)
+  let over_50s = nongoose
+    .query::<Actor>()
+    .filter(doc! { "age": { "$gte": 50 } })
+    .sort(doc! { "age": 1 });
+
+  let count = over_50s.count().await;
+  assert!(count.is_ok());
+  assert_eq!(count.unwrap(), 4);
+
+  let actors = over_50s.find().await;
+  assert!(actors.is_ok());
+
+  let actors = actors.unwrap();
+  assert_eq!(actors.len(), 4);
+  assert_eq!(actors[0].fullname, "Jeniffer Lopez");
+  assert_eq!(actors[3].fullname, "Tom Hanks");
+
+  // Actors between 50 and 59 years old (composed filter)
+  let actors = over_50s
+    .clone()
+    .filter(doc! { "age": { "$lte": 59 } })
+    .skip(1)
+    .limit(1)
+    .find()
+    .await;
+  assert!(actors.is_ok());
+
+  let actors = actors.unwrap();
+  assert_eq!(actors.len(), 1);
+  assert_eq!(actors[0].fullname, "Will Smith");
+
+  // Youngest actor without its name
+  let youngest = nongoose
+    .query::<Actor>()
+    .sort(doc! { "age": 1 })
+    .select(doc! { "fullname": 0 })
+    .find_one()
+    .await;
+  assert!(youngest.is_ok());
+
+  let youngest = youngest.unwrap();
+  assert!(youngest.is_some());
+
+  let youngest = youngest.unwrap();
+  assert_eq!(youngest.age, 32);
+  assert!(youngest.fullname.is_empty());
+}
@@ -0,0 +1,351 @@
+use std::sync::{
+  atomic::{AtomicUsize, Ordering},
+  Arc,
+};
+
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{
+  bson::{doc, oid::ObjectId, Document},
+  schema_relations, MiddlewareQuery, MiddlewareResult, QueryMiddleware, Result, Schema,
+};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, Nongoose, SchemaBefore};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Schema, Serialize)]
+#[schema(name = "actors_query_middleware")]
+struct Actor {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub name: String,
+  pub deleted: bool,
+}
+
+impl Actor {
+  pub fn new(name: &str, deleted: bool) -> Self {
+    Self {
+      id: ObjectId::new(),
+      name: String::from(name),
+      deleted,
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Actor {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Actor {}
+
+#[schema_relations]
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "movies_query_middleware")]
+struct Movie {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+
+  #[schema(many_to_one = "Actor")]
+  #[serde(skip_serializing)]
+  pub lead: Option<Actor>,
+
+  pub title: String,
+}
+
+impl Movie {
+  pub fn new(title: &str, lead: &Actor) -> Self {
+    Self {
+      id: ObjectId::new(),
+      lead: None,
+      lead_id: lead.id,
+      title: String::from(title),
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Movie {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Movie {}
+
+// Exclude the soft-deleted actors of every query
+struct SoftDelete;
+
+impl QueryMiddleware for SoftDelete {
+  fn pre(&self, collection: &str, query: &mut MiddlewareQuery) -> Result<()> {
+    if collection != "actors_query_middleware" {
+      return Ok(());
+    }
+
+    if let Some(conditions) = query.conditions() {
+      conditions.insert("deleted", false);
+    } else if let MiddlewareQuery::Aggregate { pipeline, .. } = query {
+      pipeline.insert(0, doc! { "$match": { "deleted": false } });
+    }
+
+    Ok(())
+  }
+}
+
+// Count the documents returned by the find functions
+#[derive(Clone, Default)]
+struct FoundDocuments(Arc<AtomicUsize>);
+
+impl QueryMiddleware for FoundDocuments {
+  fn post(&self, _collection: &str, result: &MiddlewareResult) -> Result<()> {
+    let found = match result {
+      MiddlewareResult::Find(documents) => documents.len(),
+      MiddlewareResult::FindOne(document)
+      | MiddlewareResult::FindOneAndDelete(document)
+      | MiddlewareResult::FindOneAndUpdate(document) => document.is_some() as usize,
+      _ => 0,
+    };
+
+    self.0.fetch_add(found, Ordering::SeqCst);
+    Ok(())
+  }
+}
+
+#[cfg(feature = "sync")]
+fn get_blocking_instance(found: FoundDocuments) -> blocking::Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  blocking::Nongoose::builder(client.database("nongoose_blocking"))
+    .add_schema::<Actor>()
+    .add_schema::<Movie>()
+    .add_query_middleware(SoftDelete)
+    .add_query_middleware(found)
+    .build()
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance(found: FoundDocuments) -> Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match Client::with_uri_str(&url).await {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  Nongoose::builder(client.database("nongoose"))
+    .add_schema::<Actor>()
+    .add_schema::<Movie>()
+    .add_query_middleware(SoftDelete)
+    .add_query_middleware(found)
+    .build()
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn query_middleware_blocking() {
+  let found = FoundDocuments::default();
+  let nongoose = get_blocking_instance(found.clone());
+
+  // Upload data
+  let actor_one = Actor::new("Tom Holland", false);
+  assert!(nongoose.create(&actor_one).is_ok());
+
+  let actor_two = Actor::new("Zendaya", true);
+  assert!(nongoose.create(&actor_two).is_ok());
+
+  let ids = doc! { "_id": { "$in": [actor_one.id, actor_two.id] } };
+
+  // The soft-deleted actor is excluded
+  let actors = nongoose.find::<Actor>(ids.clone(), None);
+  assert!(actors.is_ok());
+  assert_eq!(actors.unwrap(), vec![actor_one.clone()]);
+
+  let actor = nongoose.find_by_id::<Actor>(&actor_two.id);
+  assert!(actor.is_ok());
+  assert!(actor.unwrap().is_none());
+
+  assert_eq!(found.0.load(Ordering::SeqCst), 1);
+
+  let count = nongoose.count::<Actor>(ids.clone(), None);
+  assert!(count.is_ok());
+  assert_eq!(count.unwrap(), 1);
+
+  let result =
+    nongoose.update_many::<Actor>(ids.clone(), doc! { "$set": { "name": "Unknown" } }, None);
+  assert!(result.is_ok());
+  assert_eq!(result.unwrap().modified_count, 1);
+
+  let names = nongoose.aggregate::<Actor, Document>(
+    vec![
+      doc! { "$match": ids.clone() },
+      doc! { "$project": { "_id": 0, "name": 1 } },
+    ],
+    None,
+  );
+  assert!(names.is_ok());
+  assert_eq!(names.unwrap(), vec![doc! { "name": "Unknown" }]);
+
+  // The populated actors are filtered too
+  let movie = nongoose.populate(Movie::new("Spider-Man", &actor_one), "lead");
+  assert!(movie.is_ok());
+  assert_eq!(movie.unwrap().lead.unwrap().id, actor_one.id);
+
+  let movie = nongoose.populate(Movie::new("Euphoria", &actor_two), "lead");
+  assert!(movie.is_ok());
+  assert!(movie.unwrap().lead.is_none());
+
+  assert_eq!(found.0.load(Ordering::SeqCst), 2);
+
+  // `save()` still finds the soft-deleted actor, so it is replaced instead of inserted again
+  let mut actor = actor_two.clone();
+  actor.name = String::from("Zendaya Coleman");
+  assert!(nongoose.save(&mut actor).is_ok());
+
+  // The soft-deleted actor is not updated nor removed
+  let actor = nongoose.find_by_id_and_update::<Actor>(
+    &actor_two.id,
+    doc! { "$set": { "name": "Zendaya" } },
+    None,
+  );
+  assert!(actor.is_ok());
+  assert!(actor.unwrap().is_none());
+
+  let actor = nongoose.find_by_id_and_update::<Actor>(
+    &actor_one.id,
+    doc! { "$set": { "name": "Tom Holland" } },
+    None,
+  );
+  assert!(actor.is_ok());
+  assert!(actor.unwrap().is_some());
+
+  let result = nongoose.find_by_id_and_remove::<Actor>(&actor_one.id);
+  assert!(result.is_ok());
+  assert!(result.unwrap().0);
+
+  let result = nongoose.delete_many::<Actor>(ids, None);
+  assert!(result.is_ok());
+  assert_eq!(result.unwrap().deleted_count, 0);
+
+  // The populated, the updated and the removed documents (found and deleted)
+  assert_eq!(found.0.load(Ordering::SeqCst), 5);
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn query_middleware() {
+  let found = FoundDocuments::default();
+  let nongoose = get_instance(found.clone()).await;
+
+  // Upload data
+  let actor_one = Actor::new("Tom Holland", false);
+  assert!(nongoose.create(&actor_one).await.is_ok());
+
+  let actor_two = Actor::new("Zendaya", true);
+  assert!(nongoose.create(&actor_two).await.is_ok());
+
+  let ids = doc! { "_id": { "$in": [actor_one.id, actor_two.id] } };
+
+  // The soft-deleted actor is excluded
+  let actors = nongoose.find::<Actor>(ids.clone(), None).await;
+  assert!(actors.is_ok());
+  assert_eq!(actors.unwrap(), vec![actor_one.clone()]);
+
+  let actor = nongoose.find_by_id::<Actor>(&actor_two.id).await;
+  assert!(actor.is_ok());
+  assert!(actor.unwrap().is_none());
+
+  assert_eq!(found.0.load(Ordering::SeqCst), 1);
+
+  let count = nongoose.count::<Actor>(ids.clone(), None).await;
+  assert!(count.is_ok());
+  assert_eq!(count.unwrap(), 1);
+
+  let result = nongoose
+    .update_many::<Actor>(ids.clone(), doc! { "$set": { "name": "Unknown" } }, None)
+    .await;
+  assert!(result.is_ok());
+  assert_eq!(result.unwrap().modified_count, 1);
+
+  let names = nongoose
+    .aggregate::<Actor, Document>(
+      vec![
+        doc! { "$match": ids.clone() },
+        doc! { "$project": { "_id": 0, "name": 1 } },
+      ],
+      None,
+    )
+    .await;
+  assert!(names.is_ok());
+  assert_eq!(names.unwrap(), vec![doc! { "name": "Unknown" }]);
+
+  // The populated actors are filtered too
+  let movie = nongoose
+    .populate(Movie::new("Spider-Man", &actor_one), "lead")
+    .await;
+  assert!(movie.is_ok());
+  assert_eq!(movie.unwrap().lead.unwrap().id, actor_one.id);
+
+  let movie = nongoose
+    .populate(Movie::new("Euphoria", &actor_two), "lead")
+    .await;
+  assert!(movie.is_ok());
+  assert!(movie.unwrap().lead.is_none());
+
+  assert_eq!(found.0.load(Ordering::SeqCst), 2);
+
+  // `save()` still finds the soft-deleted actor, so it is replaced instead of inserted again
+  let mut actor = actor_two.clone();
+  actor.name = String::from("Zendaya Coleman");
+  assert!(nongoose.save(&mut actor).await.is_ok());
+
+  // The soft-deleted actor is not updated nor removed
+  let actor = nongoose
+    .find_by_id_and_update::<Actor>(&actor_two.id, doc! { "$set": { "name": "Zendaya" } }, None)
+    .await;
+  assert!(actor.is_ok());
+  assert!(actor.unwrap().is_none());
+
+  let actor = nongoose
+    .find_by_id_and_update::<Actor>(
+      &actor_one.id,
+      doc! { "$set": { "name": "Tom Holland" } },
+      None,
+    )
+    .await;
+  assert!(actor.is_ok());
+  assert!(actor.unwrap().is_some());
+
+  let result = nongoose.find_by_id_and_remove::<Actor>(&actor_one.id).await;
+  assert!(result.is_ok());
+  assert!(result.unwrap().0);
+
+  let result = nongoose.delete_many::<Actor>(ids, None).await;
+  assert!(result.is_ok());
+  assert_eq!(result.unwrap().deleted_count, 0);
+
+  // The populated, the updated and the removed documents (found and deleted)
+  assert_eq!(found.0.load(Ordering::SeqCst), 5);
+}
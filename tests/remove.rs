@@ -1,11 +1,16 @@
+#[cfg(feature = "sync")]
+use nongoose::blocking;
 use nongoose::{
   bson::{doc, oid::ObjectId, Regex},
   options::FindOptions,
-  Client, Nongoose, Schema, SchemaBefore,
+  Schema,
 };
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, HookContext, Nongoose, SchemaAfter, SchemaBefore};
 
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "tokio-runtime")]
 #[derive(Clone, Debug, Deserialize, Schema, Serialize)]
 struct Actor {
   #[schema(id)]
@@ -15,6 +20,17 @@ struct Actor {
   pub age: u64,
 }
 
+#[cfg(feature = "sync")]
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+struct BlockingActor {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub fullname: String,
+  pub age: u64,
+}
+
+#[cfg(feature = "tokio-runtime")]
 impl Actor {
   pub fn new(fullname: &str, age: u64) -> Self {
     Self {
@@ -25,11 +41,176 @@ impl Actor {
   }
 }
 
-#[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
+#[cfg(feature = "sync")]
+impl BlockingActor {
+  pub fn new(fullname: &str, age: u64) -> Self {
+    Self {
+      id: ObjectId::new(),
+      fullname: String::from(fullname),
+      age,
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for BlockingActor {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
 impl SchemaBefore for Actor {}
 
-#[cfg(test)]
-fn get_instance() -> Nongoose {
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "protected_actors_remove")]
+struct ProtectedActor {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub fullname: String,
+  pub protected: bool,
+}
+
+impl ProtectedActor {
+  pub fn new(fullname: &str, protected: bool) -> Self {
+    Self {
+      id: ObjectId::new(),
+      fullname: String::from(fullname),
+      protected,
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for ProtectedActor {
+  fn before_delete(&mut self, _ctx: &mut blocking::HookContext<'_>) -> nongoose::Result<bool> {
+    Ok(!self.protected)
+  }
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for ProtectedActor {
+  async fn before_delete(&mut self, _ctx: &mut HookContext<'_>) -> nongoose::Result<bool> {
+    Ok(!self.protected)
+  }
+}
+
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "tickets_remove")]
+struct Ticket {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub attempts: u64,
+}
+
+impl Ticket {
+  pub fn new(attempts: u64) -> Self {
+    Self {
+      id: ObjectId::new(),
+      attempts,
+    }
+  }
+}
+
+// The document is modified in the database before removing it.
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Ticket {
+  fn before_delete(&mut self, ctx: &mut blocking::HookContext<'_>) -> nongoose::Result<bool> {
+    ctx.nongoose().update_many::<Ticket>(
+      doc! { "_id": self.id },
+      doc! { "$inc": { "attempts": 1 } },
+      None,
+    )?;
+
+    Ok(true)
+  }
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Ticket {
+  async fn before_delete(&mut self, ctx: &mut HookContext<'_>) -> nongoose::Result<bool> {
+    ctx
+      .nongoose()
+      .update_many::<Ticket>(
+        doc! { "_id": self.id },
+        doc! { "$inc": { "attempts": 1 } },
+        None,
+      )
+      .await?;
+
+    Ok(true)
+  }
+}
+
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "claims_remove", after_hooks)]
+struct Claim {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub taken: bool,
+  pub events: Vec<String>,
+}
+
+impl Claim {
+  pub fn new(taken: bool) -> Self {
+    Self {
+      id: ObjectId::new(),
+      taken,
+      events: Vec::new(),
+    }
+  }
+}
+
+// A taken claim is removed by someone else before removing it.
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Claim {
+  fn before_delete(&mut self, ctx: &mut blocking::HookContext<'_>) -> nongoose::Result<bool> {
+    if self.taken {
+      ctx
+        .nongoose()
+        .delete_many::<Claim>(doc! { "_id": self.id }, None)?;
+    }
+
+    Ok(true)
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaAfter for Claim {
+  fn after_delete(&mut self, _ctx: &mut blocking::HookContext<'_>) -> nongoose::Result<()> {
+    self.events.push(String::from("deleted"));
+    Ok(())
+  }
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Claim {
+  async fn before_delete(&mut self, ctx: &mut HookContext<'_>) -> nongoose::Result<bool> {
+    if self.taken {
+      ctx
+        .nongoose()
+        .delete_many::<Claim>(doc! { "_id": self.id }, None)
+        .await?;
+    }
+
+    Ok(true)
+  }
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaAfter for Claim {
+  async fn after_delete(&mut self, _ctx: &mut HookContext<'_>) -> nongoose::Result<()> {
+    self.events.push(String::from("deleted"));
+    Ok(())
+  }
+}
+
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> blocking::Nongoose {
   // Get database url.
   let url = match std::env::var("DATABASE_URL") {
     Ok(url) => url,
@@ -39,7 +220,33 @@ fn get_instance() -> Nongoose {
   };
 
   // Get MongoDB connection.
-  let client = match Client::with_uri_str(&url) {
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  blocking::Nongoose::builder(client.database("nongoose"))
+    .add_schema::<BlockingActor>()
+    .add_schema::<ProtectedActor>()
+    .add_schema::<Ticket>()
+    .add_schema::<Claim>()
+    .build()
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match Client::with_uri_str(&url).await {
     Ok(client) => client,
     Err(e) => {
       panic!("Error connecting to the database: {}", e);
@@ -48,30 +255,33 @@ fn get_instance() -> Nongoose {
 
   Nongoose::builder(client.database("nongoose"))
     .add_schema::<Actor>()
+    .add_schema::<ProtectedActor>()
+    .add_schema::<Ticket>()
+    .add_schema::<Claim>()
     .build()
 }
 
 #[cfg(feature = "sync")]
 #[cfg_attr(feature = "sync", test)]
-fn remove() {
-  let nongoose = get_instance();
+fn remove_blocking() {
+  let nongoose = get_blocking_instance();
 
-  let tom_hanks = Actor::new("Tom Hanks", 65).save();
+  let tom_hanks = nongoose.create(&BlockingActor::new("Tom Hanks", 65));
   assert!(tom_hanks.is_ok());
 
-  let will_smith = Actor::new("Will Smith", 53).save();
+  let will_smith = nongoose.create(&BlockingActor::new("Will Smith", 53));
   assert!(will_smith.is_ok());
 
-  let leonardo_dicaprio = Actor::new("Leonardo DiCaprio", 46).save();
+  let leonardo_dicaprio = nongoose.create(&BlockingActor::new("Leonardo DiCaprio", 46));
   assert!(leonardo_dicaprio.is_ok());
 
-  let jeniffer_lopez = Actor::new("Jeniffer Lopez", 52).save();
+  let jeniffer_lopez = nongoose.create(&BlockingActor::new("Jeniffer Lopez", 52));
   assert!(jeniffer_lopez.is_ok());
 
-  let tom_cruise = Actor::new("Tom Cruise", 59).save();
+  let tom_cruise = nongoose.create(&BlockingActor::new("Tom Cruise", 59));
   assert!(tom_cruise.is_ok());
 
-  let emma_stone = Actor::new("Emma Stone", 32).save();
+  let emma_stone = nongoose.create(&BlockingActor::new("Emma Stone", 32));
   assert!(emma_stone.is_ok());
 
   // Unwrap actors
@@ -83,7 +293,7 @@ fn remove() {
   let emma_stone = emma_stone.unwrap();
 
   // Remove one by id
-  let by_id = nongoose.find_by_id_and_remove::<Actor>(&emma_stone.id);
+  let by_id = nongoose.find_by_id_and_remove::<BlockingActor>(&emma_stone.id);
   assert!(by_id.is_ok());
 
   let (by_id_result, by_id_user) = by_id.unwrap();
@@ -94,7 +304,7 @@ fn remove() {
   assert_eq!(by_id_user.fullname, emma_stone.fullname);
 
   // Remove one by age
-  let age_32 = nongoose.find_one_and_remove::<Actor>(doc! { "age": 32 }, None);
+  let age_32 = nongoose.find_one_and_remove::<BlockingActor>(doc! { "age": 32 }, None);
   assert!(age_32.is_ok());
 
   let (age_32_result, age_32_user) = age_32.unwrap();
@@ -106,7 +316,7 @@ fn remove() {
 
   // Remove actors between 40 and 49 years old
   let actors_40s_age =
-    nongoose.find_and_remove::<Actor>(doc! { "age": { "$gte": 40, "$lte": 49 } }, None);
+    nongoose.find_and_remove::<BlockingActor>(doc! { "age": { "$gte": 40, "$lte": 49 } }, None);
   assert!(actors_40s_age.is_ok());
 
   let actors_40s_age = actors_40s_age.unwrap();
@@ -115,7 +325,7 @@ fn remove() {
   assert_eq!(actors_40s_age[0].1.fullname, leonardo_dicaprio.fullname);
 
   // Remove actors between 50 and 59 years old
-  let actors_50s_age = nongoose.find_and_remove::<Actor>(
+  let actors_50s_age = nongoose.find_and_remove::<BlockingActor>(
     doc! { "age": { "$gte": 50, "$lte": 59 }},
     Some(FindOptions::builder().sort(doc! { "age": 1 }).build()),
   );
@@ -131,7 +341,7 @@ fn remove() {
   assert_eq!(actors_50s_age[5].1.fullname, tom_cruise.fullname);
 
   // Remove actors with the word "Tom" in their name
-  let tom_actors = nongoose.find_and_remove::<Actor>(
+  let tom_actors = nongoose.find_and_remove::<BlockingActor>(
     doc! { "fullname": Regex { pattern: String::from("^Tom"), options: String::new() } },
     Some(FindOptions::builder().sort(doc! { "age": -1 }).build()),
   );
@@ -146,24 +356,24 @@ fn remove() {
 #[cfg(feature = "tokio-runtime")]
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 async fn remove() {
-  let nongoose = get_instance();
+  let nongoose = get_instance().await;
 
-  let tom_hanks = Actor::new("Tom Hanks", 65).save().await;
+  let tom_hanks = nongoose.create(&Actor::new("Tom Hanks", 65)).await;
   assert!(tom_hanks.is_ok());
 
-  let will_smith = Actor::new("Will Smith", 53).save().await;
+  let will_smith = nongoose.create(&Actor::new("Will Smith", 53)).await;
   assert!(will_smith.is_ok());
 
-  let leonardo_dicaprio = Actor::new("Leonardo DiCaprio", 46).save().await;
+  let leonardo_dicaprio = nongoose.create(&Actor::new("Leonardo DiCaprio", 46)).await;
   assert!(leonardo_dicaprio.is_ok());
 
-  let jeniffer_lopez = Actor::new("Jeniffer Lopez", 52).save().await;
+  let jeniffer_lopez = nongoose.create(&Actor::new("Jeniffer Lopez", 52)).await;
   assert!(jeniffer_lopez.is_ok());
 
-  let tom_cruise = Actor::new("Tom Cruise", 59).save().await;
+  let tom_cruise = nongoose.create(&Actor::new("Tom Cruise", 59)).await;
   assert!(tom_cruise.is_ok());
 
-  let emma_stone = Actor::new("Emma Stone", 32).save().await;
+  let emma_stone = nongoose.create(&Actor::new("Emma Stone", 32)).await;
   assert!(emma_stone.is_ok());
 
   // Unwrap actors
@@ -243,3 +453,308 @@ async fn remove() {
   assert!(tom_actors[0].0);
   assert_eq!(tom_actors[1].1.fullname, tom_hanks.fullname);
 }
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn remove_vetoed_blocking() {
+  let nongoose = get_blocking_instance();
+
+  let meryl_streep = nongoose.create(&ProtectedActor::new("Meryl Streep", true));
+  assert!(meryl_streep.is_ok());
+
+  let brad_pitt = nongoose.create(&ProtectedActor::new("Brad Pitt", false));
+  assert!(brad_pitt.is_ok());
+
+  let johnny_depp = nongoose.create(&ProtectedActor::new("Johnny Depp", false));
+  assert!(johnny_depp.is_ok());
+
+  // Unwrap actors
+  let meryl_streep = meryl_streep.unwrap();
+  let brad_pitt = brad_pitt.unwrap();
+  let johnny_depp = johnny_depp.unwrap();
+
+  // Remove a protected actor
+  let removed = nongoose.remove(&meryl_streep);
+  assert!(removed.is_ok());
+  assert!(!removed.unwrap());
+
+  // Remove a protected actor by id
+  let by_id = nongoose.find_by_id_and_remove::<ProtectedActor>(&meryl_streep.id);
+  assert!(by_id.is_ok());
+
+  let (by_id_result, by_id_actor) = by_id.unwrap();
+  assert!(!by_id_result);
+  assert!(by_id_actor.is_some());
+  assert_eq!(by_id_actor.unwrap().fullname, meryl_streep.fullname);
+
+  // Remove a protected actor by conditions
+  let by_conditions =
+    nongoose.find_one_and_remove::<ProtectedActor>(doc! { "_id": meryl_streep.id }, None);
+  assert!(by_conditions.is_ok());
+
+  let (by_conditions_result, by_conditions_actor) = by_conditions.unwrap();
+  assert!(!by_conditions_result);
+  assert!(by_conditions_actor.is_some());
+
+  // Remove a protected and an unprotected actor
+  let actors = nongoose.find_and_remove::<ProtectedActor>(
+    doc! { "_id": { "$in": [meryl_streep.id, brad_pitt.id] } },
+    Some(FindOptions::builder().sort(doc! { "fullname": 1 }).build()),
+  );
+  assert!(actors.is_ok());
+
+  let actors = actors.unwrap();
+  assert_eq!(actors.len(), 2);
+  assert!(actors[0].0);
+  assert_eq!(actors[0].1.fullname, brad_pitt.fullname);
+  assert!(!actors[1].0);
+  assert_eq!(actors[1].1.fullname, meryl_streep.fullname);
+
+  // Remove an unprotected actor
+  let removed = nongoose.remove(&johnny_depp);
+  assert!(removed.is_ok());
+  assert!(removed.unwrap());
+
+  // Only the protected actor is still in the database
+  let meryl_streep_found = nongoose.find_by_id::<ProtectedActor>(&meryl_streep.id);
+  assert!(meryl_streep_found.is_ok());
+  assert!(meryl_streep_found.unwrap().is_some());
+
+  let brad_pitt_found = nongoose.find_by_id::<ProtectedActor>(&brad_pitt.id);
+  assert!(brad_pitt_found.is_ok());
+  assert!(brad_pitt_found.unwrap().is_none());
+
+  let johnny_depp_found = nongoose.find_by_id::<ProtectedActor>(&johnny_depp.id);
+  assert!(johnny_depp_found.is_ok());
+  assert!(johnny_depp_found.unwrap().is_none());
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn remove_vetoed() {
+  let nongoose = get_instance().await;
+
+  let meryl_streep = nongoose
+    .create(&ProtectedActor::new("Meryl Streep", true))
+    .await;
+  assert!(meryl_streep.is_ok());
+
+  let brad_pitt = nongoose
+    .create(&ProtectedActor::new("Brad Pitt", false))
+    .await;
+  assert!(brad_pitt.is_ok());
+
+  let johnny_depp = nongoose
+    .create(&ProtectedActor::new("Johnny Depp", false))
+    .await;
+  assert!(johnny_depp.is_ok());
+
+  // Unwrap actors
+  let meryl_streep = meryl_streep.unwrap();
+  let brad_pitt = brad_pitt.unwrap();
+  let johnny_depp = johnny_depp.unwrap();
+
+  // Remove a protected actor
+  let removed = nongoose.remove(&meryl_streep).await;
+  assert!(removed.is_ok());
+  assert!(!removed.unwrap());
+
+  // Remove a protected actor by id
+  let by_id = nongoose
+    .find_by_id_and_remove::<ProtectedActor>(&meryl_streep.id)
+    .await;
+  assert!(by_id.is_ok());
+
+  let (by_id_result, by_id_actor) = by_id.unwrap();
+  assert!(!by_id_result);
+  assert!(by_id_actor.is_some());
+  assert_eq!(by_id_actor.unwrap().fullname, meryl_streep.fullname);
+
+  // Remove a protected actor by conditions
+  let by_conditions = nongoose
+    .find_one_and_remove::<ProtectedActor>(doc! { "_id": meryl_streep.id }, None)
+    .await;
+  assert!(by_conditions.is_ok());
+
+  let (by_conditions_result, by_conditions_actor) = by_conditions.unwrap();
+  assert!(!by_conditions_result);
+  assert!(by_conditions_actor.is_some());
+
+  // Remove a protected and an unprotected actor
+  let actors = nongoose
+    .find_and_remove::<ProtectedActor>(
+      doc! { "_id": { "$in": [meryl_streep.id, brad_pitt.id] } },
+      Some(FindOptions::builder().sort(doc! { "fullname": 1 }).build()),
+    )
+    .await;
+  assert!(actors.is_ok());
+
+  let actors = actors.unwrap();
+  assert_eq!(actors.len(), 2);
+  assert!(actors[0].0);
+  assert_eq!(actors[0].1.fullname, brad_pitt.fullname);
+  assert!(!actors[1].0);
+  assert_eq!(actors[1].1.fullname, meryl_streep.fullname);
+
+  // Remove an unprotected actor
+  let removed = nongoose.remove(&johnny_depp).await;
+  assert!(removed.is_ok());
+  assert!(removed.unwrap());
+
+  // Only the protected actor is still in the database
+  let meryl_streep_found = nongoose
+    .find_by_id::<ProtectedActor>(&meryl_streep.id)
+    .await;
+  assert!(meryl_streep_found.is_ok());
+  assert!(meryl_streep_found.unwrap().is_some());
+
+  let brad_pitt_found = nongoose.find_by_id::<ProtectedActor>(&brad_pitt.id).await;
+  assert!(brad_pitt_found.is_ok());
+  assert!(brad_pitt_found.unwrap().is_none());
+
+  let johnny_depp_found = nongoose.find_by_id::<ProtectedActor>(&johnny_depp.id).await;
+  assert!(johnny_depp_found.is_ok());
+  assert!(johnny_depp_found.unwrap().is_none());
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn remove_modified_blocking() {
+  let nongoose = get_blocking_instance();
+
+  let tickets = [Ticket::new(0), Ticket::new(1)];
+  for ticket in tickets.iter() {
+    assert!(nongoose.create(ticket).is_ok());
+  }
+
+  // The second ticket does not match the conditions after `before_delete()`, so it is not removed
+  let result = nongoose.find_and_remove::<Ticket>(
+    doc! {
+      "_id": { "$in": [tickets[0].id, tickets[1].id] },
+      "attempts": { "$lt": 2 },
+    },
+    Some(FindOptions::builder().sort(doc! { "attempts": 1 }).build()),
+  );
+  assert!(result.is_ok());
+
+  let result = result.unwrap();
+  assert_eq!(result.len(), 2);
+  assert!(result[0].0);
+  assert_eq!(result[0].1.id, tickets[0].id);
+  assert!(!result[1].0);
+  assert_eq!(result[1].1.id, tickets[1].id);
+
+  let ticket_found = nongoose.find_by_id::<Ticket>(&tickets[0].id);
+  assert!(ticket_found.is_ok());
+  assert!(ticket_found.unwrap().is_none());
+
+  let ticket_found = nongoose.find_by_id::<Ticket>(&tickets[1].id);
+  assert!(ticket_found.is_ok());
+  assert_eq!(ticket_found.unwrap().unwrap().attempts, 2);
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn remove_modified() {
+  let nongoose = get_instance().await;
+
+  let tickets = [Ticket::new(0), Ticket::new(1)];
+  for ticket in tickets.iter() {
+    assert!(nongoose.create(ticket).await.is_ok());
+  }
+
+  // The second ticket does not match the conditions after `before_delete()`, so it is not removed
+  let result = nongoose
+    .find_and_remove::<Ticket>(
+      doc! {
+        "_id": { "$in": [tickets[0].id, tickets[1].id] },
+        "attempts": { "$lt": 2 },
+      },
+      Some(FindOptions::builder().sort(doc! { "attempts": 1 }).build()),
+    )
+    .await;
+  assert!(result.is_ok());
+
+  let result = result.unwrap();
+  assert_eq!(result.len(), 2);
+  assert!(result[0].0);
+  assert_eq!(result[0].1.id, tickets[0].id);
+  assert!(!result[1].0);
+  assert_eq!(result[1].1.id, tickets[1].id);
+
+  let ticket_found = nongoose.find_by_id::<Ticket>(&tickets[0].id).await;
+  assert!(ticket_found.is_ok());
+  assert!(ticket_found.unwrap().is_none());
+
+  let ticket_found = nongoose.find_by_id::<Ticket>(&tickets[1].id).await;
+  assert!(ticket_found.is_ok());
+  assert_eq!(ticket_found.unwrap().unwrap().attempts, 2);
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn remove_concurrently_blocking() {
+  let nongoose = get_blocking_instance();
+
+  let claims = [Claim::new(false), Claim::new(true)];
+  for claim in claims.iter() {
+    assert!(nongoose.create(claim).is_ok());
+  }
+
+  // The taken claim is removed by someone else, so it is not removed by this call
+  let result = nongoose.find_and_remove::<Claim>(
+    doc! { "_id": { "$in": [claims[0].id, claims[1].id] } },
+    Some(FindOptions::builder().sort(doc! { "taken": 1 }).build()),
+  );
+  assert!(result.is_ok());
+
+  let result = result.unwrap();
+  assert_eq!(result.len(), 2);
+  assert!(result[0].0);
+  assert_eq!(result[0].1.events, vec![String::from("deleted")]);
+  assert!(!result[1].0);
+  assert!(result[1].1.events.is_empty());
+
+  let count = nongoose.count::<Claim>(
+    doc! { "_id": { "$in": [claims[0].id, claims[1].id] } },
+    None,
+  );
+  assert!(count.is_ok());
+  assert_eq!(count.unwrap(), 0);
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn remove_concurrently() {
+  let nongoose = get_instance().await;
+
+  let claims = [Claim::new(false), Claim::new(true)];
+  for claim in claims.iter() {
+    assert!(nongoose.create(claim).await.is_ok());
+  }
+
+  // The taken claim is removed by someone else, so it is not removed by this call
+  let result = nongoose
+    .find_and_remove::<Claim>(
+      doc! { "_id": { "$in": [claims[0].id, claims[1].id] } },
+      Some(FindOptions::builder().sort(doc! { "taken": 1 }).build()),
+    )
+    .await;
+  assert!(result.is_ok());
+
+  let result = result.unwrap();
+  assert_eq!(result.len(), 2);
+  assert!(result[0].0);
+  assert_eq!(result[0].1.events, vec![String::from("deleted")]);
+  assert!(!result[1].0);
+  assert!(result[1].1.events.is_empty());
+
+  let count = nongoose
+    .count::<Claim>(
+      doc! { "_id": { "$in": [claims[0].id, claims[1].id] } },
+      None,
+    )
+    .await;
+  assert!(count.is_ok());
+  assert_eq!(count.unwrap(), 0);
+}
@@ -0,0 +1,214 @@
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{bson::oid::ObjectId, Schema};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, HookContext, Nongoose, SchemaAfter, SchemaBefore};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "accounts_schema_after", after_hooks)]
+struct Account {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub username: String,
+  pub email: String,
+  pub events: Vec<String>,
+}
+
+impl Account {
+  pub fn new(username: &str, email: &str) -> Self {
+    Self {
+      id: ObjectId::new(),
+      username: String::from(username),
+      email: String::from(email),
+      events: Vec::new(),
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Account {}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaAfter for Account {
+  fn after_create(&mut self, _ctx: &mut blocking::HookContext<'_>) -> nongoose::Result<()> {
+    self.events.push(String::from("created"));
+    Ok(())
+  }
+
+  fn after_delete(&mut self, _ctx: &mut blocking::HookContext<'_>) -> nongoose::Result<()> {
+    self.events.push(String::from("deleted"));
+    Ok(())
+  }
+
+  fn after_find(&mut self, _ctx: &mut blocking::HookContext<'_>) -> nongoose::Result<()> {
+    self.email = self.email.to_lowercase();
+    Ok(())
+  }
+
+  fn after_update(&mut self, _ctx: &mut blocking::HookContext<'_>) -> nongoose::Result<()> {
+    self.events.push(String::from("updated"));
+    Ok(())
+  }
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Account {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaAfter for Account {
+  async fn after_create(&mut self, _ctx: &mut HookContext<'_>) -> nongoose::Result<()> {
+    self.events.push(String::from("created"));
+    Ok(())
+  }
+
+  async fn after_delete(&mut self, _ctx: &mut HookContext<'_>) -> nongoose::Result<()> {
+    self.events.push(String::from("deleted"));
+    Ok(())
+  }
+
+  async fn after_find(&mut self, _ctx: &mut HookContext<'_>) -> nongoose::Result<()> {
+    self.email = self.email.to_lowercase();
+    Ok(())
+  }
+
+  async fn after_update(&mut self, _ctx: &mut HookContext<'_>) -> nongoose::Result<()> {
+    self.events.push(String::from("updated"));
+    Ok(())
+  }
+}
+
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> blocking::Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  blocking::Nongoose::builder(client.database("nongoose_blocking"))
+    .add_schema::<Account>()
+    .build()
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match Client::with_uri_str(&url).await {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  Nongoose::builder(client.database("nongoose"))
+    .add_schema::<Account>()
+    .build()
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn schema_after_blocking() {
+  let nongoose = get_blocking_instance();
+
+  let mut account = Account::new("nongoose", "NonGoose@Example.com");
+
+  // After create
+  let account = nongoose.save(&mut account);
+  assert!(account.is_ok());
+
+  let account = account.unwrap();
+  assert_eq!(account.events, vec![String::from("created")]);
+
+  // After find
+  let found = nongoose.find_by_id::<Account>(&account.id);
+  assert!(found.is_ok());
+
+  let found = found.unwrap();
+  assert!(found.is_some());
+
+  let mut found = found.unwrap();
+  assert_eq!(found.email, String::from("nongoose@example.com"));
+  assert!(found.events.is_empty());
+
+  // After update
+  let updated = nongoose.save(&mut found);
+  assert!(updated.is_ok());
+  assert_eq!(updated.unwrap().events, vec![String::from("updated")]);
+
+  // After delete
+  let removed = nongoose.find_by_id_and_remove::<Account>(&account.id);
+  assert!(removed.is_ok());
+
+  let (removed_result, removed_account) = removed.unwrap();
+  assert!(removed_result);
+  assert!(removed_account.is_some());
+
+  let removed_account = removed_account.unwrap();
+  assert_eq!(removed_account.email, String::from("nongoose@example.com"));
+  assert_eq!(removed_account.events, vec![String::from("deleted")]);
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn schema_after() {
+  let nongoose = get_instance().await;
+
+  let mut account = Account::new("nongoose", "NonGoose@Example.com");
+
+  // After create
+  let account = nongoose.save(&mut account).await;
+  assert!(account.is_ok());
+
+  let account = account.unwrap();
+  assert_eq!(account.events, vec![String::from("created")]);
+
+  // After find
+  let found = nongoose.find_by_id::<Account>(&account.id).await;
+  assert!(found.is_ok());
+
+  let found = found.unwrap();
+  assert!(found.is_some());
+
+  let mut found = found.unwrap();
+  assert_eq!(found.email, String::from("nongoose@example.com"));
+  assert!(found.events.is_empty());
+
+  // After update
+  let updated = nongoose.save(&mut found).await;
+  assert!(updated.is_ok());
+  assert_eq!(updated.unwrap().events, vec![String::from("updated")]);
+
+  // After delete
+  let removed = nongoose.find_by_id_and_remove::<Account>(&account.id).await;
+  assert!(removed.is_ok());
+
+  let (removed_result, removed_account) = removed.unwrap();
+  assert!(removed_result);
+  assert!(removed_account.is_some());
+
+  let removed_account = removed_account.unwrap();
+  assert_eq!(removed_account.email, String::from("nongoose@example.com"));
+  assert_eq!(removed_account.events, vec![String::from("deleted")]);
+}
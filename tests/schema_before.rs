@@ -1,6 +1,11 @@
-use nongoose::{bson::oid::ObjectId, Client, Database, Nongoose, Schema, SchemaBefore};
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{bson::oid::ObjectId, Schema};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, HookContext, Nongoose, SchemaBefore};
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "tokio-runtime")]
 #[derive(Clone, Debug, Deserialize, Schema, Serialize)]
 struct User {
   #[schema(id, unique)]
@@ -11,6 +16,18 @@ struct User {
   pub password: String,
 }
 
+#[cfg(feature = "sync")]
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+struct BlockingUser {
+  #[schema(id, unique)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  #[schema(unique)]
+  pub username: String,
+  pub password: String,
+}
+
+#[cfg(feature = "tokio-runtime")]
 impl User {
   pub fn new(username: &str, password: &str) -> Self {
     Self {
@@ -36,23 +53,74 @@ impl User {
   }
 }
 
-#[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
-impl SchemaBefore for User {
-  #[cfg(feature = "sync")]
-  fn before_create(&mut self, _db: &Database) -> nongoose::Result<()> {
+#[cfg(feature = "sync")]
+impl BlockingUser {
+  pub fn new(username: &str, password: &str) -> Self {
+    Self {
+      id: ObjectId::new(),
+      username: String::from(username),
+      password: String::from(password),
+    }
+  }
+
+  fn change_password(&mut self) {
+    self.password = self
+      .password
+      .chars()
+      .map(|c| {
+        let case = if c.is_uppercase() { 'A' } else { 'a' } as u8;
+        if c.is_alphabetic() {
+          (((c as u8 - case + 3) % 26) + case) as char
+        } else {
+          c
+        }
+      })
+      .collect::<String>();
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for BlockingUser {
+  fn before_create(&mut self, _ctx: &mut blocking::HookContext<'_>) -> nongoose::Result<()> {
     self.change_password();
     Ok(())
   }
+}
 
-  #[cfg(feature = "tokio-runtime")]
-  async fn before_create(&mut self, _db: &Database) -> nongoose::Result<()> {
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for User {
+  async fn before_create(&mut self, _ctx: &mut HookContext<'_>) -> nongoose::Result<()> {
     self.change_password();
     Ok(())
   }
 }
 
-#[cfg(test)]
-fn get_instance() -> Nongoose {
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> blocking::Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  blocking::Nongoose::builder(client.database("nongoose"))
+    .add_schema::<BlockingUser>()
+    .build()
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> Nongoose {
   // Get database url.
   let url = match std::env::var("DATABASE_URL") {
     Ok(url) => url,
@@ -62,7 +130,7 @@ fn get_instance() -> Nongoose {
   };
 
   // Get MongoDB connection.
-  let client = match Client::with_uri_str(&url) {
+  let client = match Client::with_uri_str(&url).await {
     Ok(client) => client,
     Err(e) => {
       panic!("Error connecting to the database: {}", e);
@@ -76,18 +144,18 @@ fn get_instance() -> Nongoose {
 
 #[cfg(feature = "sync")]
 #[cfg_attr(feature = "sync", test)]
-fn schema_before_create() {
-  let nongoose = get_instance();
+fn schema_before_create_blocking() {
+  let nongoose = get_blocking_instance();
 
-  let mut user = User::new("nongoose", "password");
+  let mut user = BlockingUser::new("nongoose", "password");
 
-  let user = user.save();
+  let user = nongoose.save(&mut user);
   assert!(user.is_ok());
 
   let user = user.unwrap();
   assert_eq!(user.password, String::from("sdvvzrug"));
 
-  let check_user = nongoose.find_by_id::<User>(&user.id);
+  let check_user = nongoose.find_by_id::<BlockingUser>(&user.id);
   assert!(check_user.is_ok());
 
   let check_user = check_user.unwrap();
@@ -100,11 +168,11 @@ fn schema_before_create() {
 #[cfg(feature = "tokio-runtime")]
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 async fn schema_before_create() {
-  let nongoose = get_instance();
+  let nongoose = get_instance().await;
 
   let mut user = User::new("nongoose", "password");
 
-  let user = user.save().await;
+  let user = nongoose.save(&mut user).await;
   assert!(user.is_ok());
 
   let user = user.unwrap();
@@ -1,4 +1,8 @@
-use nongoose::{bson::oid::ObjectId, Client, Nongoose, Schema, SchemaBefore};
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{bson::oid::ObjectId, Schema};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, Nongoose, SchemaBefore};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
@@ -18,7 +22,11 @@ pub struct Animal {
   pub name: String,
 }
 
-#[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Animal {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
 impl SchemaBefore for Animal {}
 
 impl Animal {
@@ -31,8 +39,31 @@ impl Animal {
   }
 }
 
-#[cfg(test)]
-fn get_instance() -> Nongoose {
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> blocking::Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  blocking::Nongoose::builder(client.database("nongoose_blocking"))
+    .add_schema::<Animal>()
+    .build()
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> Nongoose {
   // Get database url.
   let url = match std::env::var("DATABASE_URL") {
     Ok(url) => url,
@@ -42,7 +73,7 @@ fn get_instance() -> Nongoose {
   };
 
   // Get MongoDB connection.
-  let client = match Client::with_uri_str(&url) {
+  let client = match Client::with_uri_str(&url).await {
     Ok(client) => client,
     Err(e) => {
       panic!("Error connecting to the database: {}", e);
@@ -56,18 +87,18 @@ fn get_instance() -> Nongoose {
 
 #[cfg(feature = "sync")]
 #[cfg_attr(feature = "sync", test)]
-fn schema_remove() {
-  let nongoose = get_instance();
+fn schema_remove_blocking() {
+  let nongoose = get_blocking_instance();
 
   let mut dog = Animal::new(AnimalType::Dog, "dog");
 
-  let dog_saved = dog.save();
+  let dog_saved = nongoose.save(&mut dog);
   assert!(dog_saved.is_ok());
 
   let dog_saved = dog_saved.unwrap();
   assert_eq!(dog, dog_saved);
 
-  let dog_removed = dog_saved.remove();
+  let dog_removed = nongoose.remove(&dog_saved);
   assert!(dog_removed.is_ok());
   assert!(dog_removed.unwrap());
 
@@ -79,17 +110,17 @@ fn schema_remove() {
 #[cfg(feature = "tokio-runtime")]
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 async fn schema_remove() {
-  let nongoose = get_instance();
+  let nongoose = get_instance().await;
 
   let mut dog = Animal::new(AnimalType::Dog, "dog");
 
-  let dog_saved = dog.save().await;
+  let dog_saved = nongoose.save(&mut dog).await;
   assert!(dog_saved.is_ok());
 
   let dog_saved = dog_saved.unwrap();
   assert_eq!(dog, dog_saved);
 
-  let dog_removed = dog_saved.remove().await;
+  let dog_removed = nongoose.remove(&dog_saved).await;
   assert!(dog_removed.is_ok());
   assert!(dog_removed.unwrap());
 
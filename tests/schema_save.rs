@@ -1,4 +1,8 @@
-use nongoose::{bson::oid::ObjectId, Client, Nongoose, Schema, SchemaBefore};
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{bson::oid::ObjectId, Schema};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, Nongoose, SchemaBefore};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
@@ -8,6 +12,7 @@ pub enum AnimalType {
   Dog,
 }
 
+#[cfg(feature = "tokio-runtime")]
 #[derive(Clone, Debug, Deserialize, PartialEq, Schema, Serialize)]
 pub struct Animal {
   #[schema(id, unique)]
@@ -19,9 +24,26 @@ pub struct Animal {
   pub name: String,
 }
 
-#[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
+#[cfg(feature = "sync")]
+#[derive(Clone, Debug, Deserialize, PartialEq, Schema, Serialize)]
+pub struct BlockingAnimal {
+  #[schema(id, unique)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  #[serde(rename = "type")]
+  pub animal_type: AnimalType,
+  #[schema(unique)]
+  pub name: String,
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for BlockingAnimal {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
 impl SchemaBefore for Animal {}
 
+#[cfg(feature = "tokio-runtime")]
 impl Animal {
   pub fn new(animal_type: AnimalType, name: &str) -> Self {
     Self {
@@ -32,8 +54,19 @@ impl Animal {
   }
 }
 
-#[cfg(test)]
-fn get_instance() -> Nongoose {
+#[cfg(feature = "sync")]
+impl BlockingAnimal {
+  pub fn new(animal_type: AnimalType, name: &str) -> Self {
+    Self {
+      id: ObjectId::new(),
+      animal_type,
+      name: String::from(name),
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> blocking::Nongoose {
   // Get database url.
   let url = match std::env::var("DATABASE_URL") {
     Ok(url) => url,
@@ -43,7 +76,30 @@ fn get_instance() -> Nongoose {
   };
 
   // Get MongoDB connection.
-  let client = match Client::with_uri_str(&url) {
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  blocking::Nongoose::builder(client.database("nongoose"))
+    .add_schema::<BlockingAnimal>()
+    .build()
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match Client::with_uri_str(&url).await {
     Ok(client) => client,
     Err(e) => {
       panic!("Error connecting to the database: {}", e);
@@ -57,18 +113,18 @@ fn get_instance() -> Nongoose {
 
 #[cfg(feature = "sync")]
 #[cfg_attr(feature = "sync", test)]
-fn schema_save() {
-  let _nongoose = get_instance();
+fn schema_save_blocking() {
+  let nongoose = get_blocking_instance();
 
-  let mut dog = Animal::new(AnimalType::Dog, "dog");
+  let mut dog = BlockingAnimal::new(AnimalType::Dog, "dog");
 
-  let dog_saved = dog.save();
+  let dog_saved = nongoose.save(&mut dog);
   assert!(dog_saved.is_ok());
 
   let mut dog_saved = dog_saved.unwrap();
   assert_eq!(dog, dog_saved);
 
-  let duplicated_dog = Animal::new(AnimalType::Dog, "dog").save();
+  let duplicated_dog = nongoose.create(&BlockingAnimal::new(AnimalType::Dog, "dog"));
   assert!(duplicated_dog.is_err());
   assert_eq!(
     format!("{}", duplicated_dog.unwrap_err()),
@@ -76,14 +132,14 @@ fn schema_save() {
   );
 
   dog_saved.name = String::from("dog1");
-  assert!(dog_saved.save().is_ok());
+  assert!(nongoose.save(&mut dog_saved).is_ok());
 
   dog_saved.name = String::from("dog1");
-  assert!(dog_saved.save().is_ok());
+  assert!(nongoose.save(&mut dog_saved).is_ok());
 
-  let mut cat = Animal::new(AnimalType::Cat, "cat");
+  let mut cat = BlockingAnimal::new(AnimalType::Cat, "cat");
 
-  let cat_saved = cat.save();
+  let cat_saved = nongoose.save(&mut cat);
   assert!(cat_saved.is_ok());
   assert_eq!(cat, cat_saved.unwrap());
 }
@@ -91,17 +147,17 @@ fn schema_save() {
 #[cfg(feature = "tokio-runtime")]
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 async fn schema_save() {
-  let _nongoose = get_instance();
+  let nongoose = get_instance().await;
 
   let mut dog = Animal::new(AnimalType::Dog, "dog");
 
-  let dog_saved = dog.save().await;
+  let dog_saved = nongoose.save(&mut dog).await;
   assert!(dog_saved.is_ok());
 
   let mut dog_saved = dog_saved.unwrap();
   assert_eq!(dog, dog_saved);
 
-  let duplicated_dog = Animal::new(AnimalType::Dog, "dog").save().await;
+  let duplicated_dog = nongoose.create(&Animal::new(AnimalType::Dog, "dog")).await;
   assert!(duplicated_dog.is_err());
   assert_eq!(
     format!("{}", duplicated_dog.unwrap_err()),
@@ -109,13 +165,13 @@ async fn schema_save() {
   );
 
   dog_saved.name = String::from("dog1");
-  assert!(dog_saved.save().await.is_ok());
+  assert!(nongoose.save(&mut dog_saved).await.is_ok());
 
   dog_saved.name = String::from("dog1");
-  assert!(dog_saved.save().await.is_ok());
+  assert!(nongoose.save(&mut dog_saved).await.is_ok());
 
   let mut cat = Animal::new(AnimalType::Cat, "cat");
-  let cat_saved = cat.save().await;
+  let cat_saved = nongoose.save(&mut cat).await;
 
   assert!(cat_saved.is_ok());
   assert_eq!(cat, cat_saved.unwrap());
@@ -0,0 +1,229 @@
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{
+  bson::{doc, oid::ObjectId, Document},
+  options::FindOptions,
+  Schema,
+};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, HookContext, Nongoose, SchemaAfter, SchemaBefore};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "tokio-runtime")]
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "actors_stream", after_hooks)]
+struct Actor {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub fullname: String,
+  pub age: i64,
+}
+
+#[cfg(feature = "sync")]
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "actors_stream_blocking", after_hooks)]
+struct BlockingActor {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub fullname: String,
+  pub age: i64,
+}
+
+#[cfg(feature = "tokio-runtime")]
+impl Actor {
+  pub fn new(fullname: &str, age: i64) -> Self {
+    Self {
+      id: ObjectId::new(),
+      fullname: String::from(fullname),
+      age,
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl BlockingActor {
+  pub fn new(fullname: &str, age: i64) -> Self {
+    Self {
+      id: ObjectId::new(),
+      fullname: String::from(fullname),
+      age,
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for BlockingActor {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Actor {}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaAfter for BlockingActor {
+  fn after_find(&mut self, _ctx: &mut blocking::HookContext<'_>) -> nongoose::Result<()> {
+    self.fullname = self.fullname.to_uppercase();
+    Ok(())
+  }
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaAfter for Actor {
+  async fn after_find(&mut self, _ctx: &mut HookContext<'_>) -> nongoose::Result<()> {
+    self.fullname = self.fullname.to_uppercase();
+    Ok(())
+  }
+}
+
+#[derive(Debug)]
+struct AgeGroup {
+  age: i64,
+  total: i32,
+}
+
+impl From<Document> for AgeGroup {
+  fn from(document: Document) -> Self {
+    Self {
+      age: document.get_i64("_id").unwrap_or_default(),
+      total: document.get_i32("total").unwrap_or_default(),
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> blocking::Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  blocking::Nongoose::builder(client.database("nongoose"))
+    .add_schema::<BlockingActor>()
+    .build()
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match Client::with_uri_str(&url).await {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  Nongoose::builder(client.database("nongoose"))
+    .add_schema::<Actor>()
+    .build()
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn stream_blocking() {
+  let nongoose = get_blocking_instance();
+
+  for age in 0..250 {
+    assert!(nongoose
+      .create(&BlockingActor::new(
+        &format!("BlockingActor {}", age),
+        age % 5
+      ))
+      .is_ok());
+  }
+
+  // Find all the actors (more than one batch)
+  let actors = nongoose
+    .find_stream::<BlockingActor>(doc! {}, Some(FindOptions::builder().batch_size(50).build()));
+  assert!(actors.is_ok());
+
+  let mut total = 0;
+  for actor in actors.unwrap() {
+    assert!(actor.is_ok());
+    assert!(actor.unwrap().fullname.starts_with("ACTOR "));
+    total += 1;
+  }
+  assert_eq!(total, 250);
+
+  // Group the actors by age
+  let groups = nongoose.aggregate_stream::<BlockingActor, AgeGroup>(
+    vec![
+      doc! { "$group": { "_id": "$age", "total": { "$sum": 1 } } },
+      doc! { "$sort": { "_id": 1 } },
+    ],
+    None,
+  );
+  assert!(groups.is_ok());
+
+  let groups: Vec<AgeGroup> = groups.unwrap().map(|group| group.unwrap()).collect();
+  assert_eq!(groups.len(), 5);
+  assert_eq!(groups[0].age, 0);
+  assert_eq!(groups[0].total, 50);
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn stream() {
+  use futures::StreamExt;
+
+  let nongoose = get_instance().await;
+
+  for age in 0..250 {
+    assert!(nongoose
+      .create(&Actor::new(&format!("Actor {}", age), age % 5))
+      .await
+      .is_ok());
+  }
+
+  // Find all the actors (more than one batch)
+  let actors = nongoose
+    .find_stream::<Actor>(doc! {}, Some(FindOptions::builder().batch_size(50).build()))
+    .await;
+  assert!(actors.is_ok());
+
+  let mut actors = actors.unwrap();
+  let mut total = 0;
+  while let Some(actor) = actors.next().await {
+    assert!(actor.is_ok());
+    assert!(actor.unwrap().fullname.starts_with("ACTOR "));
+    total += 1;
+  }
+  assert_eq!(total, 250);
+
+  // Group the actors by age
+  let groups = nongoose
+    .aggregate_stream::<Actor, AgeGroup>(
+      vec![
+        doc! { "$group": { "_id": "$age", "total": { "$sum": 1 } } },
+        doc! { "$sort": { "_id": 1 } },
+      ],
+      None,
+    )
+    .await;
+  assert!(groups.is_ok());
+
+  let groups: Vec<AgeGroup> = groups.unwrap().map(|group| group.unwrap()).collect().await;
+  assert_eq!(groups.len(), 5);
+  assert_eq!(groups[0].age, 0);
+  assert_eq!(groups[0].total, 50);
+}
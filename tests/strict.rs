@@ -0,0 +1,97 @@
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{
+  bson::{doc, oid::ObjectId},
+  schema_relations, Error, Schema,
+};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, SchemaBefore};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "users_strict")]
+struct User {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub username: String,
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for User {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for User {}
+
+#[schema_relations]
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "posts_strict")]
+struct Post {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+
+  #[schema(many_to_one = "User")]
+  #[serde(skip_serializing)]
+  pub author: Option<User>,
+
+  pub title: String,
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Post {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Post {}
+
+fn assert_not_registered(error: Error, collection_name: &str) {
+  match error {
+    Error::SchemaNotRegistered { collection } => assert_eq!(collection, collection_name),
+    error => panic!("Unexpected error: {}", error),
+  }
+}
+
+// The MongoDB client connects lazily, so these tests do not need a database.
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn strict_blocking() {
+  let client = blocking::Client::with_uri_str("mongodb://localhost:27017").unwrap();
+
+  // `User` is used by the relation of `Post` but it is not registered
+  let builder =
+    blocking::Nongoose::builder(client.database("nongoose_blocking")).add_schema::<Post>();
+
+  let nongoose = builder.build_strict();
+  assert!(nongoose.is_err());
+  assert_not_registered(nongoose.err().unwrap(), "users_strict");
+
+  let count = builder.build().count::<User>(doc! {}, None);
+  assert!(count.is_err());
+  assert_not_registered(count.unwrap_err(), "users_strict");
+
+  assert!(builder.add_schema::<User>().build_strict().is_ok());
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn strict() {
+  let client = Client::with_uri_str("mongodb://localhost:27017")
+    .await
+    .unwrap();
+
+  // `User` is used by the relation of `Post` but it is not registered
+  let builder = nongoose::Nongoose::builder(client.database("nongoose")).add_schema::<Post>();
+
+  let nongoose = builder.build_strict();
+  assert!(nongoose.is_err());
+  assert_not_registered(nongoose.err().unwrap(), "users_strict");
+
+  let count = builder.build().count::<User>(doc! {}, None).await;
+  assert!(count.is_err());
+  assert_not_registered(count.unwrap_err(), "users_strict");
+
+  assert!(builder.add_schema::<User>().build_strict().is_ok());
+}
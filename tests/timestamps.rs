@@ -0,0 +1,259 @@
+use std::time::Duration;
+
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{
+  bson::{doc, oid::ObjectId, serde_helpers::chrono_datetime_as_bson_datetime, DateTime},
+  options::{FindOneAndUpdateOptions, ReturnDocument},
+  Schema,
+};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, Nongoose, SchemaBefore};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Schema, Serialize)]
+#[schema(name = "articles_timestamps", timestamps)]
+struct Article {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub title: String,
+  #[serde(rename = "createdAt")]
+  pub created_at: DateTime,
+  #[serde(rename = "updatedAt")]
+  pub updated_at: Option<DateTime>,
+}
+
+impl Article {
+  pub fn new(title: &str) -> Self {
+    Self {
+      id: ObjectId::new(),
+      title: String::from(title),
+      created_at: DateTime::MIN,
+      updated_at: None,
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Article {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Article {}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Schema, Serialize)]
+#[schema(
+  name = "comments_timestamps",
+  timestamps(created_at = "posted", updated_at = "edited")
+)]
+struct Comment {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub text: String,
+  #[serde(with = "chrono_datetime_as_bson_datetime")]
+  pub posted: chrono::DateTime<chrono::Utc>,
+  #[serde(with = "chrono_datetime_as_bson_datetime")]
+  pub edited: chrono::DateTime<chrono::Utc>,
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Comment {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Comment {}
+
+fn upsert_options() -> Option<FindOneAndUpdateOptions> {
+  Some(
+    FindOneAndUpdateOptions::builder()
+      .upsert(true)
+      .return_document(ReturnDocument::After)
+      .build(),
+  )
+}
+
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> blocking::Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  blocking::Nongoose::builder(client.database("nongoose_blocking"))
+    .add_schema::<Article>()
+    .add_schema::<Comment>()
+    .build()
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match Client::with_uri_str(&url).await {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  Nongoose::builder(client.database("nongoose"))
+    .add_schema::<Article>()
+    .add_schema::<Comment>()
+    .build()
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn timestamps_blocking() {
+  let nongoose = get_blocking_instance();
+
+  // The creation and update times are set when the document is created
+  let mut article = Article::new("Timestamps");
+  assert!(nongoose.save(&mut article).is_ok());
+  assert!(article.created_at > DateTime::MIN);
+  assert_eq!(article.updated_at, Some(article.created_at));
+
+  let created_at = article.created_at;
+  std::thread::sleep(Duration::from_millis(10));
+
+  // Only the update time changes when the document is replaced
+  article.title = String::from("Automatic timestamps");
+  assert!(nongoose.save(&mut article).is_ok());
+  assert_eq!(article.created_at, created_at);
+  assert!(article.updated_at.unwrap() > created_at);
+
+  let updated_at = article.updated_at.unwrap();
+  std::thread::sleep(Duration::from_millis(10));
+
+  // `$currentDate` in `update_many` and `find_one_and_update`
+  let result = nongoose.update_many::<Article>(
+    doc! { "_id": article.id },
+    doc! { "$set": { "title": "Updated" } },
+    None,
+  );
+  assert!(result.is_ok());
+
+  let article_found = nongoose.find_by_id::<Article>(&article.id);
+  assert!(article_found.is_ok());
+
+  let article_found = article_found.unwrap().unwrap();
+  assert_eq!(article_found.created_at, created_at);
+  assert!(article_found.updated_at.unwrap() > updated_at);
+
+  let result = nongoose.find_one_and_update::<Article>(
+    doc! { "_id": article.id },
+    doc! { "$set": { "title": "Updated again" } },
+    Some(
+      FindOneAndUpdateOptions::builder()
+        .return_document(ReturnDocument::After)
+        .build(),
+    ),
+  );
+  assert!(result.is_ok());
+
+  let article_updated = result.unwrap().unwrap();
+  assert_eq!(article_updated.created_at, created_at);
+  assert!(article_updated.updated_at.unwrap() > article_found.updated_at.unwrap());
+
+  // The creation time is set in the upserts, with the configured fields
+  let result = nongoose.find_one_and_update::<Comment>(
+    doc! { "_id": ObjectId::new() },
+    doc! { "$set": { "text": "Upserted" } },
+    upsert_options(),
+  );
+  assert!(result.is_ok());
+
+  let comment = result.unwrap().unwrap();
+  assert!(comment.edited >= comment.posted);
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn timestamps() {
+  let nongoose = get_instance().await;
+
+  // The creation and update times are set when the document is created
+  let mut article = Article::new("Timestamps");
+  assert!(nongoose.save(&mut article).await.is_ok());
+  assert!(article.created_at > DateTime::MIN);
+  assert_eq!(article.updated_at, Some(article.created_at));
+
+  let created_at = article.created_at;
+  tokio::time::sleep(Duration::from_millis(10)).await;
+
+  // Only the update time changes when the document is replaced
+  article.title = String::from("Automatic timestamps");
+  assert!(nongoose.save(&mut article).await.is_ok());
+  assert_eq!(article.created_at, created_at);
+  assert!(article.updated_at.unwrap() > created_at);
+
+  let updated_at = article.updated_at.unwrap();
+  tokio::time::sleep(Duration::from_millis(10)).await;
+
+  // `$currentDate` in `update_many` and `find_one_and_update`
+  let result = nongoose
+    .update_many::<Article>(
+      doc! { "_id": article.id },
+      doc! { "$set": { "title": "Updated" } },
+      None,
+    )
+    .await;
+  assert!(result.is_ok());
+
+  let article_found = nongoose.find_by_id::<Article>(&article.id).await;
+  assert!(article_found.is_ok());
+
+  let article_found = article_found.unwrap().unwrap();
+  assert_eq!(article_found.created_at, created_at);
+  assert!(article_found.updated_at.unwrap() > updated_at);
+
+  let result = nongoose
+    .find_one_and_update::<Article>(
+      doc! { "_id": article.id },
+      doc! { "$set": { "title": "Updated again" } },
+      Some(
+        FindOneAndUpdateOptions::builder()
+          .return_document(ReturnDocument::After)
+          .build(),
+      ),
+    )
+    .await;
+  assert!(result.is_ok());
+
+  let article_updated = result.unwrap().unwrap();
+  assert_eq!(article_updated.created_at, created_at);
+  assert!(article_updated.updated_at.unwrap() > article_found.updated_at.unwrap());
+
+  // The creation time is set in the upserts, with the configured fields
+  let result = nongoose
+    .find_one_and_update::<Comment>(
+      doc! { "_id": ObjectId::new() },
+      doc! { "$set": { "text": "Upserted" } },
+      upsert_options(),
+    )
+    .await;
+  assert!(result.is_ok());
+
+  let comment = result.unwrap().unwrap();
+  assert!(comment.edited >= comment.posted);
+}
@@ -0,0 +1,265 @@
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{
+  bson::{doc, oid::ObjectId, Document},
+  Error, Schema,
+};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, HookContext, Nongoose, SchemaBefore};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "authors_transaction")]
+struct Author {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub name: String,
+  pub posts: i32,
+}
+
+impl Author {
+  pub fn new(name: &str) -> Self {
+    Self {
+      id: ObjectId::new(),
+      name: String::from(name),
+      posts: 0,
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Author {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Author {}
+
+#[derive(Clone, Debug, Deserialize, Schema, Serialize)]
+#[schema(name = "posts_transaction")]
+struct Post {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub author_id: ObjectId,
+  pub title: String,
+}
+
+impl Post {
+  pub fn new(author_id: ObjectId, title: &str) -> Self {
+    Self {
+      id: ObjectId::new(),
+      author_id,
+      title: String::from(title),
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Post {
+  fn before_create(&mut self, ctx: &mut blocking::HookContext<'_>) -> nongoose::Result<()> {
+    let authors = ctx.database().collection::<Document>("authors_transaction");
+
+    if let Some(session) = ctx.session() {
+      authors.update_one_with_session(
+        doc! { "_id": self.author_id },
+        doc! { "$inc": { "posts": 1 } },
+        None,
+        session,
+      )?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Post {
+  async fn before_create(&mut self, ctx: &mut HookContext<'_>) -> nongoose::Result<()> {
+    let authors = ctx.database().collection::<Document>("authors_transaction");
+
+    if let Some(session) = ctx.session() {
+      authors
+        .update_one_with_session(
+          doc! { "_id": self.author_id },
+          doc! { "$inc": { "posts": 1 } },
+          None,
+          session,
+        )
+        .await?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> blocking::Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  blocking::Nongoose::builder(client.database("nongoose_blocking"))
+    .client(client)
+    .add_schema::<Author>()
+    .add_schema::<Post>()
+    .build()
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match Client::with_uri_str(&url).await {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  Nongoose::builder(client.database("nongoose"))
+    .client(client)
+    .add_schema::<Author>()
+    .add_schema::<Post>()
+    .build()
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn transaction_blocking() {
+  let nongoose = get_blocking_instance();
+
+  let author = nongoose.create(&Author::new("Daniel"));
+  assert!(author.is_ok());
+
+  let author = author.unwrap();
+
+  let post = Post::new(author.id, "Transactions");
+  let result = nongoose.transaction(|tx| tx.create(&post));
+  assert!(result.is_ok());
+
+  // The before_create hook updated the author inside the transaction
+  let author = nongoose.find_by_id::<Author>(&author.id);
+  assert!(author.is_ok());
+
+  let author = author.unwrap();
+  assert!(author.is_some());
+  assert_eq!(author.unwrap().posts, 1);
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn transaction() {
+  let nongoose = get_instance().await;
+
+  let author = nongoose.create(&Author::new("Daniel")).await;
+  assert!(author.is_ok());
+
+  let author = author.unwrap();
+
+  let post = Post::new(author.id, "Transactions");
+  let result = nongoose
+    .transaction(|tx| {
+      let post = post.clone();
+      async move { tx.create(&post).await }
+    })
+    .await;
+  assert!(result.is_ok());
+
+  // The before_create hook updated the author inside the transaction
+  let author = nongoose.find_by_id::<Author>(&author.id).await;
+  assert!(author.is_ok());
+
+  let author = author.unwrap();
+  assert!(author.is_some());
+  assert_eq!(author.unwrap().posts, 1);
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn transaction_abort_blocking() {
+  let nongoose = get_blocking_instance();
+
+  let author = Author::new("Aborted");
+  let result = nongoose.transaction(|tx| -> nongoose::Result<()> {
+    tx.create(&author)?;
+    Err(Error::NoImplemented)
+  });
+  assert!(matches!(result, Err(Error::NoImplemented)));
+
+  // The author was not created
+  let author = nongoose.find_by_id::<Author>(&author.id);
+  assert!(author.is_ok());
+  assert!(author.unwrap().is_none());
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn transaction_abort() {
+  let nongoose = get_instance().await;
+
+  let author = Author::new("Aborted");
+  let result = nongoose
+    .transaction(|tx| {
+      let author = author.clone();
+      async move {
+        tx.create(&author).await?;
+        Err::<(), _>(Error::NoImplemented)
+      }
+    })
+    .await;
+  assert!(matches!(result, Err(Error::NoImplemented)));
+
+  // The author was not created
+  let author = nongoose.find_by_id::<Author>(&author.id).await;
+  assert!(author.is_ok());
+  assert!(author.unwrap().is_none());
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn transaction_without_client_blocking() {
+  let client = blocking::Client::with_uri_str("mongodb://localhost:27017/").unwrap();
+  let nongoose = blocking::Nongoose::builder(client.database("nongoose_blocking"))
+    .add_schema::<Author>()
+    .build();
+
+  let result = nongoose.transaction(|tx| tx.count::<Author>(doc! {}, None));
+  assert!(matches!(result, Err(Error::ClientNotSet)));
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn transaction_without_client() {
+  let client = Client::with_uri_str("mongodb://localhost:27017/")
+    .await
+    .unwrap();
+  let nongoose = Nongoose::builder(client.database("nongoose"))
+    .add_schema::<Author>()
+    .build();
+
+  let result = nongoose
+    .transaction(|tx| async move { tx.count::<Author>(doc! {}, None).await })
+    .await;
+  assert!(matches!(result, Err(Error::ClientNotSet)));
+}
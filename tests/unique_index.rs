@@ -0,0 +1,221 @@
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{
+  bson::{doc, oid::ObjectId},
+  Error, Schema,
+};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, Nongoose, SchemaBefore};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Schema, Serialize)]
+#[schema(name = "accounts_unique_index")]
+struct Account {
+  #[schema(id, unique)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  #[schema(unique)]
+  #[serde(rename = "emailAddress")]
+  pub email: String,
+  pub name: String,
+}
+
+impl Account {
+  pub fn new(email: &str, name: &str) -> Self {
+    Self {
+      id: ObjectId::new(),
+      email: String::from(email),
+      name: String::from(name),
+    }
+  }
+}
+
+// Its indexes are created by `auto_index`
+#[derive(Clone, Debug, Deserialize, PartialEq, Schema, Serialize)]
+#[schema(name = "profiles_unique_index")]
+struct Profile {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  #[schema(unique)]
+  pub username: String,
+}
+
+impl Profile {
+  pub fn new(username: &str) -> Self {
+    Self {
+      id: ObjectId::new(),
+      username: String::from(username),
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Account {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Account {}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Profile {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Profile {}
+
+fn is_duplicated_username(error: &Error, username: &str) -> bool {
+  matches!(
+    error,
+    Error::DuplicatedSchemaField(field, value) if field == "username" && value == username
+  )
+}
+
+fn is_duplicated_email(error: &Error, email: &str) -> bool {
+  matches!(
+    error,
+    Error::DuplicatedSchemaField(field, value) if field == "emailAddress" && value == email
+  )
+}
+
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> blocking::Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  blocking::Nongoose::builder(client.database("nongoose_blocking"))
+    .add_schema::<Account>()
+    .add_schema::<Profile>()
+    .unique_pre_check(false)
+    .auto_index(true)
+    .build()
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match Client::with_uri_str(&url).await {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  Nongoose::builder(client.database("nongoose"))
+    .add_schema::<Account>()
+    .add_schema::<Profile>()
+    .unique_pre_check(false)
+    .auto_index(true)
+    .build()
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn unique_index_blocking() {
+  let nongoose = get_blocking_instance();
+  assert!(nongoose.ensure_indexes::<Account>().is_ok());
+
+  let email = format!("{}@nongoose.com", ObjectId::new());
+
+  let mut account = Account::new(&email, "Daniel");
+  assert!(nongoose.save(&mut account).is_ok());
+
+  // The account can be saved again
+  account.name = String::from("Daniel Solarte");
+  assert!(nongoose.save(&mut account).is_ok());
+
+  // The unique index rejects the duplicated email
+  let duplicated = nongoose.create(&Account::new(&email, "Other"));
+  assert!(duplicated.is_err());
+  assert!(is_duplicated_email(&duplicated.unwrap_err(), &email));
+
+  let other = Account::new(&format!("{}@nongoose.com", ObjectId::new()), "Other");
+  assert!(nongoose.create(&other).is_ok());
+
+  let updated = nongoose.find_by_id_and_update::<Account>(
+    &other.id,
+    doc! { "$set": { "emailAddress": &email } },
+    None,
+  );
+  assert!(updated.is_err());
+  assert!(is_duplicated_email(&updated.unwrap_err(), &email));
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn unique_index() {
+  let nongoose = get_instance().await;
+  assert!(nongoose.ensure_indexes::<Account>().await.is_ok());
+
+  let email = format!("{}@nongoose.com", ObjectId::new());
+
+  let mut account = Account::new(&email, "Daniel");
+  assert!(nongoose.save(&mut account).await.is_ok());
+
+  // The account can be saved again
+  account.name = String::from("Daniel Solarte");
+  assert!(nongoose.save(&mut account).await.is_ok());
+
+  // The unique index rejects the duplicated email
+  let duplicated = nongoose.create(&Account::new(&email, "Other")).await;
+  assert!(duplicated.is_err());
+  assert!(is_duplicated_email(&duplicated.unwrap_err(), &email));
+
+  let other = Account::new(&format!("{}@nongoose.com", ObjectId::new()), "Other");
+  assert!(nongoose.create(&other).await.is_ok());
+
+  let updated = nongoose
+    .find_by_id_and_update::<Account>(&other.id, doc! { "$set": { "emailAddress": &email } }, None)
+    .await;
+  assert!(updated.is_err());
+  assert!(is_duplicated_email(&updated.unwrap_err(), &email));
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn unique_index_auto_index_blocking() {
+  let nongoose = get_blocking_instance();
+  let username = ObjectId::new().to_hex();
+
+  // The first operation that uses the Schema creates the unique index (`auto_index`)
+  assert!(nongoose.create(&Profile::new(&username)).is_ok());
+
+  let duplicated = nongoose.create(&Profile::new(&username));
+  assert!(duplicated.is_err());
+  assert!(is_duplicated_username(&duplicated.unwrap_err(), &username));
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn unique_index_auto_index() {
+  let nongoose = get_instance().await;
+  let username = ObjectId::new().to_hex();
+
+  // The first operation that uses the Schema creates the unique index (`auto_index`)
+  assert!(nongoose.create(&Profile::new(&username)).await.is_ok());
+
+  let duplicated = nongoose.create(&Profile::new(&username)).await;
+  assert!(duplicated.is_err());
+  assert!(is_duplicated_username(&duplicated.unwrap_err(), &username));
+}
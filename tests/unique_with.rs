@@ -0,0 +1,168 @@
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{bson::oid::ObjectId, Error, Schema};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, Nongoose, SchemaBefore};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Schema, Serialize)]
+#[schema(
+  name = "members_unique_with",
+  unique(fields = "tenant_id,username", name = "tenant_username")
+)]
+struct Member {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  #[serde(rename = "tenantId")]
+  pub tenant_id: ObjectId,
+  #[schema(unique_with = "tenant_id")]
+  pub email: String,
+  pub username: String,
+}
+
+impl Member {
+  pub fn new(tenant_id: ObjectId, email: &str, username: &str) -> Self {
+    Self {
+      id: ObjectId::new(),
+      tenant_id,
+      email: String::from(email),
+      username: String::from(username),
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Member {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Member {}
+
+fn assert_duplicated(error: Error, fields: &str, values: &str) {
+  match error {
+    Error::DuplicatedSchemaField(field, value) => {
+      assert_eq!(field, fields);
+      assert_eq!(value, values);
+    }
+    error => panic!("Unexpected error: {}", error),
+  }
+}
+
+#[cfg(feature = "sync")]
+fn get_blocking_instance(unique_pre_check: bool) -> blocking::Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  blocking::Nongoose::builder(client.database("nongoose_blocking"))
+    .add_schema::<Member>()
+    .unique_pre_check(unique_pre_check)
+    .build()
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance(unique_pre_check: bool) -> Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match Client::with_uri_str(&url).await {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  Nongoose::builder(client.database("nongoose"))
+    .add_schema::<Member>()
+    .unique_pre_check(unique_pre_check)
+    .build()
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn unique_with_blocking() {
+  let nongoose = get_blocking_instance(true);
+  assert!(nongoose.ensure_indexes::<Member>().is_ok());
+
+  let tenant_id = ObjectId::new();
+  let mut member = Member::new(tenant_id, "daniel@nongoose.com", "daniel");
+  assert!(nongoose.save(&mut member).is_ok());
+
+  // The same email and username in another tenant
+  let mut other_tenant = Member::new(ObjectId::new(), "daniel@nongoose.com", "daniel");
+  assert!(nongoose.save(&mut other_tenant).is_ok());
+
+  // Checked before saving
+  let mut duplicated = Member::new(tenant_id, "daniel@nongoose.com", "solarte");
+  let error = nongoose.save(&mut duplicated).unwrap_err();
+  assert_duplicated(
+    error,
+    "email, tenantId",
+    &format!("daniel@nongoose.com, {}", tenant_id),
+  );
+
+  // Rejected by the compound unique index
+  let nongoose = get_blocking_instance(false);
+
+  let mut duplicated = Member::new(tenant_id, "solarte@nongoose.com", "daniel");
+  let error = nongoose.save(&mut duplicated).unwrap_err();
+  assert_duplicated(
+    error,
+    "tenantId, username",
+    &format!("ObjectId('{}'), daniel", tenant_id),
+  );
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn unique_with() {
+  let nongoose = get_instance(true).await;
+  assert!(nongoose.ensure_indexes::<Member>().await.is_ok());
+
+  let tenant_id = ObjectId::new();
+  let mut member = Member::new(tenant_id, "daniel@nongoose.com", "daniel");
+  assert!(nongoose.save(&mut member).await.is_ok());
+
+  // The same email and username in another tenant
+  let mut other_tenant = Member::new(ObjectId::new(), "daniel@nongoose.com", "daniel");
+  assert!(nongoose.save(&mut other_tenant).await.is_ok());
+
+  // Checked before saving
+  let mut duplicated = Member::new(tenant_id, "daniel@nongoose.com", "solarte");
+  let error = nongoose.save(&mut duplicated).await.unwrap_err();
+  assert_duplicated(
+    error,
+    "email, tenantId",
+    &format!("daniel@nongoose.com, {}", tenant_id),
+  );
+
+  // Rejected by the compound unique index
+  let nongoose = get_instance(false).await;
+
+  let mut duplicated = Member::new(tenant_id, "solarte@nongoose.com", "daniel");
+  let error = nongoose.save(&mut duplicated).await.unwrap_err();
+  assert_duplicated(
+    error,
+    "tenantId, username",
+    &format!("ObjectId('{}'), daniel", tenant_id),
+  );
+}
@@ -1,7 +1,11 @@
+#[cfg(feature = "sync")]
+use nongoose::blocking;
 use nongoose::{
   bson::{doc, oid::ObjectId},
-  Client, Nongoose, Schema, SchemaBefore,
+  Schema,
 };
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, Nongoose, SchemaBefore};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Schema, Serialize)]
@@ -23,11 +27,38 @@ impl Article {
   }
 }
 
-#[cfg_attr(feature = "tokio-runtime", async_trait::async_trait)]
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Article {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
 impl SchemaBefore for Article {}
 
-#[cfg(test)]
-fn get_instance() -> Nongoose {
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> blocking::Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  blocking::Nongoose::builder(client.database("nongoose_blocking"))
+    .add_schema::<Article>()
+    .build()
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> Nongoose {
   // Get database url.
   let url = match std::env::var("DATABASE_URL") {
     Ok(url) => url,
@@ -37,7 +68,7 @@ fn get_instance() -> Nongoose {
   };
 
   // Get MongoDB connection.
-  let client = match Client::with_uri_str(&url) {
+  let client = match Client::with_uri_str(&url).await {
     Ok(client) => client,
     Err(e) => {
       panic!("Error connecting to the database: {}", e);
@@ -51,13 +82,13 @@ fn get_instance() -> Nongoose {
 
 #[cfg(feature = "sync")]
 #[cfg_attr(feature = "sync", test)]
-fn update_many() {
-  let nongoose = get_instance();
+fn update_many_blocking() {
+  let nongoose = get_blocking_instance();
 
   // Upload data
   let mut article_one = Article::new("Title 1", "Content 1");
 
-  let article_one_saved = article_one.save();
+  let article_one_saved = nongoose.save(&mut article_one);
   assert!(article_one_saved.is_ok());
 
   let article_one_saved = article_one_saved.unwrap();
@@ -65,7 +96,7 @@ fn update_many() {
 
   let mut article_two = Article::new("Title 2", "Content 2");
 
-  let article_two_saved = article_two.save();
+  let article_two_saved = nongoose.save(&mut article_two);
   assert!(article_two_saved.is_ok());
 
   let article_two_saved = article_two_saved.unwrap();
@@ -109,12 +140,12 @@ fn update_many() {
 #[cfg(feature = "tokio-runtime")]
 #[cfg_attr(feature = "tokio-runtime", tokio::test)]
 async fn update_many() {
-  let nongoose = get_instance();
+  let nongoose = get_instance().await;
 
   // Upload data
   let mut article_one = Article::new("Title 1", "Content 1");
 
-  let article_one_saved = article_one.save().await;
+  let article_one_saved = nongoose.save(&mut article_one).await;
   assert!(article_one_saved.is_ok());
 
   let article_one_saved = article_one_saved.unwrap();
@@ -122,7 +153,7 @@ async fn update_many() {
 
   let mut article_two = Article::new("Title 2", "Content 2");
 
-  let article_two_saved = article_two.save().await;
+  let article_two_saved = nongoose.save(&mut article_two).await;
   assert!(article_two_saved.is_ok());
 
   let article_two_saved = article_two_saved.unwrap();
@@ -0,0 +1,232 @@
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{
+  bson::{doc, oid::ObjectId, Document},
+  Error, Schema, ValidationError,
+};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, Database, HookContext, Nongoose, SchemaBefore};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Schema, Serialize)]
+#[schema(name = "profiles_validation")]
+struct Profile {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  #[schema(min_length = 3, max_length = 16, regex = "^[a-z0-9_]+$")]
+  pub username: String,
+  #[schema(min = 0, max = 150)]
+  pub age: u8,
+  #[schema(min = 0, max = "1.5")]
+  pub score: f64,
+  #[schema(one_of = ["admin", "user"])]
+  pub role: String,
+  #[schema(required, max_length = 3)]
+  #[serde(rename = "displayName")]
+  pub display_name: Option<String>,
+  #[schema(max_length = 2)]
+  pub tags: Vec<String>,
+}
+
+impl Profile {
+  pub fn new(username: &str) -> Self {
+    Self {
+      id: ObjectId::new(),
+      username: String::from(username),
+      age: 30,
+      score: 1.0,
+      role: String::from("user"),
+      display_name: Some(String::from("Dan")),
+      tags: Vec::new(),
+    }
+  }
+}
+
+/// Collection where `before_create()` registers the created profiles.
+const AUDIT_COLLECTION: &str = "profiles_validation_audit";
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Profile {
+  fn before_create(&mut self, ctx: &mut blocking::HookContext<'_>) -> nongoose::Result<()> {
+    ctx
+      .database()
+      .collection::<Document>(AUDIT_COLLECTION)
+      .insert_one(doc! { "profile": self.id }, None)?;
+
+    Ok(())
+  }
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Profile {
+  async fn before_create(&mut self, ctx: &mut HookContext<'_>) -> nongoose::Result<()> {
+    ctx
+      .database()
+      .collection::<Document>(AUDIT_COLLECTION)
+      .insert_one(doc! { "profile": self.id }, None)
+      .await?;
+
+    Ok(())
+  }
+}
+
+fn invalid_profile() -> Profile {
+  let mut profile = Profile::new("Dan Solarte");
+  profile.age = 151;
+  profile.score = 2.0;
+  profile.role = String::from("owner");
+  profile.display_name = None;
+  profile.tags = vec![String::from("a"), String::from("b"), String::from("c")];
+
+  profile
+}
+
+fn assert_invalid(error: Error) {
+  let errors = match error {
+    Error::Validation(errors) => errors,
+    error => panic!("Unexpected error: {}", error),
+  };
+
+  let rules = errors
+    .iter()
+    .map(|error| (error.field.as_str(), error.rule.as_str()))
+    .collect::<Vec<_>>();
+
+  assert_eq!(
+    rules,
+    vec![
+      ("username", "regex"),
+      ("age", "max"),
+      ("score", "max"),
+      ("role", "one_of"),
+      ("displayName", "required"),
+      ("tags", "max_length"),
+    ]
+  );
+
+  assert_eq!(
+    errors[3],
+    ValidationError::new("role", "one_of", "must be one of admin, user")
+  );
+}
+
+#[test]
+fn validate() {
+  assert!(Profile::new("dsolartec").validate().is_ok());
+
+  // Every failing rule is returned
+  assert_invalid(invalid_profile().validate().unwrap_err());
+
+  let mut profile = Profile::new("ds");
+  profile.display_name = Some(String::from("Daniel"));
+
+  let error = profile.validate().unwrap_err();
+  assert_eq!(
+    format!("{}", error),
+    "Validation error: username (min_length): length must be greater than or equal to 3, displayName \
+     (max_length): length must be less than or equal to 3"
+  );
+}
+
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> (blocking::Nongoose, blocking::Database) {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  let database = client.database("nongoose_blocking");
+  let nongoose = blocking::Nongoose::builder(database.clone())
+    .add_schema::<Profile>()
+    .build();
+
+  (nongoose, database)
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> (Nongoose, Database) {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match Client::with_uri_str(&url).await {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  let database = client.database("nongoose");
+  let nongoose = Nongoose::builder(database.clone())
+    .add_schema::<Profile>()
+    .build();
+
+  (nongoose, database)
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn validation_blocking() {
+  let (nongoose, database) = get_blocking_instance();
+
+  let mut profile = Profile::new("dsolartec");
+  assert!(nongoose.save(&mut profile).is_ok());
+
+  // The invalid document is not saved
+  let mut profile = invalid_profile();
+  assert_invalid(nongoose.save(&mut profile).unwrap_err());
+
+  let profile_found = nongoose.find_by_id::<Profile>(&profile.id);
+  assert!(profile_found.is_ok());
+  assert!(profile_found.unwrap().is_none());
+
+  // The document is validated before calling `before_create()`
+  let audit = database
+    .collection::<Document>(AUDIT_COLLECTION)
+    .count_documents(doc! { "profile": profile.id }, None);
+  assert!(audit.is_ok());
+  assert_eq!(audit.unwrap(), 0);
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn validation() {
+  let (nongoose, database) = get_instance().await;
+
+  let mut profile = Profile::new("dsolartec");
+  assert!(nongoose.save(&mut profile).await.is_ok());
+
+  // The invalid document is not saved
+  let mut profile = invalid_profile();
+  assert_invalid(nongoose.save(&mut profile).await.unwrap_err());
+
+  let profile_found = nongoose.find_by_id::<Profile>(&profile.id).await;
+  assert!(profile_found.is_ok());
+  assert!(profile_found.unwrap().is_none());
+
+  // The document is validated before calling `before_create()`
+  let audit = database
+    .collection::<Document>(AUDIT_COLLECTION)
+    .count_documents(doc! { "profile": profile.id }, None)
+    .await;
+  assert!(audit.is_ok());
+  assert_eq!(audit.unwrap(), 0);
+}
@@ -0,0 +1,315 @@
+#[cfg(feature = "sync")]
+use nongoose::blocking;
+use nongoose::{bson::oid::ObjectId, Error, Schema, ValidationError};
+#[cfg(feature = "tokio-runtime")]
+use nongoose::{Client, HookContext, Nongoose, SchemaBefore};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Schema, Serialize)]
+#[schema(name = "teams_validators")]
+struct Team {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  pub name: String,
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Team {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Team {}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Schema, Serialize)]
+#[schema(name = "events_validators", validate = "dates_in_order")]
+#[cfg_attr(feature = "tokio-runtime", schema(validate_async = "name_available"))]
+struct Event {
+  #[schema(id)]
+  #[serde(rename = "_id")]
+  pub id: ObjectId,
+  #[schema(validate = "not_blank")]
+  pub name: String,
+  #[cfg_attr(feature = "tokio-runtime", schema(validate_async = "team_exists"))]
+  #[serde(rename = "teamId")]
+  pub team_id: ObjectId,
+  #[schema(validate = "validators::positive")]
+  pub seats: Option<u32>,
+  pub starts: i64,
+  pub ends: i64,
+}
+
+impl Event {
+  pub fn new(name: &str, team_id: ObjectId) -> Self {
+    Self {
+      id: ObjectId::new(),
+      name: String::from(name),
+      team_id,
+      seats: Some(10),
+      starts: 10,
+      ends: 20,
+    }
+  }
+}
+
+#[cfg(feature = "sync")]
+impl blocking::SchemaBefore for Event {}
+
+#[cfg(feature = "tokio-runtime")]
+#[async_trait::async_trait]
+impl SchemaBefore for Event {}
+
+fn not_blank(value: &str) -> Result<(), String> {
+  if value.trim().is_empty() {
+    Err(String::from("must not be blank"))
+  } else {
+    Ok(())
+  }
+}
+
+mod validators {
+  pub fn positive(value: &u32) -> Result<(), String> {
+    if *value > 0 {
+      Ok(())
+    } else {
+      Err(String::from("must be positive"))
+    }
+  }
+}
+
+fn dates_in_order(event: &Event) -> Result<(), ValidationError> {
+  if event.starts < event.ends {
+    Ok(())
+  } else {
+    Err(ValidationError::new(
+      "ends",
+      "dates_in_order",
+      "must be after starts",
+    ))
+  }
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn team_exists(team_id: &ObjectId, ctx: &mut HookContext<'_>) -> Result<(), String> {
+  match ctx.nongoose().find_by_id::<Team>(team_id).await {
+    Ok(Some(_)) => Ok(()),
+    Ok(None) => Err(String::from("team not found")),
+    Err(error) => Err(error.to_string()),
+  }
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn name_available(event: &Event, ctx: &mut HookContext<'_>) -> Result<(), ValidationError> {
+  let events = ctx
+    .nongoose()
+    .find::<Event>(
+      nongoose::bson::doc! { "name": &event.name, "_id": { "$ne": event.id } },
+      None,
+    )
+    .await;
+
+  match events {
+    Ok(events) if events.is_empty() => Ok(()),
+    Ok(_) => Err(ValidationError::new(
+      "name",
+      "name_available",
+      "is already used",
+    )),
+    Err(error) => Err(ValidationError::new(
+      "name",
+      "name_available",
+      error.to_string(),
+    )),
+  }
+}
+
+fn invalid_event(team_id: ObjectId) -> Event {
+  let mut event = Event::new(" ", team_id);
+  event.seats = Some(0);
+  event.starts = 30;
+
+  event
+}
+
+fn assert_rules(error: Error, expected: Vec<(&str, &str)>) {
+  let errors = match error {
+    Error::Validation(errors) => errors,
+    error => panic!("Unexpected error: {}", error),
+  };
+
+  let rules = errors
+    .iter()
+    .map(|error| (error.field.as_str(), error.rule.as_str()))
+    .collect::<Vec<_>>();
+
+  assert_eq!(rules, expected);
+}
+
+#[test]
+fn validate() {
+  assert!(Event::new("Launch", ObjectId::new()).validate().is_ok());
+
+  // The validators are called with the value of the field (if it is not `None`) and with the document
+  let mut event = invalid_event(ObjectId::new());
+  assert_rules(
+    event.validate().unwrap_err(),
+    vec![
+      ("name", "not_blank"),
+      ("seats", "validators::positive"),
+      ("ends", "dates_in_order"),
+    ],
+  );
+
+  event.seats = None;
+  assert_eq!(
+    format!("{}", event.validate().unwrap_err()),
+    "Validation error: name (not_blank): must not be blank, ends (dates_in_order): must be after starts"
+  );
+}
+
+#[cfg(feature = "sync")]
+fn get_blocking_instance() -> blocking::Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match blocking::Client::with_uri_str(&url) {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  blocking::Nongoose::builder(client.database("nongoose_blocking"))
+    .add_schema::<Team>()
+    .add_schema::<Event>()
+    .build()
+}
+
+#[cfg(feature = "tokio-runtime")]
+async fn get_instance() -> Nongoose {
+  // Get database url.
+  let url = match std::env::var("DATABASE_URL") {
+    Ok(url) => url,
+    Err(_) => {
+      panic!("Cannot find `DATABASE_URL` on the environment variables.");
+    }
+  };
+
+  // Get MongoDB connection.
+  let client = match Client::with_uri_str(&url).await {
+    Ok(client) => client,
+    Err(e) => {
+      panic!("Error connecting to the database: {}", e);
+    }
+  };
+
+  Nongoose::builder(client.database("nongoose"))
+    .client(client)
+    .add_schema::<Team>()
+    .add_schema::<Event>()
+    .build()
+}
+
+#[cfg(feature = "sync")]
+#[cfg_attr(feature = "sync", test)]
+fn validators_blocking() {
+  let nongoose = get_blocking_instance();
+
+  // The blocking API cannot call the async validators, so it does not save the documents of their Schema
+  let mut event = Event::new("Launch", ObjectId::new());
+  let saved = nongoose.save(&mut event);
+
+  #[cfg(feature = "tokio-runtime")]
+  {
+    assert!(matches!(
+      saved.unwrap_err(),
+      Error::AsyncValidatorsNotSupported { collection } if collection == "events_validators"
+    ));
+
+    let event_found = nongoose.find_by_id::<Event>(&event.id);
+    assert!(event_found.is_ok());
+    assert!(event_found.unwrap().is_none());
+  }
+
+  #[cfg(not(feature = "tokio-runtime"))]
+  assert!(saved.is_ok());
+
+  // The invalid document is not saved
+  let mut event = invalid_event(ObjectId::new());
+  assert_rules(
+    nongoose.save(&mut event).unwrap_err(),
+    vec![
+      ("name", "not_blank"),
+      ("seats", "validators::positive"),
+      ("ends", "dates_in_order"),
+    ],
+  );
+
+  let event_found = nongoose.find_by_id::<Event>(&event.id);
+  assert!(event_found.is_ok());
+  assert!(event_found.unwrap().is_none());
+}
+
+#[cfg(feature = "tokio-runtime")]
+#[cfg_attr(feature = "tokio-runtime", tokio::test)]
+async fn validators() {
+  let nongoose = get_instance().await;
+
+  let mut team = Team {
+    id: ObjectId::new(),
+    name: String::from("Nongoose"),
+  };
+  assert!(nongoose.save(&mut team).await.is_ok());
+
+  let name = format!("Launch {}", ObjectId::new());
+
+  let mut event = Event::new(&name, team.id);
+  assert!(nongoose.save(&mut event).await.is_ok());
+
+  // Updating the same document does not conflict with its own name
+  assert!(nongoose.save(&mut event).await.is_ok());
+
+  // The async validators are only called once the other rules pass
+  let mut event = invalid_event(ObjectId::new());
+  assert_rules(
+    nongoose.save(&mut event).await.unwrap_err(),
+    vec![
+      ("name", "not_blank"),
+      ("seats", "validators::positive"),
+      ("ends", "dates_in_order"),
+    ],
+  );
+
+  let mut event = Event::new(&format!("Launch {}", ObjectId::new()), ObjectId::new());
+  assert_rules(
+    nongoose.save(&mut event).await.unwrap_err(),
+    vec![("teamId", "team_exists")],
+  );
+
+  // The async validators can query the database
+  let mut event = Event::new(&name, team.id);
+  assert_rules(
+    nongoose.save(&mut event).await.unwrap_err(),
+    vec![("name", "name_available")],
+  );
+
+  let event_found = nongoose.find_by_id::<Event>(&event.id).await;
+  assert!(event_found.is_ok());
+  assert!(event_found.unwrap().is_none());
+
+  // Inside a transaction
+  let event = Event::new(&format!("Launch {}", ObjectId::new()), ObjectId::new());
+  let result = nongoose
+    .transaction(|tx| {
+      let event = event.clone();
+      async move { tx.create(&event).await }
+    })
+    .await;
+  assert_rules(result.unwrap_err(), vec![("teamId", "team_exists")]);
+}